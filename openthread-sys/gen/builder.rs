@@ -378,6 +378,22 @@ impl OpenThreadBuilder {
             config.define(setting.knob, if setting.on { "ON" } else { "OFF" });
         }
 
+        // `OT_MDNS` only compiles the mDNS module itself; its public `otMdns*`
+        // API (which the `openthread` crate wraps) is gated separately.
+        if features::mdns_active() {
+            config
+                .cflag("-DOPENTHREAD_CONFIG_MULTICAST_DNS_PUBLIC_API_ENABLE=1")
+                .cxxflag("-DOPENTHREAD_CONFIG_MULTICAST_DNS_PUBLIC_API_ENABLE=1");
+        }
+
+        // The NAT64 prefix manager (publishing a NAT64 prefix to the Network
+        // Data) is part of the Border Routing Manager, and needs both.
+        if features::nat64_border_routing_active() {
+            config
+                .cflag("-DOPENTHREAD_CONFIG_NAT64_BORDER_ROUTING_ENABLE=1")
+                .cxxflag("-DOPENTHREAD_CONFIG_NAT64_BORDER_ROUTING_ENABLE=1");
+        }
+
         // The C CLI is a build-structure toggle rather than an `OT_*` config
         // knob (see `CMakeLists.txt`): `ON` builds the real CLI libraries plus
         // the `cli_shim.c` output bridge in `libsupport.a`; `OFF` (the
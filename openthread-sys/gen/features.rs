@@ -191,6 +191,22 @@ pub fn dtls_active() -> bool {
         .any(|f| std::env::var_os(format!("CARGO_FEATURE_{f}")).is_some())
 }
 
+/// Whether both the `nat64` and the `border-routing` features are active. The
+/// NAT64 prefix manager of the Border Routing Manager has no `OT_*` option of
+/// its own and is enabled when both are (see builder).
+pub fn nat64_border_routing_active() -> bool {
+    ["NAT64", "BORDER_ROUTING"]
+        .iter()
+        .all(|f| std::env::var_os(format!("CARGO_FEATURE_{f}")).is_some())
+}
+
+/// Whether the `mdns` feature is active. OpenThread compiles its mDNS module
+/// with `OT_MDNS`, but keeps the `otMdns*` public API out unless it is asked
+/// for explicitly (see builder).
+pub fn mdns_active() -> bool {
+    std::env::var_os("CARGO_FEATURE_MDNS").is_some()
+}
+
 /// Whether the `cli` feature is active: build OpenThread's C CLI libraries
 /// (normally stubbed out; see `CMakeLists.txt`) and the `cli_shim.c` output
 /// bridge in `libsupport.a`. Not an `OT_*` config knob but a build-structure
@@ -50,6 +50,62 @@ extern "C" {
     pub fn otr_cli_init(instance: *mut otInstance, context: *mut core::ffi::c_void);
 }
 
+// The mDNS socket platform API (`openthread/platform/mdns_socket.h`): the
+// `otPlatMdnsHandle*` callbacks are implemented by OpenThread and invoked by the
+// `openthread` crate when it receives mDNS traffic (or learns of host address
+// changes) on the infrastructure network interface. Declared by hand, like
+// `otr_cli_init` above, as the platform header is not part of the bindings.
+#[cfg(feature = "mdns")]
+#[allow(non_snake_case)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otPlatMdnsAddressInfo {
+    pub mAddress: otIp6Address,
+    pub mPort: u16,
+    pub mInfraIfIndex: u32,
+}
+
+#[cfg(feature = "mdns")]
+extern "C" {
+    pub fn otPlatMdnsHandleReceive(
+        aInstance: *mut otInstance,
+        aMessage: *mut otMessage,
+        aIsUnicast: bool,
+        aAddress: *const otPlatMdnsAddressInfo,
+    );
+
+    pub fn otPlatMdnsHandleHostAddressEvent(
+        aInstance: *mut otInstance,
+        aAddress: *const otIp6Address,
+        aAdded: bool,
+        aInfraIfIndex: u32,
+    );
+
+    pub fn otPlatMdnsHandleHostAddressRemoveAll(aInstance: *mut otInstance, aInfraIfIndex: u32);
+}
+
+// The infrastructure interface platform API (`openthread/platform/infra_if.h`)
+// of the Routing Manager: the `otPlatInfraIf*` callbacks below are implemented
+// by OpenThread and invoked by the `openthread` crate when it receives ICMPv6
+// Neighbor Discovery messages on (or learns of state changes of) the
+// infrastructure network interface. Declared by hand, like the mDNS ones above.
+#[cfg(feature = "border-routing")]
+extern "C" {
+    pub fn otPlatInfraIfRecvIcmp6Nd(
+        aInstance: *mut otInstance,
+        aInfraIfIndex: u32,
+        aSrcAddress: *const otIp6Address,
+        aBuffer: *const u8,
+        aBufferLength: u16,
+    );
+
+    pub fn otPlatInfraIfStateChanged(
+        aInstance: *mut otInstance,
+        aInfraIfIndex: u32,
+        aIsRunning: bool,
+    ) -> otError;
+}
+
 #[allow(
     non_camel_case_types,
     non_snake_case,
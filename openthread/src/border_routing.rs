@@ -0,0 +1,557 @@
+//! Border Routing Manager API (`OPENTHREAD_CONFIG_BORDER_ROUTING_ENABLE` / the `border-routing` feature).
+//!
+//! OpenThread's Routing Manager makes a Thread Border Router provide bi-directional IPv6
+//! connectivity between the Thread network and the *infrastructure* network (i.e. the non-Thread
+//! link of the border router, typically Ethernet or Wi-Fi): it publishes an OMR (Off-Mesh Routable)
+//! prefix in the Thread Network Data, advertises it as a route on the infrastructure link (and an
+//! on-link prefix if the link has none) via ICMPv6 Router Advertisements, and tracks the prefixes
+//! advertised by the other routers on the link. It does not do any I/O by itself: it sends and
+//! receives ICMPv6 Neighbor Discovery messages via the `otPlatInfraIf*` platform API, which is
+//! bridged here to the [`InfraIf`] trait.
+//!
+//! Usage:
+//! - Run [`OpenThread::run_infra_if`] with an [`InfraIf`] implementation, concurrently with
+//!   [`OpenThread::run`]. With the `std` feature, [`RawInfraIf`] is a ready-made implementation
+//!   over a raw ICMPv6 socket of the host (Linux).
+//! - Initialize the Routing Manager on the infrastructure network interface with
+//!   [`OpenThread::border_routing_init`], and keep the interface state up to date with
+//!   [`OpenThread::border_routing_set_infra_if_running`] and
+//!   [`OpenThread::border_routing_set_infra_if_addrs`].
+//! - Enable the Routing Manager with [`OpenThread::border_routing_set_enabled`]. It starts
+//!   operating once the device is attached and the infrastructure network interface is running.
+
+use core::fmt::Debug;
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+use core::task::Poll;
+
+use embassy_futures::select::{select, Either};
+
+use crate::signal::Signal;
+use crate::sys::{
+    otBorderRoutingGetFavoredOmrPrefix, otBorderRoutingGetFavoredOnLinkPrefix,
+    otBorderRoutingGetNextPrefixTableEntry, otBorderRoutingGetNextRouterEntry,
+    otBorderRoutingGetOmrPrefix, otBorderRoutingGetOnLinkPrefix, otBorderRoutingGetState,
+    otBorderRoutingInit, otBorderRoutingPrefixTableEntry, otBorderRoutingPrefixTableInitIterator,
+    otBorderRoutingPrefixTableIterator, otBorderRoutingRouterEntry, otBorderRoutingSetEnabled,
+    otBorderRoutingState, otBorderRoutingState_OT_BORDER_ROUTING_STATE_DISABLED,
+    otBorderRoutingState_OT_BORDER_ROUTING_STATE_RUNNING,
+    otBorderRoutingState_OT_BORDER_ROUTING_STATE_STOPPED,
+    otBorderRoutingState_OT_BORDER_ROUTING_STATE_UNINITIALIZED, otError, otError_OT_ERROR_NONE,
+    otError_OT_ERROR_NO_BUFS, otIp6Address, otIp6Prefix, otPlatInfraIfRecvIcmp6Nd,
+    otPlatInfraIfStateChanged, otRoutePreference,
+};
+use crate::{ot, to_ot_ip6_addr, OpenThread, OtContext, OtError, OtRoutePreference};
+
+#[cfg(feature = "std")]
+mod raw;
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub use raw::RawInfraIf;
+
+/// The maximum size of an ICMPv6 Neighbor Discovery message sent or received by
+/// [`OpenThread::run_infra_if`] (the IPv6 minimum MTU).
+pub const INFRA_IF_MAX_MESSAGE_SIZE: usize = 1280;
+
+/// The maximum number of outgoing ICMPv6 Neighbor Discovery messages which can be queued
+/// by OpenThread, waiting to be sent by [`OpenThread::run_infra_if`].
+///
+/// Messages arriving while the queue is full are dropped.
+pub const INFRA_IF_MAX_PENDING_TX: usize = 2;
+
+/// The maximum number of addresses of the infrastructure network interface
+/// (see [`OpenThread::border_routing_set_infra_if_addrs`]).
+pub const INFRA_IF_MAX_ADDRS: usize = 8;
+
+/// The infrastructure network interface of a border router, for sending and receiving
+/// ICMPv6 Neighbor Discovery messages (Router Solicitations and Advertisements,
+/// Neighbor Solicitations and Advertisements).
+///
+/// Driven by [`OpenThread::run_infra_if`]. With the `std` feature, [`RawInfraIf`] is a ready-made
+/// implementation over a raw ICMPv6 socket of the host.
+///
+/// The state of the interface (running or not, and its addresses) is reported separately, with
+/// [`OpenThread::border_routing_set_infra_if_running`] and [`OpenThread::border_routing_set_infra_if_addrs`].
+pub trait InfraIf {
+    /// The error type for interface operations.
+    type Error: Debug;
+
+    /// Send an ICMPv6 Neighbor Discovery message on the interface.
+    ///
+    /// The message must be sent with an IPv6 hop limit of 255, and with one of the link-local
+    /// addresses of the interface as its source.
+    ///
+    /// Arguments:
+    /// - `dest`: The destination address of the message (unicast or multicast).
+    /// - `data`: The ICMPv6 message, starting with its ICMPv6 header. The checksum
+    ///   is left for the implementation (i.e. the host IPv6 stack) to compute.
+    async fn send(&mut self, dest: Ipv6Addr, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Receive an ICMPv6 Neighbor Discovery message from the interface.
+    ///
+    /// The implementation should only report messages received with an IPv6 hop limit of 255,
+    /// but may report ICMPv6 messages of other types, which are ignored.
+    ///
+    /// The returned future must be cancel-safe: [`OpenThread::run_infra_if`] drops it whenever
+    /// OpenThread has an ICMPv6 message to send, and calls `recv` again afterwards. Dropping
+    /// the future must therefore not lose a message which had already been received by the
+    /// implementation.
+    ///
+    /// Arguments:
+    /// - `buf`: A buffer where the received ICMPv6 message (starting with its ICMPv6 header) will be stored.
+    ///
+    /// Returns:
+    /// - The length of the received message and its source address.
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, Ipv6Addr), Self::Error>;
+}
+
+impl<T> InfraIf for &mut T
+where
+    T: InfraIf,
+{
+    type Error = T::Error;
+
+    async fn send(&mut self, dest: Ipv6Addr, data: &[u8]) -> Result<(), Self::Error> {
+        T::send(self, dest, data).await
+    }
+
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, Ipv6Addr), Self::Error> {
+        T::recv(self, buf).await
+    }
+}
+
+/// The state of the Routing Manager.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BorderRoutingState {
+    /// Not initialized (see [`OpenThread::border_routing_init`]).
+    Uninitialized,
+    /// Disabled.
+    Disabled,
+    /// Enabled, but not operating (e.g. the device is detached,
+    /// or the infrastructure network interface is not running).
+    Stopped,
+    /// Enabled and operating.
+    Running,
+    /// Any other state.
+    Other(otBorderRoutingState),
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otBorderRoutingState> for BorderRoutingState {
+    fn from(value: otBorderRoutingState) -> Self {
+        match value {
+            otBorderRoutingState_OT_BORDER_ROUTING_STATE_UNINITIALIZED => Self::Uninitialized,
+            otBorderRoutingState_OT_BORDER_ROUTING_STATE_DISABLED => Self::Disabled,
+            otBorderRoutingState_OT_BORDER_ROUTING_STATE_STOPPED => Self::Stopped,
+            otBorderRoutingState_OT_BORDER_ROUTING_STATE_RUNNING => Self::Running,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A router on the infrastructure link, as discovered by the Routing Manager
+/// from its Router Advertisements.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BorderRoutingRouter {
+    /// The IPv6 address of the router.
+    pub address: Ipv6Addr,
+    /// Milliseconds since the last message received from the router.
+    pub msec_since_last_update: u32,
+    /// Seconds since the router was first discovered.
+    pub age: u32,
+    /// The Managed Address Configuration (`M`) flag of its Router Advertisements.
+    pub managed_address_config: bool,
+    /// The Other Configuration (`O`) flag of its Router Advertisements.
+    pub other_config: bool,
+    /// Whether the router is a Stub Router (`SNAC`).
+    pub snac_router: bool,
+    /// Whether the router is this device.
+    pub is_local_device: bool,
+    /// Whether the router is reachable.
+    pub is_reachable: bool,
+    /// Whether the router is a peer border router of the same Thread network.
+    pub is_peer_br: bool,
+}
+
+impl BorderRoutingRouter {
+    fn from_ot(entry: &otBorderRoutingRouterEntry) -> Self {
+        Self {
+            address: unsafe { entry.mAddress.mFields.m8 }.into(),
+            msec_since_last_update: entry.mMsecSinceLastUpdate,
+            age: entry.mAge,
+            managed_address_config: entry.mManagedAddressConfigFlag(),
+            other_config: entry.mOtherConfigFlag(),
+            snac_router: entry.mSnacRouterFlag(),
+            is_local_device: entry.mIsLocalDevice(),
+            is_reachable: entry.mIsReachable(),
+            is_peer_br: entry.mIsPeerBr(),
+        }
+    }
+}
+
+/// A prefix advertised on the infrastructure link, as discovered by the Routing Manager
+/// from the Router Advertisements of the routers on the link.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BorderRoutingPrefix {
+    /// The router advertising the prefix.
+    pub router: BorderRoutingRouter,
+    /// The IPv6 prefix.
+    pub prefix: (Ipv6Addr, u8),
+    /// Whether this is an on-link prefix (as opposed to a route prefix).
+    pub on_link: bool,
+    /// Milliseconds since the last update of the prefix.
+    pub msec_since_last_update: u32,
+    /// The valid lifetime of the prefix, in seconds.
+    pub valid_lifetime: u32,
+    /// The preferred lifetime of the prefix (for an on-link prefix), in seconds.
+    pub preferred_lifetime: u32,
+    /// The route preference (for a route prefix).
+    pub route_preference: OtRoutePreference,
+}
+
+impl BorderRoutingPrefix {
+    fn from_ot(entry: &otBorderRoutingPrefixTableEntry) -> Self {
+        Self {
+            router: BorderRoutingRouter::from_ot(&entry.mRouter),
+            prefix: prefix_from_ot(&entry.mPrefix),
+            on_link: entry.mIsOnLink,
+            msec_since_last_update: entry.mMsecSinceLastUpdate,
+            valid_lifetime: entry.mValidLifetime,
+            preferred_lifetime: entry.mPreferredLifetime,
+            route_preference: OtRoutePreference::from_ot_int(entry.mRoutePreference as _),
+        }
+    }
+}
+
+/// The infrastructure interface state of `OtState`.
+pub(crate) struct InfraIfState {
+    /// The infrastructure network interface index the Routing Manager was initialized with.
+    infra_if_index: u32,
+    /// The addresses of the infrastructure network interface.
+    addrs: heapless::Vec<Ipv6Addr, INFRA_IF_MAX_ADDRS>,
+    /// Outgoing messages waiting to be sent by `run_infra_if`, with their destinations.
+    tx: heapless::Deque<
+        (Ipv6Addr, heapless::Vec<u8, INFRA_IF_MAX_MESSAGE_SIZE>),
+        INFRA_IF_MAX_PENDING_TX,
+    >,
+    /// Signaled when a new message is queued in `tx`.
+    tx_queued: Signal<()>,
+}
+
+impl InfraIfState {
+    pub(crate) const fn new() -> Self {
+        Self {
+            infra_if_index: 0,
+            addrs: heapless::Vec::new(),
+            tx: heapless::Deque::new(),
+            tx_queued: Signal::new(),
+        }
+    }
+}
+
+impl OpenThread<'_> {
+    /// Run the infrastructure network interface I/O of OpenThread with the provided interface.
+    ///
+    /// Needs to run concurrently with [`OpenThread::run`] for the Routing Manager to operate.
+    ///
+    /// Arguments:
+    /// - `infra_if`: The infrastructure network interface to send and receive ICMPv6 Neighbor
+    ///   Discovery messages with.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn run_infra_if<I>(&self, mut infra_if: I) -> !
+    where
+        I: InfraIf,
+    {
+        let mut buf = [0; INFRA_IF_MAX_MESSAGE_SIZE];
+
+        loop {
+            if let Some((dest, len)) = self.infra_if_take_tx(&mut buf) {
+                if let Err(e) = infra_if.send(dest, &buf[..len]).await {
+                    warn!("Sending ICMPv6 ND message failed: {:?}", debug2format!(e));
+                }
+
+                continue;
+            }
+
+            match select(self.infra_if_wait_tx(), infra_if.recv(&mut buf)).await {
+                Either::First(_) => (),
+                Either::Second(Ok((len, src))) => self.infra_if_rx(&buf[..len], src),
+                Either::Second(Err(e)) => {
+                    warn!("Receiving ICMPv6 ND message failed: {:?}", debug2format!(e))
+                }
+            }
+        }
+    }
+
+    /// Initialize the Routing Manager on the infrastructure network interface.
+    ///
+    /// Must be called once, before enabling the Routing Manager with
+    /// [`OpenThread::border_routing_set_enabled`].
+    ///
+    /// Arguments:
+    /// - `infra_if_index`: The index of the infrastructure network interface.
+    /// - `running`: Whether the infrastructure network interface is running
+    ///   (see [`OpenThread::border_routing_set_infra_if_running`]).
+    pub fn border_routing_init(&self, infra_if_index: u32, running: bool) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.ot.infra_if.infra_if_index = infra_if_index;
+
+        ot!(unsafe { otBorderRoutingInit(state.ot.instance, infra_if_index, running) })
+    }
+
+    /// Report whether the infrastructure network interface is running,
+    /// i.e. up and able to send and receive IPv6 traffic.
+    ///
+    /// Should be called whenever the state of the interface changes.
+    pub fn border_routing_set_infra_if_running(&self, running: bool) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe {
+            otPlatInfraIfStateChanged(state.ot.instance, state.ot.infra_if.infra_if_index, running)
+        })
+    }
+
+    /// Report the IPv6 addresses of the infrastructure network interface.
+    ///
+    /// The Routing Manager uses these to tell its own Router Advertisements apart from the
+    /// ones of the other routers on the link. Should be called whenever the addresses of the
+    /// interface change.
+    ///
+    /// Arguments:
+    /// - `addrs`: The addresses of the infrastructure network interface
+    ///   (max [`INFRA_IF_MAX_ADDRS`]).
+    pub fn border_routing_set_infra_if_addrs(&self, addrs: &[Ipv6Addr]) -> Result<(), OtError> {
+        let addrs =
+            heapless::Vec::from_slice(addrs).map_err(|_| OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+        self.activate().state().ot.infra_if.addrs = addrs;
+
+        Ok(())
+    }
+
+    /// Enable or disable the Routing Manager.
+    ///
+    /// Arguments:
+    /// - `enable`: `true` to enable the Routing Manager, `false` to disable it.
+    pub fn border_routing_set_enabled(&self, enable: bool) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRoutingSetEnabled(state.ot.instance, enable) })
+    }
+
+    /// Return the state of the Routing Manager.
+    pub fn border_routing_state(&self) -> BorderRoutingState {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otBorderRoutingGetState(state.ot.instance) }.into()
+    }
+
+    /// Return the local OMR (Off-Mesh Routable) prefix of this border router.
+    ///
+    /// The prefix is randomly generated and persisted; it is only published in the Network Data
+    /// when no better OMR prefix is present there (see [`OpenThread::border_routing_favored_omr_prefix`]).
+    pub fn border_routing_omr_prefix(&self) -> Result<(Ipv6Addr, u8), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut prefix = otIp6Prefix::default();
+
+        ot!(unsafe { otBorderRoutingGetOmrPrefix(state.ot.instance, &mut prefix) })?;
+
+        Ok(prefix_from_ot(&prefix))
+    }
+
+    /// Return the currently favored OMR prefix of the Thread network, and its preference.
+    ///
+    /// The favored OMR prefix can be discovered from the Network Data, or be the local
+    /// OMR prefix of this border router.
+    pub fn border_routing_favored_omr_prefix(
+        &self,
+    ) -> Result<((Ipv6Addr, u8), OtRoutePreference), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut prefix = otIp6Prefix::default();
+        let mut preference: otRoutePreference = 0;
+
+        ot!(unsafe {
+            otBorderRoutingGetFavoredOmrPrefix(state.ot.instance, &mut prefix, &mut preference)
+        })?;
+
+        Ok((
+            prefix_from_ot(&prefix),
+            OtRoutePreference::from_ot_int(preference as _),
+        ))
+    }
+
+    /// Return the local on-link prefix of this border router for the infrastructure link.
+    ///
+    /// The prefix is randomly generated and persisted; it is only advertised on the
+    /// infrastructure link when no other router advertises a usable on-link prefix there.
+    pub fn border_routing_on_link_prefix(&self) -> Result<(Ipv6Addr, u8), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut prefix = otIp6Prefix::default();
+
+        ot!(unsafe { otBorderRoutingGetOnLinkPrefix(state.ot.instance, &mut prefix) })?;
+
+        Ok(prefix_from_ot(&prefix))
+    }
+
+    /// Return the currently favored on-link prefix of the infrastructure link.
+    ///
+    /// The favored on-link prefix can be discovered on the infrastructure link, or be the
+    /// local on-link prefix of this border router.
+    pub fn border_routing_favored_on_link_prefix(&self) -> Result<(Ipv6Addr, u8), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut prefix = otIp6Prefix::default();
+
+        ot!(unsafe { otBorderRoutingGetFavoredOnLinkPrefix(state.ot.instance, &mut prefix) })?;
+
+        Ok(prefix_from_ot(&prefix))
+    }
+
+    /// Gets the prefixes discovered on the infrastructure link from Router Advertisements.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each discovered prefix. Once called for all
+    ///   prefixes, the closure will be called with `None`.
+    pub fn border_routing_prefixes<F>(&self, mut f: F)
+    where
+        F: FnMut(Option<&BorderRoutingPrefix>),
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut iter = otBorderRoutingPrefixTableIterator::default();
+        let mut entry = otBorderRoutingPrefixTableEntry::default();
+
+        unsafe { otBorderRoutingPrefixTableInitIterator(instance, &mut iter) };
+
+        while unsafe { otBorderRoutingGetNextPrefixTableEntry(instance, &mut iter, &mut entry) }
+            == otError_OT_ERROR_NONE
+        {
+            f(Some(&BorderRoutingPrefix::from_ot(&entry)));
+        }
+
+        f(None);
+    }
+
+    /// Gets the routers discovered on the infrastructure link from Router Advertisements.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each discovered router. Once called for all
+    ///   routers, the closure will be called with `None`.
+    pub fn border_routing_routers<F>(&self, mut f: F)
+    where
+        F: FnMut(Option<&BorderRoutingRouter>),
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut iter = otBorderRoutingPrefixTableIterator::default();
+        let mut entry = otBorderRoutingRouterEntry::default();
+
+        unsafe { otBorderRoutingPrefixTableInitIterator(instance, &mut iter) };
+
+        while unsafe { otBorderRoutingGetNextRouterEntry(instance, &mut iter, &mut entry) }
+            == otError_OT_ERROR_NONE
+        {
+            f(Some(&BorderRoutingRouter::from_ot(&entry)));
+        }
+
+        f(None);
+    }
+
+    async fn infra_if_wait_tx(&self) {
+        poll_fn(move |cx| {
+            let mut ot = self.activate();
+            let infra_if = &mut ot.state().ot.infra_if;
+
+            if !infra_if.tx.is_empty() {
+                return Poll::Ready(());
+            }
+
+            infra_if.tx_queued.reset();
+            let _ = infra_if.tx_queued.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn infra_if_take_tx(&self, buf: &mut [u8]) -> Option<(Ipv6Addr, usize)> {
+        let mut ot = self.activate();
+        let (dest, data) = ot.state().ot.infra_if.tx.pop_front()?;
+
+        buf[..data.len()].copy_from_slice(&data);
+
+        Some((dest, data.len()))
+    }
+
+    fn infra_if_rx(&self, data: &[u8], src: Ipv6Addr) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let src = to_ot_ip6_addr(src);
+
+        unsafe {
+            otPlatInfraIfRecvIcmp6Nd(
+                state.ot.instance,
+                state.ot.infra_if.infra_if_index,
+                &src,
+                data.as_ptr(),
+                data.len() as _,
+            );
+        }
+    }
+}
+
+impl OtContext<'_> {
+    pub(crate) fn plat_infra_if_has_address(&mut self, address: &otIp6Address) -> bool {
+        let address: Ipv6Addr = unsafe { address.mFields.m8 }.into();
+
+        self.state().ot.infra_if.addrs.contains(&address)
+    }
+
+    pub(crate) fn plat_infra_if_send(&mut self, dest: &otIp6Address, data: &[u8]) -> otError {
+        let infra_if = &mut self.state().ot.infra_if;
+
+        let Ok(data) = heapless::Vec::from_slice(data) else {
+            warn!("Dropping TX ICMPv6 ND message, too large: {}", data.len());
+            return otError_OT_ERROR_NO_BUFS;
+        };
+
+        if infra_if
+            .tx
+            .push_back((unsafe { dest.mFields.m8 }.into(), data))
+            .is_err()
+        {
+            warn!("Dropping TX ICMPv6 ND message, buffer full");
+            return otError_OT_ERROR_NO_BUFS;
+        }
+
+        infra_if.tx_queued.signal(());
+
+        otError_OT_ERROR_NONE
+    }
+}
+
+fn prefix_from_ot(prefix: &otIp6Prefix) -> (Ipv6Addr, u8) {
+    (unsafe { prefix.mPrefix.mFields.m8 }.into(), prefix.mLength)
+}
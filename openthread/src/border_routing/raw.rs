@@ -0,0 +1,179 @@
+//! [`RawInfraIf`]: a host (`std`) [`InfraIf`] over a raw ICMPv6 socket of the host, so
+//! that the Routing Manager of OpenThread can be exercised on a Linux host.
+//!
+//! Mirrors the infrastructure interface of OpenThread's POSIX platform: a raw ICMPv6 socket
+//! joined to the all-routers multicast group on the infrastructure network interface, which
+//! sends with a hop limit of 255 and only reports the Neighbor Discovery messages received on
+//! that interface with a hop limit of 255.
+//!
+//! Opening a raw socket requires the `CAP_NET_RAW` capability (or root).
+//!
+//! **Linux only** (`#[cfg(target_os = "linux")]`).
+
+#![cfg(target_os = "linux")]
+
+// The crate is `#![no_std]`; this module (gated on the `std` feature) opts back
+// into `std`.
+extern crate std;
+
+use core::mem::size_of;
+use core::net::{Ipv6Addr, SocketAddrV6};
+
+use std::io::{self, IoSliceMut};
+use std::os::fd::{AsFd, AsRawFd, OwnedFd};
+use std::vec;
+
+use async_io::Async;
+
+use nix::libc;
+use nix::sys::socket::{
+    self, sockopt, AddressFamily, ControlMessageOwned, MsgFlags, SockFlag, SockProtocol, SockType,
+    SockaddrIn6,
+};
+
+use super::InfraIf;
+
+/// The all-routers link-local multicast group (`ff02::2`), the destination of Router Solicitations.
+const ALL_ROUTERS_IP6_GROUP: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 2);
+
+/// The range of the ICMPv6 types of the Neighbor Discovery messages (RFC 4861):
+/// Router Solicitation (133) to Redirect (137).
+const ND_ICMP6_TYPES: core::ops::RangeInclusive<u8> = 133..=137;
+
+/// The hop limit of Neighbor Discovery messages (RFC 4861).
+const ND_HOP_LIMIT: libc::c_int = 255;
+
+/// An [`InfraIf`] over a raw ICMPv6 socket of the host (Linux).
+pub struct RawInfraIf {
+    socket: Async<OwnedFd>,
+    infra_if_index: u32,
+}
+
+impl RawInfraIf {
+    /// Open a raw ICMPv6 socket on the infrastructure network interface with the given index.
+    pub fn new(infra_if_index: u32) -> io::Result<Self> {
+        let fd = socket::socket(
+            AddressFamily::Inet6,
+            SockType::Raw,
+            SockFlag::empty(),
+            SockProtocol::IcmpV6,
+        )?;
+
+        socket::setsockopt(&fd, sockopt::Ipv6RecvPacketInfo, &true)?;
+        socket::setsockopt(&fd, sockopt::Ipv6RecvHopLimit, &true)?;
+        socket::setsockopt(&fd, sockopt::Ipv6MulticastHops, &ND_HOP_LIMIT)?;
+        socket::setsockopt(&fd, sockopt::Ipv6Ttl, &ND_HOP_LIMIT)?;
+
+        // Not covered by `nix::sys::socket::sockopt` (its `Ipv6AddMembership` always joins
+        // the group on the default interface)
+        set_ipv6_opt(
+            &fd,
+            libc::IPV6_MULTICAST_IF,
+            &(infra_if_index as libc::c_int),
+        )?;
+        set_ipv6_opt(&fd, libc::IPV6_MULTICAST_LOOP, &(0 as libc::c_int))?;
+        set_ipv6_opt(
+            &fd,
+            libc::IPV6_ADD_MEMBERSHIP,
+            &libc::ipv6_mreq {
+                ipv6mr_multiaddr: libc::in6_addr {
+                    s6_addr: ALL_ROUTERS_IP6_GROUP.octets(),
+                },
+                ipv6mr_interface: infra_if_index,
+            },
+        )?;
+
+        Ok(Self {
+            socket: Async::new(fd)?,
+            infra_if_index,
+        })
+    }
+
+    /// Receive one ICMPv6 message, returning its length, source address,
+    /// and the interface index and hop limit it was received with.
+    fn recv_msg(fd: &OwnedFd, buf: &mut [u8]) -> io::Result<(usize, Ipv6Addr, u32, i32)> {
+        let mut iov = [IoSliceMut::new(buf)];
+
+        // Room for the `IPV6_PKTINFO` and `IPV6_HOPLIMIT` control messages
+        let mut control = nix::cmsg_space!(libc::in6_pktinfo, libc::c_int);
+
+        let msg = socket::recvmsg::<SockaddrIn6>(
+            fd.as_raw_fd(),
+            &mut iov,
+            Some(&mut control),
+            MsgFlags::empty(),
+        )?;
+
+        let mut if_index = 0;
+        let mut hop_limit = -1;
+
+        for cmsg in msg.cmsgs()? {
+            match cmsg {
+                ControlMessageOwned::Ipv6PacketInfo(info) => if_index = info.ipi6_ifindex,
+                ControlMessageOwned::Ipv6HopLimit(limit) => hop_limit = limit,
+                _ => (),
+            }
+        }
+
+        let src = msg
+            .address
+            .map(|addr| addr.ip())
+            .unwrap_or(Ipv6Addr::UNSPECIFIED);
+
+        Ok((msg.bytes, src, if_index, hop_limit))
+    }
+}
+
+impl InfraIf for RawInfraIf {
+    type Error = io::Error;
+
+    async fn send(&mut self, dest: Ipv6Addr, data: &[u8]) -> Result<(), Self::Error> {
+        let scope_id = if dest.is_multicast() || dest.is_unicast_link_local() {
+            self.infra_if_index
+        } else {
+            0
+        };
+
+        let dest = SockaddrIn6::from(SocketAddrV6::new(dest, 0, 0, scope_id));
+
+        self.socket
+            .write_with(|fd| {
+                socket::sendto(fd.as_raw_fd(), data, &dest, MsgFlags::empty())?;
+
+                Ok(())
+            })
+            .await
+    }
+
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, Ipv6Addr), Self::Error> {
+        loop {
+            let (len, src, if_index, hop_limit) =
+                self.socket.read_with(|fd| Self::recv_msg(fd, buf)).await?;
+
+            if if_index == self.infra_if_index
+                && hop_limit == ND_HOP_LIMIT
+                && len > 0
+                && ND_ICMP6_TYPES.contains(&buf[0])
+            {
+                break Ok((len, src));
+            }
+        }
+    }
+}
+
+fn set_ipv6_opt<T>(fd: &impl AsFd, opt: libc::c_int, value: &T) -> io::Result<()> {
+    if unsafe {
+        libc::setsockopt(
+            fd.as_fd().as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            opt,
+            value as *const _ as *const libc::c_void,
+            size_of::<T>() as _,
+        )
+    } < 0
+    {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,359 @@
+//! Typed OpenThread state changes.
+//!
+//! OpenThread reports its state changes with the `otChangedFlags` bit-field. The flags reported since
+//! the last call to [`OpenThread::wait_changed_flags`] are accumulated, so that no change is missed
+//! between two calls, even if the changes happened in between.
+//!
+//! Tasks which need to wait for changes independently of each other should each use
+//! their own [`ChangeSubscriber`] instead, which has its own accumulated changes and waker.
+
+use core::future::poll_fn;
+use core::task::Poll;
+
+use crate::fmt::bitflags;
+use crate::signal::Signal;
+use crate::sys::otError_OT_ERROR_NO_BUFS;
+use crate::sys::{
+    OT_CHANGED_ACTIVE_DATASET, OT_CHANGED_CHANNEL_MANAGER_NEW_CHANNEL,
+    OT_CHANGED_COMMISSIONER_STATE, OT_CHANGED_IP6_ADDRESS_ADDED, OT_CHANGED_IP6_ADDRESS_REMOVED,
+    OT_CHANGED_IP6_MULTICAST_SUBSCRIBED, OT_CHANGED_IP6_MULTICAST_UNSUBSCRIBED,
+    OT_CHANGED_JOINER_STATE, OT_CHANGED_NAT64_TRANSLATOR_STATE, OT_CHANGED_NETWORK_KEY,
+    OT_CHANGED_PARENT_LINK_QUALITY, OT_CHANGED_PENDING_DATASET, OT_CHANGED_PSKC,
+    OT_CHANGED_SECURITY_POLICY, OT_CHANGED_SUPPORTED_CHANNEL_MASK,
+    OT_CHANGED_THREAD_BACKBONE_ROUTER_LOCAL, OT_CHANGED_THREAD_BACKBONE_ROUTER_STATE,
+    OT_CHANGED_THREAD_CHANNEL, OT_CHANGED_THREAD_CHILD_ADDED, OT_CHANGED_THREAD_CHILD_REMOVED,
+    OT_CHANGED_THREAD_EXT_PANID, OT_CHANGED_THREAD_KEY_SEQUENCE_COUNTER, OT_CHANGED_THREAD_LL_ADDR,
+    OT_CHANGED_THREAD_ML_ADDR, OT_CHANGED_THREAD_NETDATA, OT_CHANGED_THREAD_NETIF_STATE,
+    OT_CHANGED_THREAD_NETWORK_NAME, OT_CHANGED_THREAD_PANID, OT_CHANGED_THREAD_PARTITION_ID,
+    OT_CHANGED_THREAD_RLOC_ADDED, OT_CHANGED_THREAD_RLOC_REMOVED, OT_CHANGED_THREAD_ROLE,
+};
+use crate::{DeviceRole, OpenThread, OtContext, OtError};
+
+bitflags! {
+    /// OpenThread state changes - a mirror of the C `otChangedFlags` bit-field.
+    #[repr(transparent)]
+    #[derive(Default)]
+    #[cfg_attr(not(feature = "defmt"), derive(Debug, Copy, Clone, Eq, PartialEq, Hash))]
+    pub struct ChangedFlags: u32 {
+        /// An IPv6 address was added.
+        const IP6_ADDRESS_ADDED = OT_CHANGED_IP6_ADDRESS_ADDED;
+        /// An IPv6 address was removed.
+        const IP6_ADDRESS_REMOVED = OT_CHANGED_IP6_ADDRESS_REMOVED;
+        /// The device role changed.
+        const THREAD_ROLE = OT_CHANGED_THREAD_ROLE;
+        /// The link-local address changed.
+        const THREAD_LL_ADDR = OT_CHANGED_THREAD_LL_ADDR;
+        /// The mesh-local address changed.
+        const THREAD_ML_ADDR = OT_CHANGED_THREAD_ML_ADDR;
+        /// An RLOC address was added.
+        const THREAD_RLOC_ADDED = OT_CHANGED_THREAD_RLOC_ADDED;
+        /// An RLOC address was removed.
+        const THREAD_RLOC_REMOVED = OT_CHANGED_THREAD_RLOC_REMOVED;
+        /// The partition ID changed.
+        const THREAD_PARTITION_ID = OT_CHANGED_THREAD_PARTITION_ID;
+        /// The Thread key sequence counter changed.
+        const THREAD_KEY_SEQUENCE_COUNTER = OT_CHANGED_THREAD_KEY_SEQUENCE_COUNTER;
+        /// The Network Data changed.
+        const THREAD_NETDATA = OT_CHANGED_THREAD_NETDATA;
+        /// A child was added.
+        const THREAD_CHILD_ADDED = OT_CHANGED_THREAD_CHILD_ADDED;
+        /// A child was removed.
+        const THREAD_CHILD_REMOVED = OT_CHANGED_THREAD_CHILD_REMOVED;
+        /// A multicast group was subscribed to.
+        const IP6_MULTICAST_SUBSCRIBED = OT_CHANGED_IP6_MULTICAST_SUBSCRIBED;
+        /// A multicast group was unsubscribed from.
+        const IP6_MULTICAST_UNSUBSCRIBED = OT_CHANGED_IP6_MULTICAST_UNSUBSCRIBED;
+        /// The Thread network channel changed.
+        const THREAD_CHANNEL = OT_CHANGED_THREAD_CHANNEL;
+        /// The Thread network PAN ID changed.
+        const THREAD_PANID = OT_CHANGED_THREAD_PANID;
+        /// The Thread network name changed.
+        const THREAD_NETWORK_NAME = OT_CHANGED_THREAD_NETWORK_NAME;
+        /// The Thread network extended PAN ID changed.
+        const THREAD_EXT_PANID = OT_CHANGED_THREAD_EXT_PANID;
+        /// The network key changed.
+        const NETWORK_KEY = OT_CHANGED_NETWORK_KEY;
+        /// The PSKc changed.
+        const PSKC = OT_CHANGED_PSKC;
+        /// The security policy changed.
+        const SECURITY_POLICY = OT_CHANGED_SECURITY_POLICY;
+        /// The channel manager selected a new channel.
+        const CHANNEL_MANAGER_NEW_CHANNEL = OT_CHANGED_CHANNEL_MANAGER_NEW_CHANNEL;
+        /// The supported channel mask changed.
+        const SUPPORTED_CHANNEL_MASK = OT_CHANGED_SUPPORTED_CHANNEL_MASK;
+        /// The commissioner state changed.
+        const COMMISSIONER_STATE = OT_CHANGED_COMMISSIONER_STATE;
+        /// The Thread network interface state (up/down) changed.
+        const THREAD_NETIF_STATE = OT_CHANGED_THREAD_NETIF_STATE;
+        /// The backbone router state changed.
+        const THREAD_BACKBONE_ROUTER_STATE = OT_CHANGED_THREAD_BACKBONE_ROUTER_STATE;
+        /// The local backbone router configuration changed.
+        const THREAD_BACKBONE_ROUTER_LOCAL = OT_CHANGED_THREAD_BACKBONE_ROUTER_LOCAL;
+        /// The joiner state changed.
+        const JOINER_STATE = OT_CHANGED_JOINER_STATE;
+        /// The active operational dataset changed.
+        const ACTIVE_DATASET = OT_CHANGED_ACTIVE_DATASET;
+        /// The pending operational dataset changed.
+        const PENDING_DATASET = OT_CHANGED_PENDING_DATASET;
+        /// The NAT64 translator state changed.
+        const NAT64_TRANSLATOR_STATE = OT_CHANGED_NAT64_TRANSLATOR_STATE;
+        /// The link quality to the parent changed.
+        const PARENT_LINK_QUALITY = OT_CHANGED_PARENT_LINK_QUALITY;
+    }
+}
+
+impl OpenThread<'_> {
+    /// Wait for the OpenThread stack to change its state, and return the changes
+    /// accumulated since the previous call.
+    ///
+    /// If there are accumulated changes already, the method returns immediately.
+    ///
+    /// NOTE:
+    /// The changes are accumulated only once, so when this method (or [`Self::wait_role`] /
+    /// [`Self::wait_attached`] which are based on it) is called from multiple async tasks,
+    /// each change will be returned to only one of them. Use a [`ChangeSubscriber`] per task instead.
+    /// Moreover, it is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn wait_changed_flags(&self) -> ChangedFlags {
+        poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            let flags = core::mem::take(&mut state.ot.changed_flags);
+            if !flags.is_empty() {
+                return Poll::Ready(flags);
+            }
+
+            state.ot.changed_flags_signal.reset();
+            let _ = state.ot.changed_flags_signal.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for the device role to satisfy the provided predicate, and return the role.
+    ///
+    /// If the current device role already satisfies the predicate, the method returns immediately.
+    ///
+    /// Arguments:
+    /// - `f`: The predicate, called with the current device role on each role change
+    ///
+    /// NOTE: See [`Self::wait_changed_flags`] on calling this method from multiple async tasks.
+    pub async fn wait_role<F>(&self, mut f: F) -> DeviceRole
+    where
+        F: FnMut(DeviceRole) -> bool,
+    {
+        loop {
+            let role = self.device_role();
+            if f(role) {
+                break role;
+            }
+
+            while !self
+                .wait_changed_flags()
+                .await
+                .contains(ChangedFlags::THREAD_ROLE)
+            {}
+        }
+    }
+
+    /// Wait for the device to be attached to a Thread network (i.e. to be a child, a router or a leader),
+    /// and return its role.
+    ///
+    /// NOTE: See [`Self::wait_changed_flags`] on calling this method from multiple async tasks.
+    pub async fn wait_attached(&self) -> DeviceRole {
+        self.wait_role(|role| role.is_connected()).await
+    }
+}
+
+/// A subscriber to the OpenThread state changes.
+///
+/// Each subscriber accumulates the changes reported since its previous [`ChangeSubscriber::wait`] call
+/// in its own slot, and has its own waker registration, so that multiple async tasks can wait for
+/// state changes independently of each other, each with its own subscriber.
+///
+/// The subscriber releases its slot when dropped. The number of slots is set by the
+/// `CHANGE_SUBSCRIBERS` parameter of [`OtResources`](crate::OtResources).
+pub struct ChangeSubscriber<'a> {
+    /// The OpenThread stack that the subscriber is associated with.
+    ot: OpenThread<'a>,
+    /// The slot in the OpenThread stack's change subscriber array that this subscriber occupies.
+    slot: usize,
+}
+
+impl<'a> ChangeSubscriber<'a> {
+    /// Create a new change subscriber.
+    ///
+    /// Only the changes happening after the creation of the subscriber are reported by it.
+    ///
+    /// Returns `OT_ERROR_NO_BUFS` if all the `CHANGE_SUBSCRIBERS` slots of
+    /// [`OtResources`](crate::OtResources) are taken.
+    pub fn new(ot: OpenThread<'a>) -> Result<Self, OtError> {
+        let slot = {
+            let mut active_ot = ot.activate();
+            let state = active_ot.state();
+
+            let slot = state
+                .ot
+                .change_subscribers
+                .iter()
+                .position(|subscriber| !subscriber.taken)
+                .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+            let subscriber = &mut state.ot.change_subscribers[slot];
+            subscriber.taken = true;
+            subscriber.flags = ChangedFlags::empty();
+            #[cfg(feature = "srp-client")]
+            {
+                subscriber.srp_changed = false;
+            }
+            subscriber.signal.reset();
+
+            slot
+        };
+
+        Ok(Self { ot, slot })
+    }
+
+    /// Wait for the OpenThread stack to change its state, and return the changes
+    /// accumulated by this subscriber since its previous call.
+    ///
+    /// If there are accumulated changes already, the method returns immediately.
+    pub async fn wait(&mut self) -> ChangedFlags {
+        poll_fn(|cx| {
+            let mut ot = self.ot.activate();
+            let subscriber = &mut ot.state().ot.change_subscribers[self.slot];
+
+            let flags = core::mem::take(&mut subscriber.flags);
+            if !flags.is_empty() {
+                return Poll::Ready(flags);
+            }
+
+            subscriber.signal.reset();
+            let _ = subscriber.signal.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for the device role to satisfy the provided predicate, and return the role.
+    ///
+    /// Same as [`OpenThread::wait_role`], but using the changes of this subscriber.
+    pub async fn wait_role<F>(&mut self, mut f: F) -> DeviceRole
+    where
+        F: FnMut(DeviceRole) -> bool,
+    {
+        loop {
+            let role = self.ot.device_role();
+            if f(role) {
+                break role;
+            }
+
+            while !self.wait().await.contains(ChangedFlags::THREAD_ROLE) {}
+        }
+    }
+
+    /// Wait for the device to be attached to a Thread network, and return its role.
+    ///
+    /// Same as [`OpenThread::wait_attached`], but using the changes of this subscriber.
+    pub async fn wait_attached(&mut self) -> DeviceRole {
+        self.wait_role(|role| role.is_connected()).await
+    }
+
+    /// Wait for the SRP client state to change since the previous call.
+    ///
+    /// Same as [`OpenThread::srp_wait_changed`], but using the changes of this subscriber.
+    ///
+    /// This method will wait forever if `OpenThread` is not instantiated with SRP.
+    #[cfg(feature = "srp-client")]
+    pub async fn wait_srp_changed(&mut self) {
+        poll_fn(|cx| {
+            let mut ot = self.ot.activate();
+            let subscriber = &mut ot.state().ot.change_subscribers[self.slot];
+
+            if core::mem::take(&mut subscriber.srp_changed) {
+                return Poll::Ready(());
+            }
+
+            subscriber.signal.reset();
+            let _ = subscriber.signal.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Drop for ChangeSubscriber<'_> {
+    fn drop(&mut self) {
+        let mut ot = self.ot.activate();
+
+        ot.state().ot.change_subscribers[self.slot].taken = false;
+    }
+}
+
+/// A change subscriber slot of `OtState`.
+pub(crate) struct ChangeSubscriberSlot {
+    /// Whether the slot is occupied by a `ChangeSubscriber`.
+    taken: bool,
+    /// The changes accumulated since the last `ChangeSubscriber::wait` call.
+    flags: ChangedFlags,
+    /// Whether the SRP client state changed since the last `ChangeSubscriber::wait_srp_changed` call.
+    #[cfg(feature = "srp-client")]
+    srp_changed: bool,
+    /// Signaled when `flags` (or `srp_changed`) is updated.
+    signal: Signal<()>,
+}
+
+impl ChangeSubscriberSlot {
+    pub(crate) const fn new() -> Self {
+        Self {
+            taken: false,
+            flags: ChangedFlags::empty(),
+            #[cfg(feature = "srp-client")]
+            srp_changed: false,
+            signal: Signal::new(),
+        }
+    }
+}
+
+impl OtContext<'_> {
+    /// Accumulate the changes reported by OpenThread, for `wait_changed_flags`
+    /// and for each change subscriber.
+    pub(crate) fn plat_changed_flags(&mut self, flags: u32) {
+        let flags = ChangedFlags::from_bits_truncate(flags);
+
+        let state = self.state();
+
+        state.ot.changed_flags |= flags;
+        state.ot.changed_flags_signal.signal(());
+
+        for subscriber in state
+            .ot
+            .change_subscribers
+            .iter_mut()
+            .filter(|subscriber| subscriber.taken)
+        {
+            subscriber.flags |= flags;
+            subscriber.signal.signal(());
+        }
+    }
+
+    /// Report an SRP client state change to each change subscriber.
+    #[cfg(feature = "srp-client")]
+    pub(crate) fn plat_srp_changed_subscribers(&mut self) {
+        for subscriber in self
+            .state()
+            .ot
+            .change_subscribers
+            .iter_mut()
+            .filter(|subscriber| subscriber.taken)
+        {
+            subscriber.srp_changed = true;
+            subscriber.signal.signal(());
+        }
+    }
+}
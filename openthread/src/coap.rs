@@ -0,0 +1,2158 @@
+//! CoAP API: OpenThread's application CoAP agent (RFC 7252), bridged into async Rust.
+//!
+//! Server-side, each [`CoapResource`] registers a URI path with the agent and
+//! yields the requests targeting it, which are then answered asynchronously
+//! with [`CoapResource::respond`]. Client-side, [`OpenThread::coap_request`]
+//! sends a (confirmable or non-confirmable) request and awaits the response.
+//!
+//! With the `coap-block` feature, large payloads can be transferred block by block
+//! (RFC 7959): client-side with [`CoapBlockReader`] (Block2) and [`CoapBlockWriter`] (Block1),
+//! server-side by inspecting the `block1`/`block2` options of the received requests
+//! and answering with [`CoapResource::respond_block`].
+//!
+//! With the `coap-observe` feature, resources can be observed (RFC 7641): client-side with
+//! [`OpenThread::coap_observe`], server-side by creating the resource with
+//! [`CoapResource::new_observable`] and pushing its new state with [`CoapResource::notify`].
+//!
+//! The agent must be started with [`OpenThread::coap_start`] before use, and
+//! the `OpenThread` instance must have been created with `OtCoapResources`
+//! (see [`OpenThreadBuilder::coap`](crate::OpenThreadBuilder::coap)).
+
+use core::cell::RefCell;
+use core::ffi::{c_char, c_void};
+use core::fmt::{self, Display};
+use core::future::poll_fn;
+use core::mem::MaybeUninit;
+use core::net::SocketAddrV6;
+
+use crate::signal::Signal;
+use crate::sys::{
+    otCoapAddResource, otCoapCode, otCoapCode_OT_COAP_CODE_BAD_GATEWAY,
+    otCoapCode_OT_COAP_CODE_BAD_OPTION, otCoapCode_OT_COAP_CODE_BAD_REQUEST,
+    otCoapCode_OT_COAP_CODE_CHANGED, otCoapCode_OT_COAP_CODE_CONTENT,
+    otCoapCode_OT_COAP_CODE_CONTINUE, otCoapCode_OT_COAP_CODE_CREATED,
+    otCoapCode_OT_COAP_CODE_DELETE, otCoapCode_OT_COAP_CODE_DELETED, otCoapCode_OT_COAP_CODE_EMPTY,
+    otCoapCode_OT_COAP_CODE_FORBIDDEN, otCoapCode_OT_COAP_CODE_GATEWAY_TIMEOUT,
+    otCoapCode_OT_COAP_CODE_GET, otCoapCode_OT_COAP_CODE_INTERNAL_ERROR,
+    otCoapCode_OT_COAP_CODE_METHOD_NOT_ALLOWED, otCoapCode_OT_COAP_CODE_NOT_ACCEPTABLE,
+    otCoapCode_OT_COAP_CODE_NOT_FOUND, otCoapCode_OT_COAP_CODE_NOT_IMPLEMENTED,
+    otCoapCode_OT_COAP_CODE_POST, otCoapCode_OT_COAP_CODE_PRECONDITION_FAILED,
+    otCoapCode_OT_COAP_CODE_PROXY_NOT_SUPPORTED, otCoapCode_OT_COAP_CODE_PUT,
+    otCoapCode_OT_COAP_CODE_REQUEST_INCOMPLETE, otCoapCode_OT_COAP_CODE_REQUEST_TOO_LARGE,
+    otCoapCode_OT_COAP_CODE_SERVICE_UNAVAILABLE, otCoapCode_OT_COAP_CODE_UNAUTHORIZED,
+    otCoapCode_OT_COAP_CODE_UNSUPPORTED_FORMAT, otCoapCode_OT_COAP_CODE_VALID,
+    otCoapMessageAppendUriPathOptions, otCoapMessageGenerateToken, otCoapMessageGetCode,
+    otCoapMessageGetType, otCoapMessageInit, otCoapMessageInitResponse, otCoapMessageSetCode,
+    otCoapMessageSetPayloadMarker, otCoapNewMessage, otCoapRemoveResource, otCoapResource,
+    otCoapResponseHandler, otCoapSendRequestWithParameters, otCoapSendResponseWithParameters,
+    otCoapStart, otCoapStop, otCoapType, otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT,
+    otCoapType_OT_COAP_TYPE_CONFIRMABLE, otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE,
+    otCoapType_OT_COAP_TYPE_RESET, otError, otError_OT_ERROR_ABORT, otError_OT_ERROR_INVALID_ARGS,
+    otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NONE, otError_OT_ERROR_NO_BUFS, otInstance,
+    otMessage, otMessageAppend, otMessageFree, otMessageGetLength, otMessageGetOffset,
+    otMessageInfo, otMessageRead, OT_COAP_DEFAULT_TOKEN_LENGTH, OT_DEFAULT_COAP_PORT,
+};
+#[cfg(feature = "coap-block")]
+use crate::sys::{
+    otCoapBlockSzx, otCoapMessageAppendBlock1Option, otCoapMessageAppendBlock2Option,
+    otCoapOptionType_OT_COAP_OPTION_BLOCK1, otCoapOptionType_OT_COAP_OPTION_BLOCK2,
+};
+#[cfg(feature = "coap-observe")]
+use crate::sys::{
+    otCoapMessageAppendObserveOption, otCoapMessageGetToken, otCoapMessageGetTokenLength,
+    otCoapMessageSetToken, otCoapOptionType_OT_COAP_OPTION_OBSERVE, OT_COAP_MAX_TOKEN_LENGTH,
+};
+#[cfg(any(feature = "coap-block", feature = "coap-observe"))]
+use crate::sys::{
+    otCoapOptionIterator, otCoapOptionIteratorGetFirstOptionMatching,
+    otCoapOptionIteratorGetOptionUintValue, otCoapOptionIteratorInit, otCoapOptionType,
+};
+#[cfg(feature = "coaps")]
+use crate::sys::{
+    otCoapSecureAddResource, otCoapSecureRemoveResource, otCoapSecureSendRequest,
+    otCoapSecureSendResponse,
+};
+#[cfg(feature = "coaps")]
+use crate::CoapSecureCtx;
+use crate::{ot, to_sock_addr, Bytes, OpenThread, OtContext, OtError};
+
+/// The default UDP port of the CoAP agent (5683)
+pub const COAP_DEFAULT_PORT: u16 = OT_DEFAULT_COAP_PORT as _;
+
+/// The maximum length of the URI path of a CoAP resource or request
+pub const COAP_MAX_URI_PATH_LEN: usize = 64;
+
+/// The maximum number of observers of each observable CoAP resource
+#[cfg(feature = "coap-observe")]
+pub const COAP_MAX_OBSERVERS: usize = 4;
+
+/// The type of a CoAP message
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CoapType {
+    /// Confirmable (CON) message, retransmitted until acknowledged
+    Confirmable,
+    /// Non-confirmable (NON) message
+    NonConfirmable,
+    /// Acknowledgment (ACK) message
+    Acknowledgment,
+    /// Reset (RST) message
+    Reset,
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otCoapType> for CoapType {
+    fn from(value: otCoapType) -> Self {
+        match value {
+            otCoapType_OT_COAP_TYPE_CONFIRMABLE => Self::Confirmable,
+            otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE => Self::NonConfirmable,
+            otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT => Self::Acknowledgment,
+            _ => Self::Reset,
+        }
+    }
+}
+
+impl From<CoapType> for otCoapType {
+    fn from(value: CoapType) -> Self {
+        match value {
+            CoapType::Confirmable => otCoapType_OT_COAP_TYPE_CONFIRMABLE,
+            CoapType::NonConfirmable => otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE,
+            CoapType::Acknowledgment => otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT,
+            CoapType::Reset => otCoapType_OT_COAP_TYPE_RESET,
+        }
+    }
+}
+
+/// A CoAP message code (method or response code), as its raw `c.dd` byte
+///
+/// The well-known codes are available as associated constants.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoapCode(pub u8);
+
+impl CoapCode {
+    pub const EMPTY: Self = Self(otCoapCode_OT_COAP_CODE_EMPTY as _);
+    pub const GET: Self = Self(otCoapCode_OT_COAP_CODE_GET as _);
+    pub const POST: Self = Self(otCoapCode_OT_COAP_CODE_POST as _);
+    pub const PUT: Self = Self(otCoapCode_OT_COAP_CODE_PUT as _);
+    pub const DELETE: Self = Self(otCoapCode_OT_COAP_CODE_DELETE as _);
+    pub const CREATED: Self = Self(otCoapCode_OT_COAP_CODE_CREATED as _);
+    pub const DELETED: Self = Self(otCoapCode_OT_COAP_CODE_DELETED as _);
+    pub const VALID: Self = Self(otCoapCode_OT_COAP_CODE_VALID as _);
+    pub const CHANGED: Self = Self(otCoapCode_OT_COAP_CODE_CHANGED as _);
+    pub const CONTENT: Self = Self(otCoapCode_OT_COAP_CODE_CONTENT as _);
+    pub const CONTINUE: Self = Self(otCoapCode_OT_COAP_CODE_CONTINUE as _);
+    pub const BAD_REQUEST: Self = Self(otCoapCode_OT_COAP_CODE_BAD_REQUEST as _);
+    pub const UNAUTHORIZED: Self = Self(otCoapCode_OT_COAP_CODE_UNAUTHORIZED as _);
+    pub const BAD_OPTION: Self = Self(otCoapCode_OT_COAP_CODE_BAD_OPTION as _);
+    pub const FORBIDDEN: Self = Self(otCoapCode_OT_COAP_CODE_FORBIDDEN as _);
+    pub const NOT_FOUND: Self = Self(otCoapCode_OT_COAP_CODE_NOT_FOUND as _);
+    pub const METHOD_NOT_ALLOWED: Self = Self(otCoapCode_OT_COAP_CODE_METHOD_NOT_ALLOWED as _);
+    pub const NOT_ACCEPTABLE: Self = Self(otCoapCode_OT_COAP_CODE_NOT_ACCEPTABLE as _);
+    pub const REQUEST_INCOMPLETE: Self = Self(otCoapCode_OT_COAP_CODE_REQUEST_INCOMPLETE as _);
+    pub const PRECONDITION_FAILED: Self = Self(otCoapCode_OT_COAP_CODE_PRECONDITION_FAILED as _);
+    pub const REQUEST_TOO_LARGE: Self = Self(otCoapCode_OT_COAP_CODE_REQUEST_TOO_LARGE as _);
+    pub const UNSUPPORTED_FORMAT: Self = Self(otCoapCode_OT_COAP_CODE_UNSUPPORTED_FORMAT as _);
+    pub const INTERNAL_ERROR: Self = Self(otCoapCode_OT_COAP_CODE_INTERNAL_ERROR as _);
+    pub const NOT_IMPLEMENTED: Self = Self(otCoapCode_OT_COAP_CODE_NOT_IMPLEMENTED as _);
+    pub const BAD_GATEWAY: Self = Self(otCoapCode_OT_COAP_CODE_BAD_GATEWAY as _);
+    pub const SERVICE_UNAVAILABLE: Self = Self(otCoapCode_OT_COAP_CODE_SERVICE_UNAVAILABLE as _);
+    pub const GATEWAY_TIMEOUT: Self = Self(otCoapCode_OT_COAP_CODE_GATEWAY_TIMEOUT as _);
+    pub const PROXY_NOT_SUPPORTED: Self = Self(otCoapCode_OT_COAP_CODE_PROXY_NOT_SUPPORTED as _);
+
+    /// The class of the code (the `c` in `c.dd`)
+    pub const fn class(&self) -> u8 {
+        self.0 >> 5
+    }
+
+    /// The detail of the code (the `dd` in `c.dd`)
+    pub const fn detail(&self) -> u8 {
+        self.0 & 0x1f
+    }
+
+    /// Whether the code is a request method (class 0, except `EMPTY`)
+    pub const fn is_request(&self) -> bool {
+        self.class() == 0 && self.0 != 0
+    }
+
+    /// Whether the code is a success (2.xx) response code
+    pub const fn is_success(&self) -> bool {
+        self.class() == 2
+    }
+}
+
+impl From<otCoapCode> for CoapCode {
+    fn from(value: otCoapCode) -> Self {
+        Self(value as _)
+    }
+}
+
+impl From<CoapCode> for otCoapCode {
+    fn from(value: CoapCode) -> Self {
+        value.0 as _
+    }
+}
+
+impl Display for CoapCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}", self.class(), self.detail())
+    }
+}
+
+/// A CoAP request received by a [`CoapResource`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoapRequest {
+    /// The request method
+    pub code: CoapCode,
+    /// The request message type
+    pub msg_type: CoapType,
+    /// The length of the request payload
+    pub len: usize,
+    /// The local address on which the request was received
+    pub local: SocketAddrV6,
+    /// The address of the peer which sent the request
+    pub peer: SocketAddrV6,
+    /// The Block1 option of the request (a block of the request payload), if any
+    #[cfg(feature = "coap-block")]
+    pub block1: Option<CoapBlock>,
+    /// The Block2 option of the request (the requested block of the response payload), if any
+    #[cfg(feature = "coap-block")]
+    pub block2: Option<CoapBlock>,
+    /// The Observe option of the request (0 to register an observer, 1 to deregister it), if any
+    #[cfg(feature = "coap-observe")]
+    pub observe: Option<u32>,
+}
+
+/// A CoAP response received by [`OpenThread::coap_request`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoapResponse {
+    /// The response code
+    pub code: CoapCode,
+    /// The response message type
+    pub msg_type: CoapType,
+    /// The length of the response payload
+    pub len: usize,
+    /// The address of the peer which sent the response
+    pub peer: SocketAddrV6,
+    /// The Block1 option of the response (the acknowledged block of the request payload), if any
+    #[cfg(feature = "coap-block")]
+    pub block1: Option<CoapBlock>,
+    /// The Block2 option of the response (a block of the response payload), if any
+    #[cfg(feature = "coap-block")]
+    pub block2: Option<CoapBlock>,
+    /// The Observe option (sequence number) of the response, if it is a notification
+    #[cfg(feature = "coap-observe")]
+    pub observe: Option<u32>,
+}
+
+/// The size of the blocks of a CoAP block-wise transfer (RFC 7959)
+#[cfg(feature = "coap-block")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CoapBlockSize {
+    B16,
+    B32,
+    B64,
+    B128,
+    B256,
+    B512,
+    B1024,
+}
+
+#[cfg(feature = "coap-block")]
+impl CoapBlockSize {
+    /// The block size in bytes
+    pub const fn bytes(&self) -> usize {
+        16 << (*self as usize)
+    }
+
+    /// Create a block size from its `SZX` exponent, if valid
+    pub const fn from_szx(szx: u8) -> Option<Self> {
+        Some(match szx {
+            0 => Self::B16,
+            1 => Self::B32,
+            2 => Self::B64,
+            3 => Self::B128,
+            4 => Self::B256,
+            5 => Self::B512,
+            6 => Self::B1024,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(feature = "coap-block")]
+impl From<CoapBlockSize> for otCoapBlockSzx {
+    fn from(value: CoapBlockSize) -> Self {
+        value as _
+    }
+}
+
+/// The value of a Block1 or Block2 option of a CoAP block-wise transfer (RFC 7959)
+#[cfg(feature = "coap-block")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoapBlock {
+    /// The number of the block, in units of `size`
+    pub num: u32,
+    /// Whether more blocks follow this one
+    pub more: bool,
+    /// The block size
+    pub size: CoapBlockSize,
+}
+
+#[cfg(feature = "coap-block")]
+impl CoapBlock {
+    /// Create a new `CoapBlock` instance.
+    pub const fn new(num: u32, more: bool, size: CoapBlockSize) -> Self {
+        Self { num, more, size }
+    }
+
+    /// The offset of the block in the whole payload
+    pub const fn offset(&self) -> usize {
+        self.num as usize * self.size.bytes()
+    }
+
+    /// Decode the block from its option value (`NUM | M | SZX`)
+    fn from_ot(value: u64) -> Option<Self> {
+        Some(Self {
+            num: (value >> 4) as _,
+            more: value & 0x08 != 0,
+            size: CoapBlockSize::from_szx((value & 0x07) as _)?,
+        })
+    }
+}
+
+impl OpenThread<'_> {
+    /// Start the CoAP agent on the given UDP port (`otCoapStart`).
+    ///
+    /// Arguments:
+    /// - `port`: The local UDP port; usually `COAP_DEFAULT_PORT`.
+    pub fn coap_start(&self, port: u16) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let _ = state.coap()?;
+
+        ot!(unsafe { otCoapStart(state.ot.instance, port) })
+    }
+
+    /// Stop the CoAP agent (`otCoapStop`).
+    ///
+    /// Requests still awaiting a response complete with an `ABORT` error.
+    pub fn coap_stop(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let _ = state.coap()?;
+
+        ot!(unsafe { otCoapStop(state.ot.instance) })
+    }
+
+    /// Send a CoAP request and wait for the response
+    /// (`otCoapSendRequestWithParameters`).
+    ///
+    /// Confirmable requests are retransmitted by OpenThread until acknowledged;
+    /// a `RESPONSE_TIMEOUT` error is returned when no response arrives in time.
+    ///
+    /// Arguments:
+    /// - `peer`: The address of the CoAP server.
+    /// - `msg_type`: `CoapType::Confirmable` or `CoapType::NonConfirmable`.
+    /// - `code`: The request method.
+    /// - `uri_path`: The URI path of the resource, e.g. `"sensors/temp"`.
+    /// - `payload`: The request payload; may be empty.
+    /// - `buf`: The buffer to store the response payload into.
+    ///
+    /// Returns:
+    /// - The response; its payload is in `buf[..len]`, truncated to the buffer size.
+    ///
+    /// The number of requests in flight at any point in time is limited by the
+    /// `COAP_REQUESTS` parameter of `OtCoapResources`; when exceeded, a `NO_BUFS` error is returned.
+    ///
+    /// Dropping the returned future abandons the request; a late response is then discarded.
+    /// The future is NOT `mem::forget`-safe.
+    pub async fn coap_request(
+        &self,
+        peer: &SocketAddrV6,
+        msg_type: CoapType,
+        code: CoapCode,
+        uri_path: &str,
+        payload: &[u8],
+        buf: &mut [u8],
+    ) -> Result<CoapResponse, OtError> {
+        self.coap_request_with(
+            CoapAgent::Plain,
+            peer,
+            msg_type,
+            code,
+            uri_path,
+            &CoapOptions::default(),
+            payload,
+            buf,
+        )
+        .await
+    }
+
+    /// Same as `coap_request`, but also appending the given options to the request
+    /// and sending it through the given CoAP agent.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn coap_request_with(
+        &self,
+        agent: CoapAgent,
+        peer: &SocketAddrV6,
+        msg_type: CoapType,
+        code: CoapCode,
+        uri_path: &str,
+        options: &CoapOptions,
+        payload: &[u8],
+        buf: &mut [u8],
+    ) -> Result<CoapResponse, OtError> {
+        let slot = {
+            let mut ot = self.activate();
+            let state = ot.state();
+            let instance = state.ot.instance;
+            let coap = state.coap()?;
+
+            let slot = coap
+                .requests
+                .iter()
+                .position(|request| !request.taken)
+                .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+            let msg = coap_new_message(instance, msg_type, code, Some(uri_path), options, payload)?;
+
+            let res = agent.send_request(
+                instance,
+                msg,
+                &coap_peer_info(peer),
+                Some(plat_c_coap_response_handler),
+                slot as *mut c_void,
+            );
+
+            if res.is_err() {
+                unsafe { otMessageFree(msg) };
+            }
+
+            res?;
+
+            let request = &mut coap.requests[slot];
+            request.taken = true;
+            request.abandoned = false;
+            request.finished = false;
+            #[cfg(feature = "coap-observe")]
+            {
+                request.observe = false;
+            }
+            request.rx.reset();
+
+            slot
+        };
+
+        // Cancel-safety: OpenThread will still call us back with the outcome of the
+        // request, so the slot is marked as abandoned rather than released, and released
+        // by the response handler then. Defused on normal completion below.
+        let guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let Ok(coap) = ot.state().coap() else {
+                return;
+            };
+
+            let request = &mut coap.requests[slot];
+            if request.finished {
+                request.taken = false;
+            } else {
+                request.abandoned = true;
+            }
+        });
+
+        let res = poll_fn(move |cx| {
+            self.activate().state().coap()?.requests[slot]
+                .rx
+                .poll_wait(cx)
+                .map(Ok::<_, OtError>)
+        })
+        .await;
+
+        scopeguard::ScopeGuard::into_inner(guard);
+
+        let mut ot = self.activate();
+        let coap = ot.state().coap()?;
+
+        coap.requests[slot].taken = false;
+
+        let mut response = res?.map_err(OtError::new)?;
+
+        let offset = slot * coap.buf_len;
+        let data = &coap.request_buffers[offset..offset + response.len];
+
+        response.len = response.len.min(buf.len());
+        buf[..response.len].copy_from_slice(&data[..response.len]);
+
+        debug!(
+            "Received CoAP response {} from {}: {}",
+            response.code,
+            response.peer,
+            Bytes(&buf[..response.len])
+        );
+
+        Ok(response)
+    }
+
+    /// Send a non-confirmable CoAP request without waiting for a response
+    /// (`otCoapSendRequestWithParameters`).
+    ///
+    /// Arguments:
+    /// - `peer`: The address of the CoAP server; might be a multicast address.
+    /// - `code`: The request method.
+    /// - `uri_path`: The URI path of the resource, e.g. `"sensors/temp"`.
+    /// - `payload`: The request payload; may be empty.
+    pub fn coap_send(
+        &self,
+        peer: &SocketAddrV6,
+        code: CoapCode,
+        uri_path: &str,
+        payload: &[u8],
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let _ = state.coap()?;
+
+        let msg = coap_new_message(
+            instance,
+            CoapType::NonConfirmable,
+            code,
+            Some(uri_path),
+            &CoapOptions::default(),
+            payload,
+        )?;
+
+        let res = ot!(unsafe {
+            otCoapSendRequestWithParameters(
+                instance,
+                msg,
+                &coap_peer_info(peer),
+                None,
+                core::ptr::null_mut(),
+                core::ptr::null(),
+            )
+        });
+
+        if res.is_err() {
+            unsafe { otMessageFree(msg) };
+        }
+
+        res
+    }
+}
+
+#[cfg(feature = "coap-observe")]
+impl<'a> OpenThread<'a> {
+    /// Start observing a CoAP resource of a peer (RFC 7641).
+    ///
+    /// Sends a confirmable GET request with an Observe option of 0 (register) and returns
+    /// a `CoapObservation`, which yields the response to the request as well as all subsequent
+    /// notifications sent by the peer.
+    ///
+    /// Dropping the `CoapObservation` deregisters from the peer, by sending a GET request
+    /// with an Observe option of 1 and the same token.
+    ///
+    /// The observation occupies one of the request slots of `OtCoapResources` until it ends.
+    ///
+    /// Arguments:
+    /// - `peer`: The socket address of the peer.
+    /// - `uri_path`: The URI path of the resource to observe; at most `COAP_MAX_URI_PATH_LEN` bytes.
+    pub fn coap_observe<'r>(
+        &'r self,
+        peer: &SocketAddrV6,
+        uri_path: &'r str,
+    ) -> Result<CoapObservation<'r, 'a>, OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let coap = state.coap()?;
+
+        let slot = coap
+            .requests
+            .iter()
+            .position(|request| !request.taken)
+            .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+        let options = CoapOptions {
+            observe: Some(0),
+            ..Default::default()
+        };
+
+        let msg = coap_new_message(
+            instance,
+            CoapType::Confirmable,
+            CoapCode::GET,
+            Some(uri_path),
+            &options,
+            &[],
+        )?;
+
+        let mut token = [0; OT_COAP_MAX_TOKEN_LENGTH as usize];
+        let token_len = {
+            let msg_token = coap_token(msg);
+            token[..msg_token.len()].copy_from_slice(msg_token);
+            msg_token.len() as u8
+        };
+
+        let res = ot!(unsafe {
+            otCoapSendRequestWithParameters(
+                instance,
+                msg,
+                &coap_peer_info(peer),
+                Some(plat_c_coap_response_handler),
+                slot as *mut c_void,
+                core::ptr::null(),
+            )
+        });
+
+        if res.is_err() {
+            unsafe { otMessageFree(msg) };
+        }
+
+        res?;
+
+        let request = &mut coap.requests[slot];
+        request.taken = true;
+        request.abandoned = false;
+        request.finished = false;
+        request.observe = true;
+        request.rx.reset();
+
+        Ok(CoapObservation {
+            ot: self,
+            slot,
+            peer: *peer,
+            uri_path,
+            token,
+            token_len,
+            done: false,
+        })
+    }
+}
+
+/// An observation of a CoAP resource of a peer, started with `OpenThread::coap_observe`.
+///
+/// Dropping the observation deregisters from the peer.
+#[cfg(feature = "coap-observe")]
+pub struct CoapObservation<'r, 'a> {
+    ot: &'r OpenThread<'a>,
+    slot: usize,
+    peer: SocketAddrV6,
+    uri_path: &'r str,
+    token: [u8; OT_COAP_MAX_TOKEN_LENGTH as usize],
+    token_len: u8,
+    done: bool,
+}
+
+#[cfg(feature = "coap-observe")]
+impl CoapObservation<'_, '_> {
+    /// Wait for the next response or notification from the observed resource.
+    ///
+    /// The first one is the response to the registration request. If it (or any later
+    /// notification) does not carry an Observe option, the peer did not register us
+    /// (or ended the observation) and the observation is over.
+    ///
+    /// Only the latest notification is retained, so notifications which arrive before
+    /// this method is called again replace each other.
+    ///
+    /// Arguments:
+    /// - `buf`: The buffer to copy the payload into.
+    ///
+    /// Returns:
+    /// - `Ok(Some(response))`: The response; its payload is in `buf[..response.len]`.
+    /// - `Ok(None)`: The observation is over.
+    /// - `Err(OtError)`: The observation failed (e.g. the peer did not respond).
+    pub async fn next(&mut self, buf: &mut [u8]) -> Result<Option<CoapResponse>, OtError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let slot = self.slot;
+        let ot = self.ot;
+
+        let res = poll_fn(move |cx| {
+            ot.activate().state().coap()?.requests[slot]
+                .rx
+                .poll_wait(cx)
+                .map(Ok::<_, OtError>)
+        })
+        .await?;
+
+        let mut ot = self.ot.activate();
+        let coap = ot.state().coap()?;
+
+        // The signal only holds the latest outcome, so if OpenThread is done with the
+        // observation, what we got is the final one
+        if coap.requests[slot].finished {
+            self.done = true;
+        }
+
+        let mut response = res.map_err(OtError::new)?;
+
+        let offset = slot * coap.buf_len;
+        let data = &coap.request_buffers[offset..offset + response.len];
+
+        response.len = response.len.min(buf.len());
+        buf[..response.len].copy_from_slice(&data[..response.len]);
+
+        debug!(
+            "Received CoAP notification {} from {}: {}",
+            response.code,
+            response.peer,
+            Bytes(&buf[..response.len])
+        );
+
+        Ok(Some(response))
+    }
+}
+
+#[cfg(feature = "coap-observe")]
+impl Drop for CoapObservation<'_, '_> {
+    fn drop(&mut self) {
+        let mut ot = self.ot.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let Ok(coap) = state.coap() else {
+            return;
+        };
+
+        let request = &mut coap.requests[self.slot];
+        if request.finished {
+            request.taken = false;
+            return;
+        }
+
+        // Released by the response handler, once OpenThread finalizes the observation;
+        // which it does when sending the deregistration request with the same token
+        request.abandoned = true;
+
+        // Not needless with `coap-block`
+        #[allow(clippy::needless_update)]
+        let options = CoapOptions {
+            observe: Some(1),
+            token: Some((self.token, self.token_len)),
+            ..Default::default()
+        };
+
+        let res = coap_new_message(
+            instance,
+            CoapType::Confirmable,
+            CoapCode::GET,
+            Some(self.uri_path),
+            &options,
+            &[],
+        )
+        .and_then(|msg| {
+            let res = ot!(unsafe {
+                otCoapSendRequestWithParameters(
+                    instance,
+                    msg,
+                    &coap_peer_info(&self.peer),
+                    None,
+                    core::ptr::null_mut(),
+                    core::ptr::null(),
+                )
+            });
+
+            if res.is_err() {
+                unsafe { otMessageFree(msg) };
+            }
+
+            res
+        });
+
+        if let Err(err) = res {
+            warn!("Failed to deregister the CoAP observation: {}", err);
+        }
+    }
+}
+
+#[cfg(feature = "coap-block")]
+impl<'a> OpenThread<'a> {
+    /// Create a reader for downloading a large resource representation block by block,
+    /// with a Block2 block-wise transfer (RFC 7959).
+    ///
+    /// Arguments:
+    /// - `peer`: The address of the CoAP server.
+    /// - `code`: The request method; usually `CoapCode::GET`.
+    /// - `uri_path`: The URI path of the resource, e.g. `"logs"`.
+    /// - `size`: The preferred block size; the server might choose a smaller one.
+    pub fn coap_block_reader<'r>(
+        &'r self,
+        peer: &SocketAddrV6,
+        code: CoapCode,
+        uri_path: &'r str,
+        size: CoapBlockSize,
+    ) -> CoapBlockReader<'r, 'a> {
+        CoapBlockReader {
+            ot: self,
+            peer: *peer,
+            code,
+            uri_path,
+            size,
+            num: 0,
+            done: false,
+        }
+    }
+
+    /// Create a writer for uploading a large request payload block by block,
+    /// with a Block1 block-wise transfer (RFC 7959).
+    ///
+    /// Arguments:
+    /// - `peer`: The address of the CoAP server.
+    /// - `code`: The request method; usually `CoapCode::PUT` or `CoapCode::POST`.
+    /// - `uri_path`: The URI path of the resource, e.g. `"config"`.
+    /// - `size`: The preferred block size; the server might ask for a smaller one.
+    pub fn coap_block_writer<'r>(
+        &'r self,
+        peer: &SocketAddrV6,
+        code: CoapCode,
+        uri_path: &'r str,
+        size: CoapBlockSize,
+    ) -> CoapBlockWriter<'r, 'a> {
+        CoapBlockWriter {
+            ot: self,
+            peer: *peer,
+            code,
+            uri_path,
+            size,
+            offset: 0,
+        }
+    }
+}
+
+/// A reader of a resource representation transferred block by block (Block2)
+///
+/// Each block is fetched with a separate confirmable request, once the previous
+/// one is consumed, so the transfer proceeds at the pace of the reader.
+#[cfg(feature = "coap-block")]
+pub struct CoapBlockReader<'r, 'a> {
+    ot: &'r OpenThread<'a>,
+    peer: SocketAddrV6,
+    code: CoapCode,
+    uri_path: &'r str,
+    size: CoapBlockSize,
+    num: u32,
+    done: bool,
+}
+
+#[cfg(feature = "coap-block")]
+impl CoapBlockReader<'_, '_> {
+    /// The block size currently in use
+    pub fn block_size(&self) -> CoapBlockSize {
+        self.size
+    }
+
+    /// Read the next block of the representation.
+    ///
+    /// Arguments:
+    /// - `buf`: The buffer to store the block into; should be at least `block_size` bytes long.
+    ///
+    /// Returns:
+    /// - `None` if the last block was already read; otherwise the response carrying the block,
+    ///   whose payload is in `buf[..len]` and whose `block2` option tells the block offset.
+    ///   A response without a `block2` option (e.g. an error response, or a representation
+    ///   small enough to fit in one response) ends the transfer.
+    pub async fn read_block(&mut self, buf: &mut [u8]) -> Result<Option<CoapResponse>, OtError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let options = CoapOptions {
+            block2: Some(CoapBlock::new(self.num, false, self.size)),
+            ..Default::default()
+        };
+
+        let response = self
+            .ot
+            .coap_request_with(
+                CoapAgent::Plain,
+                &self.peer,
+                CoapType::Confirmable,
+                self.code,
+                self.uri_path,
+                &options,
+                &[],
+                buf,
+            )
+            .await?;
+
+        match response.block2 {
+            Some(block) if response.code.is_success() => {
+                // The server might have switched to a smaller block size
+                self.size = block.size;
+                self.num = block.num + 1;
+                self.done = !block.more;
+            }
+            _ => self.done = true,
+        }
+
+        Ok(Some(response))
+    }
+}
+
+/// A writer of a request payload transferred block by block (Block1)
+///
+/// Each block is sent with a separate confirmable request and acknowledged by the server
+/// (with a `CoapCode::CONTINUE` response) before the next one can be written.
+#[cfg(feature = "coap-block")]
+pub struct CoapBlockWriter<'r, 'a> {
+    ot: &'r OpenThread<'a>,
+    peer: SocketAddrV6,
+    code: CoapCode,
+    uri_path: &'r str,
+    size: CoapBlockSize,
+    offset: usize,
+}
+
+#[cfg(feature = "coap-block")]
+impl CoapBlockWriter<'_, '_> {
+    /// The block size currently in use; the size of all but the last block must be equal to it
+    pub fn block_size(&self) -> CoapBlockSize {
+        self.size
+    }
+
+    /// Write a non-final block of the payload.
+    ///
+    /// Arguments:
+    /// - `block`: The block; must be exactly `block_size` bytes long.
+    /// - `buf`: The buffer to store the response payload into.
+    ///
+    /// Returns:
+    /// - The response of the server; anything other than `CoapCode::CONTINUE` ends the transfer.
+    pub async fn write_block(
+        &mut self,
+        block: &[u8],
+        buf: &mut [u8],
+    ) -> Result<CoapResponse, OtError> {
+        if block.len() != self.size.bytes() {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let response = self.send(block, true, buf).await?;
+
+        self.offset += block.len();
+
+        if let Some(block) = response.block1 {
+            if block.size < self.size {
+                // The server asked for a smaller block size
+                self.size = block.size;
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Write the final block of the payload and return the final response of the server.
+    ///
+    /// Arguments:
+    /// - `block`: The block; at most `block_size` bytes long.
+    /// - `buf`: The buffer to store the response payload into.
+    pub async fn finish(mut self, block: &[u8], buf: &mut [u8]) -> Result<CoapResponse, OtError> {
+        if block.len() > self.size.bytes() {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        self.send(block, false, buf).await
+    }
+
+    async fn send(
+        &mut self,
+        block: &[u8],
+        more: bool,
+        buf: &mut [u8],
+    ) -> Result<CoapResponse, OtError> {
+        let options = CoapOptions {
+            block1: Some(CoapBlock::new(
+                (self.offset / self.size.bytes()) as _,
+                more,
+                self.size,
+            )),
+            ..Default::default()
+        };
+
+        self.ot
+            .coap_request_with(
+                CoapAgent::Plain,
+                &self.peer,
+                CoapType::Confirmable,
+                self.code,
+                self.uri_path,
+                &options,
+                block,
+                buf,
+            )
+            .await
+    }
+}
+
+/// A CoAP resource served by the OpenThread CoAP agent
+///
+/// Requests targeting the resource's URI path are received with [`CoapResource::recv`]
+/// and answered with [`CoapResource::respond`]:
+///
+/// ```ignore
+/// let resource = CoapResource::new(ot.clone(), "sensors/temp")?;
+///
+/// loop {
+///     let request = resource.recv(&mut buf).await?;
+///     // ... process `buf[..request.len]` ...
+///     resource.respond(CoapCode::CONTENT, b"21.5")?;
+/// }
+/// ```
+///
+/// The resource is unregistered when dropped.
+pub struct CoapResource<'a> {
+    /// The OpenThread stack that the resource is associated with.
+    ot: OpenThread<'a>,
+    /// The slot in the OpenThread stack's CoAP resource array that this resource occupies.
+    slot: usize,
+}
+
+impl<'a> CoapResource<'a> {
+    /// Register a new CoAP resource with the CoAP agent (`otCoapAddResource`).
+    ///
+    /// Arguments:
+    /// - `uri_path`: The URI path of the resource, e.g. `"sensors/temp"`;
+    ///   at most `COAP_MAX_URI_PATH_LEN` bytes.
+    pub fn new(ot: OpenThread<'a>, uri_path: &str) -> Result<Self, OtError> {
+        Self::add(ot, uri_path, CoapAgent::Plain)
+    }
+
+    /// Register a new CoAP resource with the given CoAP agent.
+    pub(crate) fn add(
+        ot: OpenThread<'a>,
+        uri_path: &str,
+        agent: CoapAgent,
+    ) -> Result<Self, OtError> {
+        let slot = {
+            let mut active_ot = ot.activate();
+            let state = active_ot.state();
+            let instance = state.ot.instance;
+            let coap = state.coap()?;
+
+            let slot = coap
+                .resources
+                .iter()
+                .position(|resource| !resource.taken)
+                .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+            let resource = &mut coap.resources[slot];
+            store_uri_path(uri_path, &mut resource.uri_path)?;
+
+            resource.ot_resource = otCoapResource {
+                mUriPath: resource.uri_path.as_ptr() as *const c_char,
+                mHandler: Some(plat_c_coap_request_handler),
+                mContext: slot as *mut c_void,
+                mNext: core::ptr::null_mut(),
+            };
+            resource.agent = agent;
+            resource.rx.reset();
+            resource.response = None;
+            resource.taken = true;
+
+            #[cfg(feature = "coap-observe")]
+            {
+                resource.observable = false;
+                resource.observers = [CoapObserverCtx::new(); COAP_MAX_OBSERVERS];
+                resource.response_observer = None;
+            }
+
+            agent.add_resource(instance, &mut resource.ot_resource);
+
+            slot
+        };
+
+        Ok(Self { ot, slot })
+    }
+
+    /// Register a new observable CoAP resource with the CoAP agent (`otCoapAddResource`).
+    ///
+    /// Observable resources keep track of the peers which register as observers (RFC 7641),
+    /// by sending a GET request with an Observe option of 0, and answering it with a successful response.
+    /// The new resource state is pushed to all observers with `notify`.
+    ///
+    /// At most `COAP_MAX_OBSERVERS` observers are tracked; once full, registration requests
+    /// are answered as plain GET requests.
+    ///
+    /// Arguments:
+    /// - `uri_path`: The URI path of the resource, e.g. `"sensors/temp"`;
+    ///   at most `COAP_MAX_URI_PATH_LEN` bytes.
+    #[cfg(feature = "coap-observe")]
+    pub fn new_observable(ot: OpenThread<'a>, uri_path: &str) -> Result<Self, OtError> {
+        let this = Self::new(ot, uri_path)?;
+
+        {
+            let mut ot = this.ot.activate();
+            let resource = &mut ot.state().coap()?.resources[this.slot];
+
+            resource.observable = true;
+        }
+
+        Ok(this)
+    }
+
+    /// Return the number of observers currently registered with the resource.
+    #[cfg(feature = "coap-observe")]
+    pub fn observers(&self) -> Result<usize, OtError> {
+        let mut ot = self.ot.activate();
+        let resource = &ot.state().coap()?.resources[self.slot];
+
+        Ok(resource
+            .observers
+            .iter()
+            .filter(|observer| observer.msg_info.is_some())
+            .count())
+    }
+
+    /// Notify all observers of the resource of its new state (`otCoapSendRequestWithParameters`).
+    ///
+    /// Notifications are sent as confirmable messages; observers which do not acknowledge
+    /// a notification (or reject it with a reset message) are removed.
+    ///
+    /// Arguments:
+    /// - `code`: The response code; usually `CoapCode::CONTENT`.
+    /// - `payload`: The new representation of the resource.
+    ///
+    /// Returns the last error encountered while sending the notifications, if any.
+    #[cfg(feature = "coap-observe")]
+    pub fn notify(&self, code: CoapCode, payload: &[u8]) -> Result<(), OtError> {
+        let mut ot = self.ot.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let resource = &mut state.coap()?.resources[self.slot];
+
+        // The Observe sequence number is 24 bits long (RFC 7641)
+        resource.observe_seq = (resource.observe_seq + 1) & 0xff_ffff;
+
+        let seq = resource.observe_seq;
+        let observers = resource.observers;
+        let agent = resource.agent;
+
+        let mut res = Ok(());
+
+        for (index, observer) in observers.iter().enumerate() {
+            let Some(msg_info) = observer.msg_info else {
+                continue;
+            };
+
+            // Not needless with `coap-block`
+            #[allow(clippy::needless_update)]
+            let options = CoapOptions {
+                observe: Some(seq),
+                token: Some((observer.token, observer.token_len)),
+                ..Default::default()
+            };
+
+            let msg = match coap_new_message(
+                instance,
+                CoapType::Confirmable,
+                code,
+                None,
+                &options,
+                payload,
+            ) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    res = Err(err);
+                    continue;
+                }
+            };
+
+            let context =
+                ((self.slot * COAP_MAX_OBSERVERS + index) << 16) | observer.generation as usize;
+
+            let sent = agent.send_request(
+                instance,
+                msg,
+                &msg_info,
+                Some(plat_c_coap_notification_handler),
+                context as *mut c_void,
+            );
+
+            if let Err(err) = sent {
+                unsafe { otMessageFree(msg) };
+                warn!("Failed to send CoAP notification: {}", err);
+                res = Err(err);
+            }
+        }
+
+        res
+    }
+
+    /// Wait until a request for the resource is available.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn wait_recv_available(&self) -> Result<(), OtError> {
+        poll_fn(move |cx| {
+            self.ot.activate().state().coap()?.resources[self.slot]
+                .rx
+                .poll_wait_signaled(cx)
+                .map(Ok)
+        })
+        .await
+    }
+
+    /// Receive a request for the resource.
+    /// If there is no request available, this function will async-wait until a request is available.
+    ///
+    /// The request must then be answered with `respond`; if it is not, the pending response
+    /// is discarded by the next `recv` call. Until the request is answered, new requests for the
+    /// resource are dropped (confirmable requests are retransmitted by their senders).
+    ///
+    /// Arguments:
+    /// - `buf`: The buffer to store the request payload into.
+    ///
+    /// Returns:
+    /// - The request; its payload is in `buf[..len]`, truncated to the buffer size.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn recv(&self, buf: &mut [u8]) -> Result<CoapRequest, OtError> {
+        {
+            let mut ot = self.ot.activate();
+            let coap = ot.state().coap()?;
+
+            let resource = &mut coap.resources[self.slot];
+
+            if let Some((msg, _)) = resource.response.take() {
+                warn!("Discarding the unsent response to the previous CoAP request");
+                unsafe { otMessageFree(msg) };
+            }
+
+            #[cfg(feature = "coap-observe")]
+            if let Some(index) = resource.response_observer.take() {
+                // The observer registration was not confirmed with a response
+                resource.observers[index].msg_info = None;
+            }
+        }
+
+        let mut request = poll_fn(move |cx| {
+            self.ot.activate().state().coap()?.resources[self.slot]
+                .rx
+                .poll_wait(cx)
+                .map(Ok::<_, OtError>)
+        })
+        .await?;
+
+        let mut ot = self.ot.activate();
+        let coap = ot.state().coap()?;
+
+        let offset = self.slot * coap.buf_len;
+        let data = &coap.resource_buffers[offset..offset + request.len];
+
+        request.len = request.len.min(buf.len());
+        buf[..request.len].copy_from_slice(&data[..request.len]);
+
+        debug!(
+            "Received CoAP request {} from {}: {}",
+            request.code,
+            request.peer,
+            Bytes(&buf[..request.len])
+        );
+
+        Ok(request)
+    }
+
+    /// Respond to the last request received with `recv`
+    /// (`otCoapSendResponseWithParameters`).
+    ///
+    /// Confirmable requests are answered with a piggybacked (ACK) response,
+    /// non-confirmable ones with a non-confirmable response.
+    ///
+    /// Arguments:
+    /// - `code`: The response code.
+    /// - `payload`: The response payload; may be empty.
+    ///
+    /// Returns an `INVALID_STATE` error if there is no request awaiting a response.
+    pub fn respond(&self, code: CoapCode, payload: &[u8]) -> Result<(), OtError> {
+        self.respond_with(code, &CoapOptions::default(), payload)
+    }
+
+    /// Respond to the last request received with `recv` with a block of a block-wise transfer.
+    ///
+    /// Arguments:
+    /// - `code`: The response code; `CoapCode::CONTINUE` when acknowledging a non-final
+    ///   block of the request payload.
+    /// - `block1`: The Block1 option acknowledging a block of the request payload, if any;
+    ///   usually the `block1` option of the request, echoed back.
+    /// - `block2`: The Block2 option describing `payload` as a block of the response payload, if any;
+    ///   usually the `block2` option of the request (if present), with `more` set
+    ///   if the response payload continues past `payload`.
+    /// - `payload`: The response payload (block); may be empty.
+    ///
+    /// Returns an `INVALID_STATE` error if there is no request awaiting a response.
+    #[cfg(feature = "coap-block")]
+    pub fn respond_block(
+        &self,
+        code: CoapCode,
+        block1: Option<CoapBlock>,
+        block2: Option<CoapBlock>,
+        payload: &[u8],
+    ) -> Result<(), OtError> {
+        // Not needless with `coap-observe`
+        #[allow(clippy::needless_update)]
+        let options = CoapOptions {
+            block1,
+            block2,
+            ..Default::default()
+        };
+
+        self.respond_with(code, &options, payload)
+    }
+
+    /// Same as `respond`, but also appending the given options to the response.
+    fn respond_with(
+        &self,
+        code: CoapCode,
+        options: &CoapOptions,
+        payload: &[u8],
+    ) -> Result<(), OtError> {
+        let mut ot = self.ot.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let coap = state.coap()?;
+
+        let resource = &mut coap.resources[self.slot];
+
+        let (msg, msg_info) = resource
+            .response
+            .take()
+            .ok_or(OtError::new(otError_OT_ERROR_INVALID_STATE))?;
+
+        #[allow(unused_mut)]
+        let mut options = *options;
+
+        #[cfg(feature = "coap-observe")]
+        if let Some(index) = resource.response_observer.take() {
+            if code.is_success() {
+                options.observe = Some(resource.observe_seq);
+            } else {
+                // Only a successful response registers the observer (RFC 7641)
+                resource.observers[index].msg_info = None;
+            }
+        }
+
+        unsafe { otCoapMessageSetCode(msg, code.into()) };
+
+        let res = options
+            .append(msg, None)
+            .and_then(|_| coap_append_payload(msg, payload))
+            .and_then(|_| resource.agent.send_response(instance, msg, &msg_info));
+
+        if res.is_err() {
+            unsafe { otMessageFree(msg) };
+        }
+
+        res
+    }
+}
+
+impl Drop for CoapResource<'_> {
+    fn drop(&mut self) {
+        let mut ot = self.ot.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let coap = unwrap!(state.coap());
+
+        let resource = &mut coap.resources[self.slot];
+
+        resource
+            .agent
+            .remove_resource(instance, &mut resource.ot_resource);
+
+        if let Some((msg, _)) = resource.response.take() {
+            unsafe { otMessageFree(msg) };
+        }
+
+        resource.taken = false;
+    }
+}
+
+/// The resources (data) that is necessary for the OpenThread stack to operate the CoAP agent.
+///
+/// A separate type so that it can be allocated outside of the OpenThread futures,
+/// thus avoiding expensive mem-moves.
+///
+/// Can also be statically-allocated.
+///
+/// Parameters:
+/// - `COAP_RESOURCES`: The maximum number of simultaneously registered `CoapResource`s.
+/// - `COAP_REQUESTS`: The maximum number of client requests simultaneously awaiting a response.
+/// - `COAP_BUF_SZ`: The size of the payload buffer of each resource and each client request.
+pub struct OtCoapResources<
+    const COAP_RESOURCES: usize = 2,
+    const COAP_REQUESTS: usize = 2,
+    const COAP_BUF_SZ: usize = 512,
+> {
+    /// The CoAP resources that are available for use.
+    resources: MaybeUninit<[CoapResourceCtx; COAP_RESOURCES]>,
+    /// The CoAP client requests that are available for use.
+    requests: MaybeUninit<[CoapRequestCtx; COAP_REQUESTS]>,
+    /// The buffers that are used to store the payloads of received requests.
+    resource_buffers: MaybeUninit<[[u8; COAP_BUF_SZ]; COAP_RESOURCES]>,
+    /// The buffers that are used to store the payloads of received responses.
+    request_buffers: MaybeUninit<[[u8; COAP_BUF_SZ]; COAP_REQUESTS]>,
+    /// The state of the OpenThread stack, from Rust POV.
+    state: MaybeUninit<RefCell<OtCoapState<'static>>>,
+}
+
+impl<const COAP_RESOURCES: usize, const COAP_REQUESTS: usize, const COAP_BUF_SZ: usize>
+    OtCoapResources<COAP_RESOURCES, COAP_REQUESTS, COAP_BUF_SZ>
+{
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_RESOURCE: CoapResourceCtx = CoapResourceCtx::new();
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_REQUEST: CoapRequestCtx = CoapRequestCtx::new();
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_BUFFERS: [u8; COAP_BUF_SZ] = [0; COAP_BUF_SZ];
+
+    /// Create a new `OtCoapResources` instance.
+    pub const fn new() -> Self {
+        Self {
+            resources: MaybeUninit::uninit(),
+            requests: MaybeUninit::uninit(),
+            resource_buffers: MaybeUninit::uninit(),
+            request_buffers: MaybeUninit::uninit(),
+            state: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Initialize the resources, as they start their life as `MaybeUninit` so as to avoid mem-moves.
+    ///
+    /// Returns:
+    /// - A reference to a `RefCell<OtCoapState>` value that represents the initialized OpenThread CoAP state.
+    pub(crate) fn init(&mut self) -> &RefCell<OtCoapState<'static>> {
+        self.resources.write([Self::INIT_RESOURCE; COAP_RESOURCES]);
+        self.requests.write([Self::INIT_REQUEST; COAP_REQUESTS]);
+        self.resource_buffers
+            .write([Self::INIT_BUFFERS; COAP_RESOURCES]);
+        self.request_buffers
+            .write([Self::INIT_BUFFERS; COAP_REQUESTS]);
+
+        let resources = unsafe { self.resources.assume_init_mut() };
+        let resources = unsafe {
+            core::mem::transmute::<
+                &mut [CoapResourceCtx; COAP_RESOURCES],
+                &'static mut [CoapResourceCtx; COAP_RESOURCES],
+            >(resources)
+        };
+
+        let requests = unsafe { self.requests.assume_init_mut() };
+        let requests = unsafe {
+            core::mem::transmute::<
+                &mut [CoapRequestCtx; COAP_REQUESTS],
+                &'static mut [CoapRequestCtx; COAP_REQUESTS],
+            >(requests)
+        };
+
+        let resource_buffers: &mut [[u8; COAP_BUF_SZ]; COAP_RESOURCES] =
+            unsafe { self.resource_buffers.assume_init_mut() };
+        let resource_buffers: &'static mut [u8] = unsafe {
+            core::slice::from_raw_parts_mut(
+                resource_buffers.as_mut_ptr() as *mut _,
+                COAP_BUF_SZ * COAP_RESOURCES,
+            )
+        };
+
+        let request_buffers: &mut [[u8; COAP_BUF_SZ]; COAP_REQUESTS] =
+            unsafe { self.request_buffers.assume_init_mut() };
+        let request_buffers: &'static mut [u8] = unsafe {
+            core::slice::from_raw_parts_mut(
+                request_buffers.as_mut_ptr() as *mut _,
+                COAP_BUF_SZ * COAP_REQUESTS,
+            )
+        };
+
+        self.state.write(RefCell::new(OtCoapState {
+            resources,
+            requests,
+            resource_buffers,
+            request_buffers,
+            buf_len: COAP_BUF_SZ,
+            #[cfg(feature = "coaps")]
+            secure: CoapSecureCtx::new(),
+        }));
+
+        info!("OpenThread CoAP resources initialized");
+
+        unsafe { self.state.assume_init_mut() }
+    }
+}
+
+impl<const COAP_RESOURCES: usize, const COAP_REQUESTS: usize, const COAP_BUF_SZ: usize> Default
+    for OtCoapResources<COAP_RESOURCES, COAP_REQUESTS, COAP_BUF_SZ>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The CoAP state of the OpenThread stack, from Rust POV.
+///
+/// This data lives behind a `RefCell` and is mutably borrowed each time
+/// the OpenThread stack is activated, by creating an `OtContext` instance.
+pub(crate) struct OtCoapState<'a> {
+    /// The CoAP resources that are available for use.
+    resources: &'a mut [CoapResourceCtx],
+    /// The CoAP client requests that are available for use.
+    requests: &'a mut [CoapRequestCtx],
+    /// The buffers that are used to store the payloads of received requests.
+    resource_buffers: &'a mut [u8],
+    /// The buffers that are used to store the payloads of received responses.
+    request_buffers: &'a mut [u8],
+    /// The length of each buffer.
+    buf_len: usize,
+    /// The state of the CoAP secure agent.
+    #[cfg(feature = "coaps")]
+    pub(crate) secure: CoapSecureCtx,
+}
+
+/// The internal data associated with each `CoapResource` instance.
+pub(crate) struct CoapResourceCtx {
+    /// Whether the data (slot) is taken by a `CoapResource` instance or not.
+    taken: bool,
+    /// The CoAP agent the resource is registered with.
+    agent: CoapAgent,
+    /// The OpenThread native CoAP resource.
+    ot_resource: otCoapResource,
+    /// The NUL-terminated URI path of the resource, referenced by `ot_resource`.
+    uri_path: [u8; COAP_MAX_URI_PATH_LEN + 1],
+    /// The signal that is triggered when a request is received.
+    rx: Signal<CoapRequest>,
+    /// The response (already initialized from the request) to the request being processed,
+    /// along with the message info it should be sent with.
+    response: Option<(*mut otMessage, otMessageInfo)>,
+    /// Whether the resource is observable.
+    #[cfg(feature = "coap-observe")]
+    observable: bool,
+    /// The observers of the resource.
+    #[cfg(feature = "coap-observe")]
+    observers: [CoapObserverCtx; COAP_MAX_OBSERVERS],
+    /// The index of the observer registered by the request being processed, if any.
+    #[cfg(feature = "coap-observe")]
+    response_observer: Option<usize>,
+    /// The sequence number of the last notification (Observe option value).
+    #[cfg(feature = "coap-observe")]
+    observe_seq: u32,
+}
+
+impl CoapResourceCtx {
+    /// Create a new `CoapResourceCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            taken: false,
+            agent: CoapAgent::Plain,
+            ot_resource: otCoapResource {
+                mUriPath: core::ptr::null(),
+                mHandler: None,
+                mContext: core::ptr::null_mut(),
+                mNext: core::ptr::null_mut(),
+            },
+            uri_path: [0; COAP_MAX_URI_PATH_LEN + 1],
+            rx: Signal::new(),
+            response: None,
+            #[cfg(feature = "coap-observe")]
+            observable: false,
+            #[cfg(feature = "coap-observe")]
+            observers: [CoapObserverCtx::new(); COAP_MAX_OBSERVERS],
+            #[cfg(feature = "coap-observe")]
+            response_observer: None,
+            #[cfg(feature = "coap-observe")]
+            observe_seq: 0,
+        }
+    }
+
+    /// Find the observer matching the peer and the token of a request.
+    #[cfg(feature = "coap-observe")]
+    fn find_observer(&self, msg: *const otMessage, msg_info: &otMessageInfo) -> Option<usize> {
+        let peer = to_sock_addr(&msg_info.mPeerAddr, msg_info.mPeerPort, 0);
+        let token = coap_token(msg);
+
+        self.observers.iter().position(|observer| {
+            observer.msg_info.is_some_and(|info| {
+                to_sock_addr(&info.mPeerAddr, info.mPeerPort, 0) == peer
+                    && observer.token[..observer.token_len as usize] == token[..]
+            })
+        })
+    }
+
+    /// Register (or re-register) the sender of a request as an observer of the resource.
+    ///
+    /// Returns the index of the observer, or `None` if there is no room for it.
+    #[cfg(feature = "coap-observe")]
+    fn register_observer(
+        &mut self,
+        msg: *const otMessage,
+        msg_info: &otMessageInfo,
+    ) -> Option<usize> {
+        let index = self.find_observer(msg, msg_info).or_else(|| {
+            self.observers
+                .iter()
+                .position(|observer| observer.msg_info.is_none())
+        })?;
+
+        let token = coap_token(msg);
+
+        let observer = &mut self.observers[index];
+        observer.msg_info = Some(*msg_info);
+        observer.generation = observer.generation.wrapping_add(1);
+        observer.token[..token.len()].copy_from_slice(token);
+        observer.token_len = token.len() as _;
+
+        Some(index)
+    }
+}
+
+/// An observer of an observable `CoapResource`.
+#[cfg(feature = "coap-observe")]
+#[derive(Copy, Clone)]
+pub(crate) struct CoapObserverCtx {
+    /// The message info to send notifications with; `None` if the slot is free.
+    msg_info: Option<otMessageInfo>,
+    /// Incremented on each registration, so that late notification outcomes
+    /// for a previous observer in the same slot are ignored.
+    generation: u16,
+    /// The token of the registration request, used for the notifications.
+    token: [u8; OT_COAP_MAX_TOKEN_LENGTH as usize],
+    /// The length of the token.
+    token_len: u8,
+}
+
+#[cfg(feature = "coap-observe")]
+impl CoapObserverCtx {
+    /// Create a new `CoapObserverCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            msg_info: None,
+            generation: 0,
+            token: [0; OT_COAP_MAX_TOKEN_LENGTH as usize],
+            token_len: 0,
+        }
+    }
+}
+
+/// The internal data associated with each client request in flight.
+pub(crate) struct CoapRequestCtx {
+    /// Whether the data (slot) is taken by a request or not.
+    taken: bool,
+    /// Whether the future which sent the request was dropped before the response arrived.
+    abandoned: bool,
+    /// Whether OpenThread is done with the request, i.e. no more responses will arrive.
+    finished: bool,
+    /// Whether the request registers an observation, i.e. multiple responses might arrive.
+    #[cfg(feature = "coap-observe")]
+    observe: bool,
+    /// The signal that is triggered when the response (or an error) is received.
+    rx: Signal<Result<CoapResponse, otError>>,
+}
+
+impl CoapRequestCtx {
+    /// Create a new `CoapRequestCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            taken: false,
+            abandoned: false,
+            finished: false,
+            #[cfg(feature = "coap-observe")]
+            observe: false,
+            rx: Signal::new(),
+        }
+    }
+}
+
+/// The options of an outgoing CoAP message, other than its URI path.
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct CoapOptions {
+    /// The token to use instead of a random one, with its length
+    #[cfg(feature = "coap-observe")]
+    pub(crate) token: Option<([u8; OT_COAP_MAX_TOKEN_LENGTH as usize], u8)>,
+    /// The Observe option
+    #[cfg(feature = "coap-observe")]
+    pub(crate) observe: Option<u32>,
+    /// The Block1 option
+    #[cfg(feature = "coap-block")]
+    pub(crate) block1: Option<CoapBlock>,
+    /// The Block2 option
+    #[cfg(feature = "coap-block")]
+    pub(crate) block2: Option<CoapBlock>,
+}
+
+impl CoapOptions {
+    /// Append the URI path (if any) and the options to a CoAP message.
+    ///
+    /// OpenThread requires the options to be appended in increasing option number order.
+    ///
+    /// Arguments:
+    /// - `msg`: The message, with no options appended yet.
+    /// - `uri_path`: The URI path as a NUL-terminated C string, if any.
+    fn append(&self, msg: *mut otMessage, uri_path: Option<&[u8]>) -> Result<(), OtError> {
+        #[cfg(feature = "coap-observe")]
+        if let Some(observe) = self.observe {
+            ot!(unsafe { otCoapMessageAppendObserveOption(msg, observe) })?;
+        }
+
+        if let Some(uri_path) = uri_path {
+            ot!(unsafe {
+                otCoapMessageAppendUriPathOptions(msg, uri_path.as_ptr() as *const c_char)
+            })?;
+        }
+
+        #[cfg(feature = "coap-block")]
+        if let Some(block) = self.block2 {
+            ot!(unsafe {
+                otCoapMessageAppendBlock2Option(msg, block.num, block.more, block.size.into())
+            })?;
+        }
+
+        #[cfg(feature = "coap-block")]
+        if let Some(block) = self.block1 {
+            ot!(unsafe {
+                otCoapMessageAppendBlock1Option(msg, block.num, block.more, block.size.into())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the token of a new CoAP request message: either the configured one or a random one.
+    fn init_token(&self, msg: *mut otMessage) -> Result<(), OtError> {
+        #[cfg(feature = "coap-observe")]
+        if let Some((token, token_len)) = self.token {
+            return ot!(unsafe { otCoapMessageSetToken(msg, token.as_ptr(), token_len) });
+        }
+
+        unsafe { otCoapMessageGenerateToken(msg, OT_COAP_DEFAULT_TOKEN_LENGTH as _) };
+
+        Ok(())
+    }
+}
+
+/// The CoAP agent of OpenThread that a resource is registered with, or a request is sent through.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum CoapAgent {
+    /// The application CoAP agent
+    Plain,
+    /// The application CoAP secure (DTLS) agent; always talks to the peer of its session
+    #[cfg(feature = "coaps")]
+    Secure,
+}
+
+impl CoapAgent {
+    /// Register a resource with the agent.
+    fn add_resource(self, instance: *mut otInstance, resource: &mut otCoapResource) {
+        match self {
+            Self::Plain => unsafe { otCoapAddResource(instance, resource) },
+            #[cfg(feature = "coaps")]
+            Self::Secure => unsafe { otCoapSecureAddResource(instance, resource) },
+        }
+    }
+
+    /// Unregister a resource from the agent.
+    fn remove_resource(self, instance: *mut otInstance, resource: &mut otCoapResource) {
+        match self {
+            Self::Plain => unsafe { otCoapRemoveResource(instance, resource) },
+            #[cfg(feature = "coaps")]
+            Self::Secure => unsafe { otCoapSecureRemoveResource(instance, resource) },
+        }
+    }
+
+    /// Send a request (or a notification) message.
+    ///
+    /// `msg_info` is ignored by the secure agent.
+    fn send_request(
+        self,
+        instance: *mut otInstance,
+        msg: *mut otMessage,
+        msg_info: &otMessageInfo,
+        handler: otCoapResponseHandler,
+        context: *mut c_void,
+    ) -> Result<(), OtError> {
+        match self {
+            Self::Plain => ot!(unsafe {
+                otCoapSendRequestWithParameters(
+                    instance,
+                    msg,
+                    msg_info,
+                    handler,
+                    context,
+                    core::ptr::null(),
+                )
+            }),
+            #[cfg(feature = "coaps")]
+            Self::Secure => {
+                let _ = msg_info;
+                ot!(unsafe { otCoapSecureSendRequest(instance, msg, handler, context) })
+            }
+        }
+    }
+
+    /// Send a response message.
+    fn send_response(
+        self,
+        instance: *mut otInstance,
+        msg: *mut otMessage,
+        msg_info: &otMessageInfo,
+    ) -> Result<(), OtError> {
+        match self {
+            Self::Plain => ot!(unsafe {
+                otCoapSendResponseWithParameters(instance, msg, msg_info, core::ptr::null())
+            }),
+            #[cfg(feature = "coaps")]
+            Self::Secure => ot!(unsafe { otCoapSecureSendResponse(instance, msg, msg_info) }),
+        }
+    }
+}
+
+/// Copy `uri_path` as a NUL-terminated C string into `buf`.
+fn store_uri_path(
+    uri_path: &str,
+    buf: &mut [u8; COAP_MAX_URI_PATH_LEN + 1],
+) -> Result<(), OtError> {
+    if uri_path.len() > COAP_MAX_URI_PATH_LEN || uri_path.as_bytes().contains(&0) {
+        Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+    }
+
+    buf[..uri_path.len()].copy_from_slice(uri_path.as_bytes());
+    buf[uri_path.len()] = 0;
+
+    Ok(())
+}
+
+/// Create a new CoAP message with the given options, (optional) URI path and payload.
+///
+/// The message token is random, unless `options` specifies one.
+fn coap_new_message(
+    instance: *mut otInstance,
+    msg_type: CoapType,
+    code: CoapCode,
+    uri_path: Option<&str>,
+    options: &CoapOptions,
+    payload: &[u8],
+) -> Result<*mut otMessage, OtError> {
+    let mut uri_buf = [0; COAP_MAX_URI_PATH_LEN + 1];
+    if let Some(uri_path) = uri_path {
+        store_uri_path(uri_path, &mut uri_buf)?;
+    }
+
+    let msg = unsafe { otCoapNewMessage(instance, core::ptr::null()) };
+    if msg.is_null() {
+        Err(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+    }
+
+    unsafe { otCoapMessageInit(msg, msg_type.into(), code.into()) };
+
+    let res = options
+        .init_token(msg)
+        .and_then(|_| options.append(msg, uri_path.map(|_| &uri_buf[..])))
+        .and_then(|_| coap_append_payload(msg, payload));
+
+    if let Err(err) = res {
+        unsafe { otMessageFree(msg) };
+        Err(err)?;
+    }
+
+    Ok(msg)
+}
+
+/// Append the payload (if non-empty), preceded by the payload marker, to a CoAP message.
+fn coap_append_payload(msg: *mut otMessage, payload: &[u8]) -> Result<(), OtError> {
+    if !payload.is_empty() {
+        ot!(unsafe { otCoapMessageSetPayloadMarker(msg) })?;
+        ot!(unsafe { otMessageAppend(msg, payload.as_ptr() as *const _, payload.len() as _) })?;
+    }
+
+    Ok(())
+}
+
+/// Create the message info for sending a message to `peer`.
+fn coap_peer_info(peer: &SocketAddrV6) -> otMessageInfo {
+    let mut msg_info = otMessageInfo::default();
+
+    msg_info.mPeerAddr.mFields.m8 = peer.ip().octets();
+    msg_info.mPeerPort = peer.port();
+
+    msg_info
+}
+
+/// Read the payload of a CoAP message into `buf`.
+///
+/// Returns the length of the payload, or `None` if the payload does not fit in `buf`.
+fn coap_read_payload(msg: *const otMessage, buf: &mut [u8]) -> Option<usize> {
+    let offset = unsafe { otMessageGetOffset(msg) };
+    let len = unsafe { otMessageGetLength(msg) }.saturating_sub(offset) as usize;
+
+    if len > buf.len() {
+        return None;
+    }
+
+    unsafe { otMessageRead(msg, offset, buf.as_mut_ptr() as *mut _, len as _) };
+
+    Some(len)
+}
+
+/// Read the value of an unsigned integer option of a CoAP message, if present and valid.
+#[cfg(any(feature = "coap-block", feature = "coap-observe"))]
+fn coap_read_uint_option(msg: *const otMessage, option: otCoapOptionType) -> Option<u64> {
+    let mut iter = otCoapOptionIterator::default();
+    ot!(unsafe { otCoapOptionIteratorInit(&mut iter, msg) }).ok()?;
+
+    if unsafe { otCoapOptionIteratorGetFirstOptionMatching(&mut iter, option as _) }.is_null() {
+        return None;
+    }
+
+    let mut value = 0;
+    ot!(unsafe { otCoapOptionIteratorGetOptionUintValue(&mut iter, &mut value) }).ok()?;
+
+    Some(value)
+}
+
+/// Read the Block1 or Block2 option of a CoAP message, if present and valid.
+#[cfg(feature = "coap-block")]
+fn coap_read_block(msg: *const otMessage, option: otCoapOptionType) -> Option<CoapBlock> {
+    coap_read_uint_option(msg, option).and_then(CoapBlock::from_ot)
+}
+
+/// Read the Observe option of a CoAP message, if present and valid.
+#[cfg(feature = "coap-observe")]
+fn coap_read_observe(msg: *const otMessage) -> Option<u32> {
+    coap_read_uint_option(msg, otCoapOptionType_OT_COAP_OPTION_OBSERVE)
+        .and_then(|value| u32::try_from(value).ok())
+}
+
+/// Return the token of a CoAP message.
+#[cfg(feature = "coap-observe")]
+fn coap_token<'m>(msg: *const otMessage) -> &'m [u8] {
+    let len = unsafe { otCoapMessageGetTokenLength(msg) } as usize;
+
+    unsafe { core::slice::from_raw_parts(otCoapMessageGetToken(msg), len) }
+}
+
+unsafe extern "C" fn plat_c_coap_request_handler(
+    slot: *mut c_void,
+    msg: *mut otMessage,
+    msg_info: *const otMessageInfo,
+) {
+    let slot: usize = slot as usize;
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let state = ot.state();
+    let instance = state.ot.instance;
+    let Ok(coap) = state.coap() else {
+        // We cannot receive requests if there are no CoAP resources
+        warn!("CoAP request without CoAP resources, ignoring");
+        return;
+    };
+
+    let resource = &mut coap.resources[slot];
+    if resource.rx.signaled() || resource.response.is_some() {
+        // Drop the request because the previous one is not handled yet
+        warn!("Dropping CoAP request, previous one not handled yet");
+        return;
+    }
+
+    let msg_info = unsafe { &*msg_info };
+    let msg_type = CoapType::from(unsafe { otCoapMessageGetType(msg) });
+
+    let response = unsafe { otCoapNewMessage(instance, core::ptr::null()) };
+    if response.is_null() {
+        warn!("Dropping CoAP request, no buffers for the response");
+        return;
+    }
+
+    let response_type = if matches!(msg_type, CoapType::Confirmable) {
+        CoapType::Acknowledgment
+    } else {
+        CoapType::NonConfirmable
+    };
+
+    if ot!(unsafe {
+        otCoapMessageInitResponse(
+            response,
+            msg,
+            response_type.into(),
+            CoapCode::CONTENT.into(),
+        )
+    })
+    .is_err()
+    {
+        unsafe { otMessageFree(response) };
+        warn!("Dropping CoAP request, failed to initialize the response");
+        return;
+    }
+
+    let buf_len = coap.buf_len;
+    let offset = slot * buf_len;
+    let buf = &mut coap.resource_buffers[offset..offset + buf_len];
+
+    let Some(len) = coap_read_payload(msg, buf) else {
+        warn!("Rejecting CoAP request, payload too large");
+
+        unsafe { otCoapMessageSetCode(response, CoapCode::REQUEST_TOO_LARGE.into()) };
+
+        if coap.resources[slot]
+            .agent
+            .send_response(instance, response, msg_info)
+            .is_err()
+        {
+            unsafe { otMessageFree(response) };
+        }
+
+        return;
+    };
+
+    let code: CoapCode = unsafe { otCoapMessageGetCode(msg) }.into();
+
+    #[cfg(feature = "coap-observe")]
+    let observe = coap_read_observe(msg);
+
+    let resource = &mut coap.resources[slot];
+
+    #[cfg(feature = "coap-observe")]
+    if resource.observable && code == CoapCode::GET {
+        match observe {
+            Some(0) => {
+                resource.response_observer = resource.register_observer(msg, msg_info);
+                if resource.response_observer.is_none() {
+                    warn!("No room for a new CoAP observer, serving the request as a plain GET");
+                }
+            }
+            Some(1) => {
+                if let Some(index) = resource.find_observer(msg, msg_info) {
+                    resource.observers[index].msg_info = None;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    resource.response = Some((response, *msg_info));
+    resource.rx.signal(CoapRequest {
+        code,
+        msg_type,
+        len,
+        local: to_sock_addr(&msg_info.mSockAddr, msg_info.mSockPort, 0),
+        peer: to_sock_addr(&msg_info.mPeerAddr, msg_info.mPeerPort, 0),
+        #[cfg(feature = "coap-block")]
+        block1: coap_read_block(msg, otCoapOptionType_OT_COAP_OPTION_BLOCK1),
+        #[cfg(feature = "coap-block")]
+        block2: coap_read_block(msg, otCoapOptionType_OT_COAP_OPTION_BLOCK2),
+        #[cfg(feature = "coap-observe")]
+        observe,
+    });
+}
+
+unsafe extern "C" fn plat_c_coap_response_handler(
+    slot: *mut c_void,
+    msg: *mut otMessage,
+    msg_info: *const otMessageInfo,
+    error: otError,
+) {
+    let slot: usize = slot as usize;
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(coap) = ot.state().coap() else {
+        // We cannot send requests if there are no CoAP resources
+        warn!("CoAP response without CoAP resources, ignoring");
+        return;
+    };
+
+    let res = if error == otError_OT_ERROR_NONE && !msg.is_null() {
+        let msg_info = unsafe { &*msg_info };
+
+        let buf_len = coap.buf_len;
+        let offset = slot * buf_len;
+        let buf = &mut coap.request_buffers[offset..offset + buf_len];
+
+        match coap_read_payload(msg, buf) {
+            Some(len) => Ok(CoapResponse {
+                code: unsafe { otCoapMessageGetCode(msg) }.into(),
+                msg_type: unsafe { otCoapMessageGetType(msg) }.into(),
+                len,
+                peer: to_sock_addr(&msg_info.mPeerAddr, msg_info.mPeerPort, 0),
+                #[cfg(feature = "coap-block")]
+                block1: coap_read_block(msg, otCoapOptionType_OT_COAP_OPTION_BLOCK1),
+                #[cfg(feature = "coap-block")]
+                block2: coap_read_block(msg, otCoapOptionType_OT_COAP_OPTION_BLOCK2),
+                #[cfg(feature = "coap-observe")]
+                observe: coap_read_observe(msg),
+            }),
+            None => {
+                warn!("Dropping CoAP response, payload too large");
+                Err(otError_OT_ERROR_NO_BUFS)
+            }
+        }
+    } else if error == otError_OT_ERROR_NONE {
+        // An observation was cancelled
+        Err(otError_OT_ERROR_ABORT)
+    } else {
+        Err(error)
+    };
+
+    let request = &mut coap.requests[slot];
+
+    // Notifications of an observation keep coming until a response without
+    // an Observe option, or an error, arrives
+    #[cfg(feature = "coap-observe")]
+    let finished = !request.observe || !matches!(&res, Ok(response) if response.observe.is_some());
+    #[cfg(not(feature = "coap-observe"))]
+    let finished = true;
+
+    request.finished = finished;
+
+    if request.abandoned {
+        // The future which sent the request is gone; just release the slot
+        // once OpenThread is done with it
+        if finished {
+            request.abandoned = false;
+            request.taken = false;
+        }
+
+        return;
+    }
+
+    request.rx.signal(res);
+}
+
+#[cfg(feature = "coap-observe")]
+unsafe extern "C" fn plat_c_coap_notification_handler(
+    context: *mut c_void,
+    _msg: *mut otMessage,
+    _msg_info: *const otMessageInfo,
+    error: otError,
+) {
+    if error == otError_OT_ERROR_NONE {
+        return;
+    }
+
+    let context = context as usize;
+    let slot = (context >> 16) / COAP_MAX_OBSERVERS;
+    let index = (context >> 16) % COAP_MAX_OBSERVERS;
+    let generation = context as u16;
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(coap) = ot.state().coap() else {
+        // We cannot send notifications if there are no CoAP resources
+        warn!("CoAP notification response without CoAP resources, ignoring");
+        return;
+    };
+
+    let resource = &mut coap.resources[slot];
+    let observer = &mut resource.observers[index];
+
+    if resource.taken && observer.msg_info.is_some() && observer.generation == generation {
+        // The observer did not acknowledge (or rejected) the notification
+        info!("Removing CoAP observer: {}", OtError::new(error));
+        observer.msg_info = None;
+    }
+}
@@ -0,0 +1,455 @@
+//! CoAP secure API: OpenThread's application CoAP secure agent (CoAP over DTLS, RFC 7252 section 9),
+//! bridged into async Rust.
+//!
+//! The secure agent maintains a single DTLS session at a time. It is started with
+//! [`OpenThread::coaps_start`], after which the credentials are configured, either a
+//! pre-shared key ([`OpenThread::coaps_set_psk`]) or an X.509 certificate with its private key
+//! ([`OpenThread::coaps_set_certificate`], optionally with [`OpenThread::coaps_set_ca_certificate_chain`]
+//! for verifying the peer).
+//!
+//! Client-side, [`OpenThread::coaps_connect`] performs the DTLS handshake with a peer,
+//! after which [`OpenThread::coaps_request`] sends requests over the session. Server-side,
+//! [`CoapResource::new_secure`] registers resources with the secure agent, and
+//! [`OpenThread::coaps_wait_connected`] awaits a peer connecting to us.
+//!
+//! The secure agent shares the request and resource slots of the `OtCoapResources` the
+//! `OpenThread` instance was created with (see [`OpenThreadBuilder::coap`](crate::OpenThreadBuilder::coap)).
+
+use core::ffi::c_void;
+use core::fmt::{self, Display};
+use core::future::poll_fn;
+use core::net::SocketAddrV6;
+
+use crate::signal::Signal;
+use crate::sys::{
+    otCoapSecureConnect, otCoapSecureConnectEvent,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_CONNECTED,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_ERROR,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_LOCAL_CLOSED,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_MAX_ATTEMPTS,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_PEER_CLOSED,
+    otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_TIMEOUT, otCoapSecureDisconnect,
+    otCoapSecureIsConnected, otCoapSecureSetCaCertificateChain, otCoapSecureSetCertificate,
+    otCoapSecureSetClientConnectEventCallback, otCoapSecureSetPsk, otCoapSecureSetSslAuthMode,
+    otCoapSecureStart, otCoapSecureStop, otError_OT_ERROR_ABORT, otError_OT_ERROR_FAILED,
+    otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_INVALID_STATE,
+    otError_OT_ERROR_RESPONSE_TIMEOUT, otError_OT_ERROR_SECURITY,
+};
+use crate::{
+    ot, to_ot_addr, CoapAgent, CoapCode, CoapOptions, CoapResource, CoapResponse, CoapType,
+    OpenThread, OtContext, OtError,
+};
+
+/// The default UDP port of the CoAP secure agent (5684)
+pub const COAPS_DEFAULT_PORT: u16 = 5684;
+
+/// The maximum length of a CoAP secure pre-shared key
+pub const COAPS_MAX_PSK_LEN: usize = 32;
+
+/// An error establishing (or keeping) a CoAP secure (DTLS) session
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CoapSecureError {
+    /// The peer closed the session, e.g. because it rejected our credentials
+    PeerClosed,
+    /// The session was closed locally, e.g. with `OpenThread::coaps_disconnect`
+    LocalClosed,
+    /// The maximum number of connection attempts was reached
+    MaxAttempts,
+    /// The handshake failed, e.g. because the credentials of the peer could not be verified
+    HandshakeFailed,
+    /// The handshake (or the established session) timed out
+    Timeout,
+    /// Any other OpenThread error
+    Ot(OtError),
+}
+
+impl CoapSecureError {
+    /// Map a connection event of the CoAP secure agent to the outcome of a handshake.
+    #[allow(non_upper_case_globals)]
+    fn from_event(event: otCoapSecureConnectEvent) -> Result<(), Self> {
+        match event {
+            otCoapSecureConnectEvent_OT_COAP_SECURE_CONNECTED => Ok(()),
+            otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_PEER_CLOSED => {
+                Err(Self::PeerClosed)
+            }
+            otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_LOCAL_CLOSED => {
+                Err(Self::LocalClosed)
+            }
+            otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_MAX_ATTEMPTS => {
+                Err(Self::MaxAttempts)
+            }
+            otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_TIMEOUT => Err(Self::Timeout),
+            otCoapSecureConnectEvent_OT_COAP_SECURE_DISCONNECTED_ERROR => {
+                Err(Self::HandshakeFailed)
+            }
+            _ => Err(Self::Ot(OtError::new(otError_OT_ERROR_FAILED))),
+        }
+    }
+}
+
+impl From<OtError> for CoapSecureError {
+    fn from(error: OtError) -> Self {
+        Self::Ot(error)
+    }
+}
+
+impl From<CoapSecureError> for OtError {
+    fn from(error: CoapSecureError) -> Self {
+        match error {
+            CoapSecureError::PeerClosed | CoapSecureError::LocalClosed => {
+                OtError::new(otError_OT_ERROR_ABORT)
+            }
+            CoapSecureError::MaxAttempts => OtError::new(otError_OT_ERROR_FAILED),
+            CoapSecureError::HandshakeFailed => OtError::new(otError_OT_ERROR_SECURITY),
+            CoapSecureError::Timeout => OtError::new(otError_OT_ERROR_RESPONSE_TIMEOUT),
+            CoapSecureError::Ot(error) => error,
+        }
+    }
+}
+
+impl Display for CoapSecureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PeerClosed => write!(f, "Closed by peer"),
+            Self::LocalClosed => write!(f, "Closed locally"),
+            Self::MaxAttempts => write!(f, "Max connection attempts reached"),
+            Self::HandshakeFailed => write!(f, "Handshake failed"),
+            Self::Timeout => write!(f, "Timeout"),
+            Self::Ot(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for CoapSecureError {}
+
+impl<'a> OpenThread<'a> {
+    /// Start the CoAP secure agent (`otCoapSecureStart`), binding it to the given UDP port.
+    ///
+    /// Arguments:
+    /// - `port`: The local UDP port; usually `COAPS_DEFAULT_PORT`.
+    pub fn coaps_start(&self, port: u16) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+
+        state.coap()?.secure.event.reset();
+
+        ot!(unsafe { otCoapSecureStart(instance, port) })?;
+
+        // Also report the session events when a peer connects to us
+        unsafe {
+            otCoapSecureSetClientConnectEventCallback(
+                instance,
+                Some(plat_c_coaps_connect_handler),
+                core::ptr::null_mut(),
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Stop the CoAP secure agent (`otCoapSecureStop`), closing the session, if any.
+    pub fn coaps_stop(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+
+        state.coap()?.secure.peer = None;
+
+        unsafe { otCoapSecureStop(instance) };
+
+        Ok(())
+    }
+
+    /// Set the pre-shared key and its identity (`otCoapSecureSetPsk`) used for the next sessions,
+    /// with the `TLS_PSK_WITH_AES_128_CCM_8` cipher suite.
+    ///
+    /// Arguments:
+    /// - `psk`: The pre-shared key; at most `COAPS_MAX_PSK_LEN` bytes.
+    /// - `identity`: The identity of the pre-shared key.
+    pub fn coaps_set_psk(&self, psk: &[u8], identity: &[u8]) -> Result<(), OtError> {
+        if psk.is_empty() || psk.len() > COAPS_MAX_PSK_LEN || identity.len() > u16::MAX as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.coap()?;
+
+        // The key and the identity are copied by OpenThread
+        unsafe {
+            otCoapSecureSetPsk(
+                state.ot.instance,
+                psk.as_ptr(),
+                psk.len() as _,
+                identity.as_ptr(),
+                identity.len() as _,
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Set the X.509 certificate of this device and its private key (`otCoapSecureSetCertificate`)
+    /// used for the next sessions, with the `TLS_ECDHE_ECDSA_WITH_AES_128_CCM_8` cipher suite.
+    ///
+    /// OpenThread does not copy the certificate and the key, hence they must outlive
+    /// the `OpenThread` instance.
+    ///
+    /// Arguments:
+    /// - `certificate`: The PEM-formatted certificate, including its terminating NUL byte.
+    /// - `private_key`: The PEM-formatted private key, including its terminating NUL byte.
+    pub fn coaps_set_certificate(
+        &self,
+        certificate: &'a [u8],
+        private_key: &'a [u8],
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.coap()?;
+
+        unsafe {
+            otCoapSecureSetCertificate(
+                state.ot.instance,
+                certificate.as_ptr(),
+                certificate.len() as _,
+                private_key.as_ptr(),
+                private_key.len() as _,
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Set the trusted CA certificate chain (`otCoapSecureSetCaCertificateChain`),
+    /// used to verify the certificate of the peer.
+    ///
+    /// OpenThread does not copy the chain, hence it must outlive the `OpenThread` instance.
+    ///
+    /// Arguments:
+    /// - `chain`: The PEM-formatted CA certificate chain, including its terminating NUL byte.
+    pub fn coaps_set_ca_certificate_chain(&self, chain: &'a [u8]) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.coap()?;
+
+        unsafe {
+            otCoapSecureSetCaCertificateChain(state.ot.instance, chain.as_ptr(), chain.len() as _)
+        };
+
+        Ok(())
+    }
+
+    /// Enable or disable the verification of the certificate of the peer
+    /// (`otCoapSecureSetSslAuthMode`).
+    ///
+    /// Must be called before `coaps_start`.
+    pub fn coaps_set_verify_peer_certificate(&self, verify: bool) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.coap()?;
+
+        unsafe { otCoapSecureSetSslAuthMode(state.ot.instance, verify) };
+
+        Ok(())
+    }
+
+    /// Open a DTLS session with a peer (`otCoapSecureConnect`) and wait for the handshake to complete.
+    ///
+    /// Dropping the returned future before the handshake completes closes the session.
+    ///
+    /// Arguments:
+    /// - `peer`: The socket address of the peer; usually on port `COAPS_DEFAULT_PORT`.
+    pub async fn coaps_connect(&self, peer: &SocketAddrV6) -> Result<(), CoapSecureError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+            let instance = state.ot.instance;
+            let secure = &mut state.coap()?.secure;
+
+            secure.event.reset();
+            secure.peer = None;
+
+            let sock_addr = to_ot_addr(peer);
+
+            ot!(unsafe {
+                otCoapSecureConnect(
+                    instance,
+                    &sock_addr,
+                    Some(plat_c_coaps_connect_handler),
+                    core::ptr::null_mut(),
+                )
+            })?;
+        }
+
+        // Cancel-safety: if this future is dropped before the handshake completes,
+        // close the session rather than leaving it behind for nobody to use.
+        // Defused on normal completion below.
+        let guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            unsafe { otCoapSecureDisconnect(state.ot.instance) };
+        });
+
+        let res = self.coaps_wait_event().await;
+
+        scopeguard::ScopeGuard::into_inner(guard);
+
+        res?;
+
+        let mut ot = self.activate();
+        ot.state().coap()?.secure.peer = Some(*peer);
+
+        Ok(())
+    }
+
+    /// Wait for a peer to open a DTLS session with us, once the CoAP secure agent is started.
+    pub async fn coaps_wait_connected(&self) -> Result<(), CoapSecureError> {
+        self.coaps_wait_event().await
+    }
+
+    /// Close the DTLS session, if any (`otCoapSecureDisconnect`).
+    pub fn coaps_disconnect(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+
+        state.coap()?.secure.peer = None;
+
+        unsafe { otCoapSecureDisconnect(instance) };
+
+        Ok(())
+    }
+
+    /// Return `true` if the DTLS session is established (`otCoapSecureIsConnected`).
+    pub fn coaps_is_connected(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otCoapSecureIsConnected(state.ot.instance) }
+    }
+
+    /// Send a CoAP request over the DTLS session opened with `coaps_connect`,
+    /// and wait for the response.
+    ///
+    /// Same as `OpenThread::coap_request`, except that the peer is the one of the session.
+    ///
+    /// Arguments:
+    /// - `msg_type`: The message type; `Confirmable` or `NonConfirmable`.
+    /// - `code`: The request method code.
+    /// - `uri_path`: The URI path of the resource; at most `COAP_MAX_URI_PATH_LEN` bytes.
+    /// - `payload`: The request payload; can be empty.
+    /// - `buf`: The buffer to copy the response payload into.
+    ///
+    /// Returns:
+    /// - The response; its payload is in `buf[..response.len]`.
+    pub async fn coaps_request(
+        &self,
+        msg_type: CoapType,
+        code: CoapCode,
+        uri_path: &str,
+        payload: &[u8],
+        buf: &mut [u8],
+    ) -> Result<CoapResponse, OtError> {
+        let peer = {
+            let mut ot = self.activate();
+            let state = ot.state();
+            let instance = state.ot.instance;
+            let peer = state.coap()?.secure.peer;
+
+            if !unsafe { otCoapSecureIsConnected(instance) } {
+                Err(OtError::new(otError_OT_ERROR_INVALID_STATE))?;
+            }
+
+            peer.ok_or(OtError::new(otError_OT_ERROR_INVALID_STATE))?
+        };
+
+        self.coap_request_with(
+            CoapAgent::Secure,
+            &peer,
+            msg_type,
+            code,
+            uri_path,
+            &CoapOptions::default(),
+            payload,
+            buf,
+        )
+        .await
+    }
+
+    /// Wait for the next connection event of the CoAP secure agent.
+    async fn coaps_wait_event(&self) -> Result<(), CoapSecureError> {
+        let event = poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .coap()?
+                .secure
+                .event
+                .poll_wait(cx)
+                .map(Ok::<_, OtError>)
+        })
+        .await?;
+
+        CoapSecureError::from_event(event)
+    }
+}
+
+impl<'a> CoapResource<'a> {
+    /// Register a new CoAP resource with the CoAP secure agent (`otCoapSecureAddResource`).
+    ///
+    /// The resource serves the requests arriving over the DTLS session of the agent.
+    ///
+    /// Arguments:
+    /// - `uri_path`: The URI path of the resource, e.g. `"sensors/temp"`;
+    ///   at most `COAP_MAX_URI_PATH_LEN` bytes.
+    pub fn new_secure(ot: OpenThread<'a>, uri_path: &str) -> Result<Self, OtError> {
+        Self::add(ot, uri_path, CoapAgent::Secure)
+    }
+}
+
+/// The state of the CoAP secure agent.
+pub(crate) struct CoapSecureCtx {
+    /// The latest connection event of the agent.
+    pub(crate) event: Signal<otCoapSecureConnectEvent>,
+    /// The peer of the session opened with `OpenThread::coaps_connect`, if any.
+    pub(crate) peer: Option<SocketAddrV6>,
+}
+
+impl CoapSecureCtx {
+    /// Create a new `CoapSecureCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            event: Signal::new(),
+            peer: None,
+        }
+    }
+}
+
+unsafe extern "C" fn plat_c_coaps_connect_handler(
+    event: otCoapSecureConnectEvent,
+    _context: *mut c_void,
+) {
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(coap) = ot.state().coap() else {
+        // The CoAP secure agent cannot be started without CoAP resources
+        warn!(
+            "CoAP secure connection event {} without CoAP resources, ignoring",
+            event
+        );
+        return;
+    };
+
+    if event != otCoapSecureConnectEvent_OT_COAP_SECURE_CONNECTED {
+        coap.secure.peer = None;
+    }
+
+    debug!("CoAP secure connection event: {}", event);
+
+    coap.secure.event.signal(event);
+}
@@ -0,0 +1,827 @@
+//! Commissioner API: admit other devices (joiners) onto this device's Thread
+//! network via the Thread commissioning protocol (MeshCoP). The counterpart of
+//! [`OpenThread::join`] (the `joiner` feature).
+//!
+//! Usage:
+//! - Petition to become the active Commissioner of the network with
+//!   [`OpenThread::commissioner_start`] (the device must be attached).
+//! - Admit joiners by EUI-64, by discerner or any joiner, with their pre-shared
+//!   joiner keys (PSKd): [`OpenThread::commissioner_add_joiner`].
+//! - Follow the progress of the joiners with
+//!   [`OpenThread::commissioner_wait_joiner_event`].
+//!
+//! Additionally, the Commissioner can issue the MeshCoP management commands:
+//! energy scans ([`OpenThread::commissioner_energy_scan`]), PAN ID conflict
+//! queries ([`OpenThread::commissioner_pan_id_query`]), announce begin
+//! ([`OpenThread::commissioner_announce_begin`]) and updates of the
+//! Commissioning Dataset ([`OpenThread::commissioner_set_dataset`]).
+//!
+//! NOTE: OpenThread does not report the responses to `MGMT_COMMISSIONER_GET` /
+//! `MGMT_COMMISSIONER_SET` to the application. The Commissioning Dataset in
+//! effect is instead read from the partition's Network Data
+//! ([`OpenThread::commissioner_dataset`]), which reflects an accepted update
+//! once the Leader has applied it.
+
+use core::ffi::c_void;
+use core::fmt::{self, Display};
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+use core::task::Poll;
+
+use crate::sys::{
+    otCommissionerAddJoiner, otCommissionerAddJoinerWithDiscerner, otCommissionerAnnounceBegin,
+    otCommissionerEnergyScan, otCommissionerGetId, otCommissionerGetNextJoinerInfo,
+    otCommissionerGetProvisioningUrl, otCommissionerGetSessionId, otCommissionerGetState,
+    otCommissionerJoinerEvent, otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_CONNECTED,
+    otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_END,
+    otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_FINALIZE,
+    otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_REMOVED,
+    otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_START, otCommissionerPanIdQuery,
+    otCommissionerRemoveJoiner, otCommissionerRemoveJoinerWithDiscerner, otCommissionerSendMgmtSet,
+    otCommissionerSetId, otCommissionerSetProvisioningUrl, otCommissionerStart,
+    otCommissionerState, otCommissionerState_OT_COMMISSIONER_STATE_ACTIVE,
+    otCommissionerState_OT_COMMISSIONER_STATE_DISABLED,
+    otCommissionerState_OT_COMMISSIONER_STATE_PETITION, otCommissionerStop, otCommissioningDataset,
+    otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE, otError_OT_ERROR_REJECTED, otExtAddress,
+    otInstance, otJoinerDiscerner, otJoinerInfo, otJoinerInfoType_OT_JOINER_INFO_TYPE_DISCERNER,
+    otJoinerInfoType_OT_JOINER_INFO_TYPE_EUI64, otNetDataGetCommissioningDataset, otSteeringData,
+    OT_JOINER_MAX_PSKD_LENGTH, OT_PROVISIONING_URL_MAX_SIZE, OT_STEERING_DATA_MAX_LENGTH,
+};
+use crate::{c_str, ot, store_str, to_ot_ip6_addr, OpenThread, OtContext, OtError};
+
+/// The maximum number of joiner events (see [`OpenThread::commissioner_wait_joiner_event`])
+/// which can be queued, waiting to be picked up by the application.
+///
+/// Events arriving while the queue is full are dropped.
+pub const COMMISSIONER_MAX_PENDING_JOINER_EVENTS: usize = 4;
+
+/// The maximum number of energy measurements in a [`CommissionerEnergyReport`].
+pub const COMMISSIONER_MAX_ENERGY_MEASUREMENTS: usize = 64;
+
+/// The maximum length of the Commissioner ID (see [`OpenThread::commissioner_set_id`]).
+const COMMISSIONER_ID_MAX_LENGTH: usize = 64;
+
+/// The state of the Commissioner role.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CommissionerState {
+    /// The Commissioner role is disabled.
+    Disabled,
+    /// Petitioning to become the active Commissioner.
+    Petition,
+    /// The device is the active Commissioner of the network.
+    Active,
+    /// Any other state.
+    Other(otCommissionerState),
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otCommissionerState> for CommissionerState {
+    fn from(value: otCommissionerState) -> Self {
+        match value {
+            otCommissionerState_OT_COMMISSIONER_STATE_DISABLED => Self::Disabled,
+            otCommissionerState_OT_COMMISSIONER_STATE_PETITION => Self::Petition,
+            otCommissionerState_OT_COMMISSIONER_STATE_ACTIVE => Self::Active,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Which joiner(s) a joiner entry of the Commissioner admits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum JoinerSelector {
+    /// Any joiner.
+    Any,
+    /// The joiner with the given IEEE EUI-64.
+    Eui64([u8; 8]),
+    /// The joiner(s) with the given discerner.
+    Discerner {
+        /// The discerner value (its lowest `length` bits).
+        value: u64,
+        /// The length of the discerner in bits (1 to 64).
+        length: u8,
+    },
+}
+
+impl JoinerSelector {
+    #[allow(non_upper_case_globals)]
+    fn from_ot(info: &otJoinerInfo) -> Self {
+        match info.mType {
+            otJoinerInfoType_OT_JOINER_INFO_TYPE_EUI64 => {
+                Self::Eui64(unsafe { info.mSharedId.mEui64.m8 })
+            }
+            otJoinerInfoType_OT_JOINER_INFO_TYPE_DISCERNER => {
+                let discerner = unsafe { info.mSharedId.mDiscerner };
+
+                Self::Discerner {
+                    value: discerner.mValue,
+                    length: discerner.mLength,
+                }
+            }
+            _ => Self::Any,
+        }
+    }
+}
+
+impl Display for JoinerSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "*"),
+            Self::Eui64(eui64) => {
+                for byte in eui64 {
+                    write!(f, "{byte:02x}")?;
+                }
+
+                Ok(())
+            }
+            Self::Discerner { value, length } => write!(f, "0x{value:x}/{length}"),
+        }
+    }
+}
+
+/// A joiner entry of the Commissioner, as returned by [`OpenThread::commissioner_joiners`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommissionerJoinerInfo {
+    /// The joiner(s) admitted by the entry.
+    pub joiner: JoinerSelector,
+    /// The pre-shared joiner key.
+    pub pskd: heapless::String<{ OT_JOINER_MAX_PSKD_LENGTH as usize }>,
+    /// The remaining time until the entry expires, in milliseconds.
+    pub expiration_ms: u32,
+}
+
+/// The kind of a [`CommissionerJoinerEvent`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CommissionerJoinerEventKind {
+    /// A joiner started the DTLS handshake.
+    Start,
+    /// A joiner completed the DTLS handshake.
+    Connected,
+    /// A joiner sent its `JOIN_FIN` request.
+    Finalize,
+    /// A joiner completed the commissioning.
+    End,
+    /// A joiner entry was removed (expired or explicitly removed).
+    Removed,
+    /// Any other event.
+    Other(otCommissionerJoinerEvent),
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otCommissionerJoinerEvent> for CommissionerJoinerEventKind {
+    fn from(value: otCommissionerJoinerEvent) -> Self {
+        match value {
+            otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_START => Self::Start,
+            otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_CONNECTED => Self::Connected,
+            otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_FINALIZE => Self::Finalize,
+            otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_END => Self::End,
+            otCommissionerJoinerEvent_OT_COMMISSIONER_JOINER_REMOVED => Self::Removed,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A joiner event, as returned by [`OpenThread::commissioner_wait_joiner_event`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommissionerJoinerEvent {
+    /// The kind of the event.
+    pub kind: CommissionerJoinerEventKind,
+    /// The joiner entry the event relates to, if any.
+    pub joiner: Option<JoinerSelector>,
+    /// The Joiner ID of the joiner, if known.
+    pub joiner_id: Option<[u8; 8]>,
+}
+
+/// An energy report received in response to [`OpenThread::commissioner_energy_scan`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommissionerEnergyReport {
+    /// The mask of the scanned channels.
+    pub channel_mask: u32,
+    /// The energy measurements (in dBm), `count` per scanned channel, in channel order.
+    pub energies: heapless::Vec<i8, COMMISSIONER_MAX_ENERGY_MEASUREMENTS>,
+}
+
+/// A PAN ID conflict reported in response to [`OpenThread::commissioner_pan_id_query`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommissionerPanIdConflict {
+    /// The queried PAN ID.
+    pub pan_id: u16,
+    /// The mask of the channels the PAN ID is in use on.
+    pub channel_mask: u32,
+}
+
+/// The Commissioning Dataset.
+///
+/// Fields which are `None` are not present in the dataset (or are not to be updated).
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommissioningDataset {
+    /// The Border Agent RLOC16.
+    pub locator: Option<u16>,
+    /// The Commissioner Session ID.
+    pub session_id: Option<u16>,
+    /// The Steering Data (a Bloom filter of the admitted joiners).
+    pub steering_data: Option<heapless::Vec<u8, { OT_STEERING_DATA_MAX_LENGTH as usize }>>,
+    /// The Joiner UDP port.
+    pub joiner_udp_port: Option<u16>,
+}
+
+impl From<&otCommissioningDataset> for CommissioningDataset {
+    fn from(dataset: &otCommissioningDataset) -> Self {
+        Self {
+            locator: dataset.mIsLocatorSet().then_some(dataset.mLocator),
+            session_id: dataset.mIsSessionIdSet().then_some(dataset.mSessionId),
+            steering_data: dataset.mIsSteeringDataSet().then(|| {
+                let len =
+                    (dataset.mSteeringData.mLength as usize).min(dataset.mSteeringData.m8.len());
+
+                unwrap!(heapless::Vec::from_slice(&dataset.mSteeringData.m8[..len]))
+            }),
+            joiner_udp_port: dataset
+                .mIsJoinerUdpPortSet()
+                .then_some(dataset.mJoinerUdpPort),
+        }
+    }
+}
+
+impl From<&CommissioningDataset> for otCommissioningDataset {
+    fn from(dataset: &CommissioningDataset) -> Self {
+        let mut ot_dataset = otCommissioningDataset::default();
+
+        if let Some(locator) = dataset.locator {
+            ot_dataset.mLocator = locator;
+            ot_dataset.set_mIsLocatorSet(true);
+        }
+
+        if let Some(session_id) = dataset.session_id {
+            ot_dataset.mSessionId = session_id;
+            ot_dataset.set_mIsSessionIdSet(true);
+        }
+
+        if let Some(steering_data) = dataset.steering_data.as_ref() {
+            let mut ot_steering_data = otSteeringData {
+                mLength: steering_data.len() as _,
+                ..Default::default()
+            };
+            ot_steering_data.m8[..steering_data.len()].copy_from_slice(steering_data);
+
+            ot_dataset.mSteeringData = ot_steering_data;
+            ot_dataset.set_mIsSteeringDataSet(true);
+        }
+
+        if let Some(joiner_udp_port) = dataset.joiner_udp_port {
+            ot_dataset.mJoinerUdpPort = joiner_udp_port;
+            ot_dataset.set_mIsJoinerUdpPortSet(true);
+        }
+
+        ot_dataset
+    }
+}
+
+impl OpenThread<'_> {
+    /// Petition to become the active Commissioner of the Thread network
+    /// (`otCommissionerStart`).
+    ///
+    /// Completes once the petition is accepted by the Leader, i.e. when the
+    /// Commissioner becomes active. Returns a `REJECTED` error if the petition
+    /// is rejected (e.g. because another Commissioner is already active).
+    ///
+    /// Prerequisites: the device must be attached to the network, otherwise an
+    /// `INVALID_STATE` error is reported.
+    ///
+    /// Dropping the returned future while petitioning stops the Commissioner
+    /// role (`otCommissionerStop`).
+    pub async fn commissioner_start(&self) -> Result<(), OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            state.ot.commissioner_joiner_events.clear();
+
+            ot!(unsafe {
+                otCommissionerStart(
+                    state.ot.instance,
+                    Some(OtContext::plat_c_commissioner_state_callback),
+                    Some(OtContext::plat_c_commissioner_joiner_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        // Cancel-safety: do not leave a petition running detached from any consumer.
+        // Defused on normal completion below.
+        let guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            unsafe { otCommissionerStop(state.ot.instance) };
+        });
+
+        let active = poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            match unsafe { otCommissionerGetState(state.ot.instance) }.into() {
+                CommissionerState::Active => return Poll::Ready(true),
+                CommissionerState::Disabled => return Poll::Ready(false),
+                _ => (),
+            }
+
+            state.ot.commissioner_state.reset();
+            let _ = state.ot.commissioner_state.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await;
+
+        scopeguard::ScopeGuard::into_inner(guard);
+
+        if active {
+            Ok(())
+        } else {
+            Err(OtError::new(otError_OT_ERROR_REJECTED))
+        }
+    }
+
+    /// Stop the Commissioner role (`otCommissionerStop`).
+    pub fn commissioner_stop(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otCommissionerStop(state.ot.instance) })
+    }
+
+    /// Return the state of the Commissioner role.
+    pub fn commissioner_state(&self) -> CommissionerState {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otCommissionerGetState(state.ot.instance) }.into()
+    }
+
+    /// Return the Commissioner Session ID.
+    pub fn commissioner_session_id(&self) -> u16 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otCommissionerGetSessionId(state.ot.instance) }
+    }
+
+    /// Get the Commissioner ID.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called with the Commissioner ID.
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` if the ID is not valid UTF-8.
+    pub fn commissioner_id<F, R>(&self, f: F) -> Result<R, OtError>
+    where
+        F: FnOnce(&str) -> R,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        Ok(f(c_str(unsafe { otCommissionerGetId(state.ot.instance) })?))
+    }
+
+    /// Set the Commissioner ID, advertised to the Leader when petitioning.
+    ///
+    /// Can only be changed while the Commissioner role is disabled.
+    pub fn commissioner_set_id(&self, id: &str) -> Result<(), OtError> {
+        let mut id_buf = [0; COMMISSIONER_ID_MAX_LENGTH + 1];
+
+        let id = store_str(id, &mut id_buf)?;
+
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otCommissionerSetId(state.ot.instance, id) })
+    }
+
+    /// Get the Provisioning URL advertised to the joiners.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called with the Provisioning URL.
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` if the URL is not valid UTF-8.
+    pub fn commissioner_provisioning_url<F, R>(&self, f: F) -> Result<R, OtError>
+    where
+        F: FnOnce(&str) -> R,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        Ok(f(c_str(unsafe {
+            otCommissionerGetProvisioningUrl(state.ot.instance)
+        })?))
+    }
+
+    /// Set the Provisioning URL advertised to the joiners.
+    ///
+    /// Arguments:
+    /// - `url`: The Provisioning URL (max 64 bytes), or `None` for no URL.
+    pub fn commissioner_set_provisioning_url(&self, url: Option<&str>) -> Result<(), OtError> {
+        let mut url_buf = [0; OT_PROVISIONING_URL_MAX_SIZE as usize + 1];
+
+        let url = url.map_or(Ok(core::ptr::null()), |url| store_str(url, &mut url_buf))?;
+
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otCommissionerSetProvisioningUrl(state.ot.instance, url) })
+    }
+
+    /// Admit one or more joiners.
+    ///
+    /// Arguments:
+    /// - `joiner`: The joiner(s) to admit.
+    /// - `pskd`: The pre-shared joiner key: 6 to 32 uppercase alphanumeric
+    ///   characters excluding `I`, `O`, `Q` and `Z` (validated by OpenThread).
+    /// - `timeout_secs`: The time after which the joiner entry is automatically removed.
+    pub fn commissioner_add_joiner(
+        &self,
+        joiner: &JoinerSelector,
+        pskd: &str,
+        timeout_secs: u32,
+    ) -> Result<(), OtError> {
+        let mut pskd_buf = [0; OT_JOINER_MAX_PSKD_LENGTH as usize + 1];
+
+        let pskd = store_str(pskd, &mut pskd_buf)?;
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe {
+            match joiner {
+                JoinerSelector::Any => {
+                    otCommissionerAddJoiner(instance, core::ptr::null(), pskd, timeout_secs)
+                }
+                JoinerSelector::Eui64(eui64) => otCommissionerAddJoiner(
+                    instance,
+                    &otExtAddress { m8: *eui64 },
+                    pskd,
+                    timeout_secs,
+                ),
+                JoinerSelector::Discerner { value, length } => {
+                    otCommissionerAddJoinerWithDiscerner(
+                        instance,
+                        &otJoinerDiscerner {
+                            mValue: *value,
+                            mLength: *length,
+                        },
+                        pskd,
+                        timeout_secs,
+                    )
+                }
+            }
+        })
+    }
+
+    /// Remove a joiner entry.
+    ///
+    /// Arguments:
+    /// - `joiner`: The joiner(s) of the entry to remove.
+    pub fn commissioner_remove_joiner(&self, joiner: &JoinerSelector) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe {
+            match joiner {
+                JoinerSelector::Any => otCommissionerRemoveJoiner(instance, core::ptr::null()),
+                JoinerSelector::Eui64(eui64) => {
+                    otCommissionerRemoveJoiner(instance, &otExtAddress { m8: *eui64 })
+                }
+                JoinerSelector::Discerner { value, length } => {
+                    otCommissionerRemoveJoinerWithDiscerner(
+                        instance,
+                        &otJoinerDiscerner {
+                            mValue: *value,
+                            mLength: *length,
+                        },
+                    )
+                }
+            }
+        })
+    }
+
+    /// Get the joiner entries of the Commissioner.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each joiner entry,
+    ///   and then with `None` once all entries were reported.
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` - without reporting the remaining entries - if the PSKd of
+    /// an entry is not valid UTF-8 or is too long.
+    pub fn commissioner_joiners<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&CommissionerJoinerInfo>),
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut iter = 0;
+        let mut info = otJoinerInfo::default();
+
+        while unsafe { otCommissionerGetNextJoinerInfo(instance, &mut iter, &mut info) }
+            == otError_OT_ERROR_NONE
+        {
+            let pskd = c_str(info.mPskd.m8.as_ptr())?;
+
+            f(Some(&CommissionerJoinerInfo {
+                joiner: JoinerSelector::from_ot(&info),
+                pskd: heapless::String::try_from(pskd)
+                    .map_err(|_| OtError::new(otError_OT_ERROR_INVALID_ARGS))?,
+                expiration_ms: info.mExpirationTime,
+            }));
+        }
+
+        f(None);
+
+        Ok(())
+    }
+
+    /// Wait for the next joiner event.
+    ///
+    /// Events are only queued while the Commissioner role is started;
+    /// see [`COMMISSIONER_MAX_PENDING_JOINER_EVENTS`].
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn commissioner_wait_joiner_event(&self) -> CommissionerJoinerEvent {
+        poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if let Some(event) = state.ot.commissioner_joiner_events.pop_front() {
+                return Poll::Ready(event);
+            }
+
+            state.ot.commissioner_joiner_event.reset();
+            let _ = state.ot.commissioner_joiner_event.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Send an `MGMT_ANNOUNCE_BEGIN` message, asking the destination(s) to send
+    /// MLE Announce messages on the given channels.
+    ///
+    /// Arguments:
+    /// - `channel_mask`: The mask of the channels to announce on.
+    /// - `count`: The number of announcements per channel.
+    /// - `period_ms`: The time between two successive announcements.
+    /// - `dest`: The destination address (unicast or multicast).
+    pub fn commissioner_announce_begin(
+        &self,
+        channel_mask: u32,
+        count: u8,
+        period_ms: u16,
+        dest: Ipv6Addr,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe {
+            otCommissionerAnnounceBegin(
+                instance,
+                channel_mask,
+                count,
+                period_ms,
+                &to_ot_ip6_addr(dest),
+            )
+        })
+    }
+
+    /// Send an `MGMT_ED_SCAN` (energy scan) query, and wait for the first energy report.
+    ///
+    /// With a multicast destination, only the first energy report is returned.
+    /// Only one energy scan can be in progress at a time.
+    ///
+    /// Arguments:
+    /// - `channel_mask`: The mask of the channels to scan.
+    /// - `count`: The number of energy measurements per channel.
+    /// - `period_ms`: The time between energy measurements.
+    /// - `scan_duration_ms`: The duration of each energy measurement.
+    /// - `dest`: The destination address (unicast or multicast).
+    ///
+    /// NOTE: The returned future completes only if an energy report is received;
+    /// use a timeout to bound the wait.
+    pub async fn commissioner_energy_scan(
+        &self,
+        channel_mask: u32,
+        count: u8,
+        period_ms: u16,
+        scan_duration_ms: u16,
+        dest: Ipv6Addr,
+    ) -> Result<CommissionerEnergyReport, OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            // Clear any stale report left over from a prior scan whose future was dropped
+            state.ot.commissioner_energy_report.reset();
+
+            ot!(unsafe {
+                otCommissionerEnergyScan(
+                    state.ot.instance,
+                    channel_mask,
+                    count,
+                    period_ms,
+                    scan_duration_ms,
+                    &to_ot_ip6_addr(dest),
+                    Some(OtContext::plat_c_commissioner_energy_report_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        Ok(poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .ot
+                .commissioner_energy_report
+                .poll_wait(cx)
+        })
+        .await)
+    }
+
+    /// Send an `MGMT_PANID_QUERY` (PAN ID conflict) query, and wait for the first conflict report.
+    ///
+    /// Only one PAN ID query can be in progress at a time.
+    ///
+    /// Arguments:
+    /// - `pan_id`: The PAN ID to query.
+    /// - `channel_mask`: The mask of the channels to query on.
+    /// - `dest`: The destination address (unicast or multicast).
+    ///
+    /// NOTE: The returned future completes only if a conflict is reported;
+    /// use a timeout to bound the wait (no report meaning no conflict).
+    pub async fn commissioner_pan_id_query(
+        &self,
+        pan_id: u16,
+        channel_mask: u32,
+        dest: Ipv6Addr,
+    ) -> Result<CommissionerPanIdConflict, OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            // Clear any stale report left over from a prior query whose future was dropped
+            state.ot.commissioner_pan_id_conflict.reset();
+
+            ot!(unsafe {
+                otCommissionerPanIdQuery(
+                    state.ot.instance,
+                    pan_id,
+                    channel_mask,
+                    &to_ot_ip6_addr(dest),
+                    Some(OtContext::plat_c_commissioner_pan_id_conflict_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        Ok(poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .ot
+                .commissioner_pan_id_conflict
+                .poll_wait(cx)
+        })
+        .await)
+    }
+
+    /// Return the Commissioning Dataset in effect, from the partition's Network Data.
+    pub fn commissioner_dataset(&self) -> CommissioningDataset {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut dataset = otCommissioningDataset::default();
+
+        unsafe { otNetDataGetCommissioningDataset(instance, &mut dataset) };
+
+        (&dataset).into()
+    }
+
+    /// Send an `MGMT_COMMISSIONER_SET` request to the Leader, updating the Commissioning Dataset.
+    ///
+    /// The update is visible in [`OpenThread::commissioner_dataset`] once accepted and applied by the Leader.
+    ///
+    /// Arguments:
+    /// - `dataset`: The fields of the dataset to update.
+    /// - `extra_tlvs`: Additional raw MeshCoP TLVs to include in the request (max 255 bytes).
+    pub fn commissioner_set_dataset(
+        &self,
+        dataset: &CommissioningDataset,
+        extra_tlvs: &[u8],
+    ) -> Result<(), OtError> {
+        if extra_tlvs.len() > u8::MAX as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let dataset: otCommissioningDataset = dataset.into();
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe {
+            otCommissionerSendMgmtSet(
+                instance,
+                &dataset,
+                extra_tlvs.as_ptr(),
+                extra_tlvs.len() as _,
+            )
+        })
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_commissioner_state_callback(
+        _state: otCommissionerState,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        ot.state().ot.commissioner_state.signal(());
+    }
+
+    unsafe extern "C" fn plat_c_commissioner_joiner_callback(
+        event: otCommissionerJoinerEvent,
+        joiner_info: *const otJoinerInfo,
+        joiner_id: *const otExtAddress,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+        let state = ot.state();
+
+        let event = CommissionerJoinerEvent {
+            kind: event.into(),
+            joiner: unsafe { joiner_info.as_ref() }.map(JoinerSelector::from_ot),
+            joiner_id: unsafe { joiner_id.as_ref() }.map(|id| id.m8),
+        };
+
+        if state
+            .ot
+            .commissioner_joiner_events
+            .push_back(event)
+            .is_err()
+        {
+            warn!("Too many pending joiner events, dropping event");
+        } else {
+            state.ot.commissioner_joiner_event.signal(());
+        }
+    }
+
+    unsafe extern "C" fn plat_c_commissioner_energy_report_callback(
+        channel_mask: u32,
+        energy_list: *const u8,
+        energy_list_len: u8,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        let energies = if energy_list.is_null() {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(energy_list, energy_list_len as _) }
+        };
+
+        let mut report = CommissionerEnergyReport {
+            channel_mask,
+            energies: heapless::Vec::new(),
+        };
+
+        for energy in energies {
+            if report.energies.push(*energy as i8).is_err() {
+                warn!("Energy report too long, truncating");
+                break;
+            }
+        }
+
+        ot.state().ot.commissioner_energy_report.signal(report);
+    }
+
+    unsafe extern "C" fn plat_c_commissioner_pan_id_conflict_callback(
+        pan_id: u16,
+        channel_mask: u32,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        ot.state()
+            .ot
+            .commissioner_pan_id_conflict
+            .signal(CommissionerPanIdConflict {
+                pan_id,
+                channel_mask,
+            });
+    }
+}
@@ -0,0 +1,111 @@
+//! DHCPv6 client and server API (the `dhcp6-client` and `dhcp6-server` features).
+//!
+//! OpenThread drives both roles from the Network Data, rather than by explicit start/stop calls:
+//! - The DHCPv6 server of a border router serves each on-mesh prefix of its local Network Data
+//!   which has the `dhcp` flag set. [`OpenThread::dhcp6_server_add_prefix`] and
+//!   [`OpenThread::dhcp6_server_remove_prefix`] manage such prefixes.
+//! - The DHCPv6 client of a device solicits an address for each on-mesh prefix of the partition's
+//!   Network Data which has the `dhcp` flag set. The acquired addresses are assigned to the Thread
+//!   interface and reported by [`OpenThread::ipv6_addrs_info`] with the
+//!   [`Ipv6AddrOrigin::Dhcp6`](crate::Ipv6AddrOrigin::Dhcp6) origin
+//!   ([`OpenThread::dhcp6_client_addrs`] singles them out). As with any other address, acquiring
+//!   and releasing an address is reported as an IPv6 address change
+//!   ([`ChangedFlags::IP6_ADDRESS_ADDED`](crate::ChangedFlags::IP6_ADDRESS_ADDED) /
+//!   [`ChangedFlags::IP6_ADDRESS_REMOVED`](crate::ChangedFlags::IP6_ADDRESS_REMOVED)) by
+//!   [`OpenThread::wait_changed_flags`] and the [`ChangeSubscriber`](crate::ChangeSubscriber)s.
+
+use core::net::Ipv6Addr;
+
+#[cfg(feature = "dhcp6-client")]
+use crate::Ipv6AddrOrigin;
+use crate::{OpenThread, OtError};
+#[cfg(feature = "dhcp6-server")]
+use crate::{OtBorderRouterConfig, OtRoutePreference};
+
+impl OpenThread<'_> {
+    /// Add an on-mesh prefix served by the DHCPv6 server of this device to the local network data,
+    /// and register the local network data with the leader.
+    ///
+    /// The DHCPv6 server starts serving the prefix once the prefix is in the local network data.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix (of length 64) and its length
+    /// - `preference`: The route preference of the prefix
+    /// - `default_route`: Whether this border router is a default router for the prefix
+    /// - `stable`: Whether the prefix is considered Stable Network Data
+    #[cfg(feature = "dhcp6-server")]
+    pub fn dhcp6_server_add_prefix(
+        &self,
+        prefix: (Ipv6Addr, u8),
+        preference: OtRoutePreference,
+        default_route: bool,
+        stable: bool,
+    ) -> Result<(), OtError> {
+        self.border_router_add_on_mesh_prefix(&OtBorderRouterConfig {
+            prefix,
+            preference,
+            prefered: true,
+            slaac: false,
+            dhcp: true,
+            configure: true,
+            default_route,
+            on_mesh: true,
+            stable,
+            nd_dns: false,
+            domain_prefix: false,
+            rloc16: 0,
+        })?;
+
+        self.border_router_register()
+    }
+
+    /// Remove an on-mesh prefix served by the DHCPv6 server of this device from the local network data,
+    /// and register the local network data with the leader.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix and its length
+    #[cfg(feature = "dhcp6-server")]
+    pub fn dhcp6_server_remove_prefix(&self, prefix: (Ipv6Addr, u8)) -> Result<(), OtError> {
+        self.border_router_remove_on_mesh_prefix(prefix)?;
+
+        self.border_router_register()
+    }
+
+    /// Gets the list of the on-mesh prefixes served by the DHCPv6 server of this device,
+    /// i.e. the on-mesh prefixes of the local network data which have the `dhcp` flag set
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each prefix. Once called for all prefixes,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "dhcp6-server")]
+    pub fn dhcp6_server_prefixes<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<(Ipv6Addr, u8)>) -> Result<(), OtError>,
+    {
+        self.border_router_get_on_mesh_prefixes(|config| match config {
+            Some(config) if config.dhcp => f(Some(config.prefix)),
+            Some(_) => Ok(()),
+            None => f(None),
+        })
+    }
+
+    /// Gets the list of the IPv6 addresses acquired by the DHCPv6 client
+    /// and currently assigned to the Thread interface
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each address. Once called for all addresses,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "dhcp6-client")]
+    pub fn dhcp6_client_addrs<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<(Ipv6Addr, u8)>) -> Result<(), OtError>,
+    {
+        self.ipv6_addrs_info(|info| match info {
+            Some(info) if info.origin == Ipv6AddrOrigin::Dhcp6 => {
+                f(Some((info.addr, info.prefix_len)))
+            }
+            Some(_) => Ok(()),
+            None => f(None),
+        })
+    }
+}
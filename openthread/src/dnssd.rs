@@ -0,0 +1,439 @@
+//! DNS-SD server API (`OPENTHREAD_CONFIG_DNSSD_SERVER_ENABLE` / the `dnssd-server` feature).
+//!
+//! The DNS-SD server answers the unicast DNS queries of the Thread devices from the hosts and
+//! services registered with the local SRP server (see the `srp-server` feature). OpenThread runs
+//! the server whenever the Thread interface is up (see [`OpenThread::enable_ipv6`]); it can
+//! additionally be stopped and started again with [`OpenThread::dnssd_server_stop`] and
+//! [`OpenThread::dnssd_server_start`].
+//!
+//! Queries for names which are not in the SRP registry can additionally be answered by an
+//! application-provided "discovery proxy" (e.g. one resolving the names via mDNS on the
+//! infrastructure link):
+//! - Enable the proxy with [`OpenThread::dnssd_set_discovery_proxy`]
+//! - Wait for the names OpenThread subscribes to (and unsubscribes from) with
+//!   [`OpenThread::dnssd_wait_proxy_event`]
+//! - Resolve the names asynchronously, and report the results back with
+//!   [`OpenThread::dnssd_service_instance_discovered`] / [`OpenThread::dnssd_host_discovered`]
+//!
+//! OpenThread answers the pending DNS query as soon as the result is reported, or with an
+//! error once the query times out.
+
+use core::ffi::{c_char, c_void};
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+use core::task::Poll;
+
+use crate::sys::{
+    otDnssdGetCounters, otDnssdGetNextQuery, otDnssdGetQueryTypeAndName, otDnssdHostInfo,
+    otDnssdQuery, otDnssdQueryHandleDiscoveredHost, otDnssdQueryHandleDiscoveredServiceInstance,
+    otDnssdQuerySetCallbacks, otDnssdQueryType, otDnssdQueryType_OT_DNSSD_QUERY_TYPE_BROWSE,
+    otDnssdQueryType_OT_DNSSD_QUERY_TYPE_RESOLVE,
+    otDnssdQueryType_OT_DNSSD_QUERY_TYPE_RESOLVE_HOST, otDnssdServiceInstanceInfo,
+    otError_OT_ERROR_INVALID_ARGS, otInstance, otMessage, otMessageInfo, otUdpAddReceiver,
+    otUdpRemoveReceiver, OT_DNS_MAX_NAME_SIZE,
+};
+use crate::{c_str, ot, store_str, OpenThread, OtContext, OtError};
+
+/// The UDP port of the DNS-SD server (`OPENTHREAD_CONFIG_DNSSD_SERVER_PORT`).
+const DNSSD_SERVER_PORT: u16 = 53;
+
+/// The maximum number of discovery proxy events (see [`OpenThread::dnssd_wait_proxy_event`])
+/// which can be queued, waiting to be picked up by the application.
+///
+/// Events arriving while the queue is full are dropped; the corresponding DNS queries will time out.
+pub const DNSSD_MAX_PENDING_PROXY_EVENTS: usize = 4;
+
+/// The type of a DNS-SD query.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DnssdQueryType {
+    /// Browse for the instances of a service (e.g. `_matter._tcp.default.service.arpa.`).
+    Browse,
+    /// Resolve a service instance (e.g. `my-light._matter._tcp.default.service.arpa.`).
+    Resolve,
+    /// Resolve the addresses of a host (e.g. `my-host.default.service.arpa.`).
+    ResolveHost,
+    /// Any other query type.
+    Other(otDnssdQueryType),
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otDnssdQueryType> for DnssdQueryType {
+    fn from(value: otDnssdQueryType) -> Self {
+        match value {
+            otDnssdQueryType_OT_DNSSD_QUERY_TYPE_BROWSE => Self::Browse,
+            otDnssdQueryType_OT_DNSSD_QUERY_TYPE_RESOLVE => Self::Resolve,
+            otDnssdQueryType_OT_DNSSD_QUERY_TYPE_RESOLVE_HOST => Self::ResolveHost,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// An event reported to the discovery proxy by [`OpenThread::dnssd_wait_proxy_event`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DnssdProxyEvent<'a> {
+    /// OpenThread needs the given full service name, service instance name or host name
+    /// resolved. The type of the name can be figured out with [`OpenThread::dnssd_queries`].
+    ///
+    /// There might be multiple subscriptions for the same name.
+    Subscribe(&'a str),
+    /// OpenThread no longer needs the given name resolved (the query was answered or timed out).
+    Unsubscribe(&'a str),
+}
+
+/// A service instance discovered by the discovery proxy,
+/// as reported with [`OpenThread::dnssd_service_instance_discovered`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DnssdServiceInstance<'a> {
+    /// The full service instance name (e.g. `my-light._matter._tcp.default.service.arpa.`).
+    pub instance_name: &'a str,
+    /// The full host name (e.g. `my-host.default.service.arpa.`).
+    pub host_name: &'a str,
+    /// The IPv6 addresses of the host.
+    /// Must not contain unspecified, link-local, loopback or multicast addresses.
+    pub host_addrs: &'a [Ipv6Addr],
+    /// The service port.
+    pub port: u16,
+    /// The service priority.
+    pub priority: u16,
+    /// The service weight.
+    pub weight: u16,
+    /// The raw TXT data of the service, as encoded on the wire.
+    pub txt_data: &'a [u8],
+    /// The TTL of the service records in seconds.
+    pub ttl: u32,
+}
+
+/// DNS-SD server counters (`otDnssdGetCounters`), as reported by
+/// [`OpenThread::dnssd_counters`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DnssdCounters {
+    /// Number of successful responses.
+    pub success_responses: u32,
+    /// Number of server failure responses.
+    pub server_failure_responses: u32,
+    /// Number of format error responses.
+    pub format_error_responses: u32,
+    /// Number of name error responses.
+    pub name_error_responses: u32,
+    /// Number of "not implemented" responses.
+    pub not_implemented_responses: u32,
+    /// Number of other responses.
+    pub other_responses: u32,
+    /// Number of queries resolved by the local SRP server.
+    pub resolved_by_srp: u32,
+}
+
+impl From<&crate::sys::otDnssdCounters> for DnssdCounters {
+    fn from(c: &crate::sys::otDnssdCounters) -> Self {
+        Self {
+            success_responses: c.mSuccessResponse,
+            server_failure_responses: c.mServerFailureResponse,
+            format_error_responses: c.mFormatErrorResponse,
+            name_error_responses: c.mNameErrorResponse,
+            not_implemented_responses: c.mNotImplementedResponse,
+            other_responses: c.mOtherResponse,
+            resolved_by_srp: c.mResolvedBySrp,
+        }
+    }
+}
+
+/// A discovery proxy event queued by the subscribe/unsubscribe callbacks,
+/// until the application picks it up.
+pub(crate) struct DnssdPendingProxyEvent {
+    subscribe: bool,
+    name: heapless::String<{ OT_DNS_MAX_NAME_SIZE as usize }>,
+}
+
+impl OpenThread<'_> {
+    /// Return the DNS-SD server counters (`otDnssdGetCounters`).
+    pub fn dnssd_counters(&self) -> DnssdCounters {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let counters = unsafe { &*otDnssdGetCounters(state.ot.instance) };
+        counters.into()
+    }
+
+    /// Iterate over the DNS-SD queries currently waiting to be answered.
+    ///
+    /// Queries for names which are not valid UTF-8 are skipped.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that receives the type and the name of the next query.
+    ///   If there are no more queries, the closure will receive `None`.
+    pub fn dnssd_queries<F>(&self, mut f: F)
+    where
+        F: FnMut(Option<(DnssdQueryType, &str)>),
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut name = [0 as c_char; OT_DNS_MAX_NAME_SIZE as usize];
+        let mut query: *const otDnssdQuery =
+            unsafe { otDnssdGetNextQuery(instance, core::ptr::null()) };
+
+        while !query.is_null() {
+            let query_type = unsafe { otDnssdGetQueryTypeAndName(query, &mut name) };
+
+            // Names queried by (remote) DNS clients are not guaranteed to be valid UTF-8
+            match c_str(name.as_ptr()) {
+                Ok(name) => f(Some((query_type.into(), name))),
+                Err(_) => warn!("Invalid UTF-8 in DNS-SD name, ignoring query"),
+            }
+
+            query = unsafe { otDnssdGetNextQuery(instance, query) };
+        }
+
+        f(None);
+    }
+
+    /// Start the DNS-SD server, after it was stopped with [`OpenThread::dnssd_server_stop`].
+    ///
+    /// The DNS-SD server is started by default; it answers queries only while the Thread interface is up.
+    pub fn dnssd_server_start(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        if state.ot.dnssd_stopped {
+            ot!(unsafe { otUdpRemoveReceiver(state.ot.instance, &mut state.ot.dnssd_receiver) })?;
+
+            state.ot.dnssd_stopped = false;
+        }
+
+        Ok(())
+    }
+
+    /// Stop the DNS-SD server: the DNS queries sent to the device are dropped (and time out
+    /// on the querying clients) until the server is started again with [`OpenThread::dnssd_server_start`].
+    pub fn dnssd_server_stop(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        if !state.ot.dnssd_stopped {
+            state.ot.dnssd_receiver.mHandler = Some(OtContext::plat_c_dnssd_udp_handler);
+            state.ot.dnssd_receiver.mContext = core::ptr::null_mut();
+
+            ot!(unsafe { otUdpAddReceiver(state.ot.instance, &mut state.ot.dnssd_receiver) })?;
+
+            state.ot.dnssd_stopped = true;
+        }
+
+        Ok(())
+    }
+
+    /// Return `true` if the DNS-SD server was not stopped with [`OpenThread::dnssd_server_stop`].
+    pub fn dnssd_server_is_started(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        !state.ot.dnssd_stopped
+    }
+
+    /// Enable or disable the application-provided discovery proxy.
+    ///
+    /// When enabled, the DNS-SD server asks the application to resolve the names it cannot
+    /// answer from the SRP registry (see [`OpenThread::dnssd_wait_proxy_event`]).
+    /// Disabling drops the proxy events which are still waiting to be picked up.
+    pub fn dnssd_set_discovery_proxy(&self, enabled: bool) {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+
+        if enabled {
+            unsafe {
+                otDnssdQuerySetCallbacks(
+                    instance,
+                    Some(OtContext::plat_c_dnssd_subscribe_callback),
+                    Some(OtContext::plat_c_dnssd_unsubscribe_callback),
+                    instance as _,
+                );
+            }
+        } else {
+            unsafe {
+                otDnssdQuerySetCallbacks(instance, None, None, core::ptr::null_mut());
+            }
+
+            state.ot.dnssd_proxy_events.clear();
+        }
+    }
+
+    /// Wait for the next discovery proxy event.
+    ///
+    /// Only reports events if the discovery proxy was enabled with
+    /// [`OpenThread::dnssd_set_discovery_proxy`], otherwise waits forever.
+    ///
+    /// Names which are not valid UTF-8 are not reported.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that receives the event. The closure should start (or stop) resolving
+    ///   the name asynchronously, outside of the closure, and report the results with
+    ///   [`OpenThread::dnssd_service_instance_discovered`] / [`OpenThread::dnssd_host_discovered`].
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn dnssd_wait_proxy_event<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(DnssdProxyEvent<'_>) -> R,
+    {
+        let event = poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if let Some(event) = state.ot.dnssd_proxy_events.pop_front() {
+                return Poll::Ready(event);
+            }
+
+            state.ot.dnssd_proxy_event.reset();
+            let _ = state.ot.dnssd_proxy_event.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await;
+
+        if event.subscribe {
+            f(DnssdProxyEvent::Subscribe(&event.name))
+        } else {
+            f(DnssdProxyEvent::Unsubscribe(&event.name))
+        }
+    }
+
+    /// Report a service instance discovered by the discovery proxy.
+    ///
+    /// Arguments:
+    /// - `service_name`: The full service name the instance belongs to
+    ///   (e.g. `_matter._tcp.default.service.arpa.`), for answering browse queries.
+    /// - `instance`: The discovered service instance.
+    pub fn dnssd_service_instance_discovered(
+        &self,
+        service_name: &str,
+        instance: &DnssdServiceInstance,
+    ) -> Result<(), OtError> {
+        let mut service_name_buf = [0; OT_DNS_MAX_NAME_SIZE as usize];
+        let mut instance_name_buf = [0; OT_DNS_MAX_NAME_SIZE as usize];
+        let mut host_name_buf = [0; OT_DNS_MAX_NAME_SIZE as usize];
+
+        let service_name = store_str(service_name, &mut service_name_buf)?;
+        let instance_name = store_str(instance.instance_name, &mut instance_name_buf)?;
+        let host_name = store_str(instance.host_name, &mut host_name_buf)?;
+
+        if instance.host_addrs.len() > u8::MAX as usize
+            || instance.txt_data.len() > u16::MAX as usize
+        {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let mut info = otDnssdServiceInstanceInfo {
+            mFullName: instance_name,
+            mHostName: host_name,
+            mAddressNum: instance.host_addrs.len() as _,
+            mAddresses: instance.host_addrs.as_ptr() as _,
+            mPort: instance.port,
+            mPriority: instance.priority,
+            mWeight: instance.weight,
+            mTxtLength: instance.txt_data.len() as _,
+            mTxtData: instance.txt_data.as_ptr(),
+            mTtl: instance.ttl,
+        };
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            otDnssdQueryHandleDiscoveredServiceInstance(instance, service_name, &mut info);
+        }
+
+        Ok(())
+    }
+
+    /// Report a host discovered by the discovery proxy.
+    ///
+    /// Arguments:
+    /// - `host_name`: The full host name (e.g. `my-host.default.service.arpa.`).
+    /// - `addrs`: The IPv6 addresses of the host.
+    ///   Must not contain unspecified, link-local, loopback or multicast addresses.
+    /// - `ttl`: The TTL of the address records in seconds.
+    pub fn dnssd_host_discovered(
+        &self,
+        host_name: &str,
+        addrs: &[Ipv6Addr],
+        ttl: u32,
+    ) -> Result<(), OtError> {
+        let mut host_name_buf = [0; OT_DNS_MAX_NAME_SIZE as usize];
+
+        let host_name = store_str(host_name, &mut host_name_buf)?;
+
+        if addrs.len() > u8::MAX as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let mut info = otDnssdHostInfo {
+            mAddressNum: addrs.len() as _,
+            mAddresses: addrs.as_ptr() as _,
+            mTtl: ttl,
+        };
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            otDnssdQueryHandleDiscoveredHost(instance, host_name, &mut info);
+        }
+
+        Ok(())
+    }
+}
+
+impl OtContext<'_> {
+    /// Drop the DNS queries while the DNS-SD server is stopped, by consuming the messages
+    /// sent to its port before they reach its socket.
+    unsafe extern "C" fn plat_c_dnssd_udp_handler(
+        _context: *mut c_void,
+        _message: *const otMessage,
+        message_info: *const otMessageInfo,
+    ) -> bool {
+        unsafe { (*message_info).mSockPort == DNSSD_SERVER_PORT }
+    }
+
+    unsafe extern "C" fn plat_c_dnssd_subscribe_callback(
+        context: *mut c_void,
+        full_name: *const c_char,
+    ) {
+        Self::callback(context as *mut otInstance).plat_dnssd_proxy_event(true, full_name);
+    }
+
+    unsafe extern "C" fn plat_c_dnssd_unsubscribe_callback(
+        context: *mut c_void,
+        full_name: *const c_char,
+    ) {
+        Self::callback(context as *mut otInstance).plat_dnssd_proxy_event(false, full_name);
+    }
+
+    fn plat_dnssd_proxy_event(&mut self, subscribe: bool, full_name: *const c_char) {
+        let state = self.state();
+
+        let Ok(full_name) = c_str(full_name) else {
+            warn!("Invalid UTF-8 in DNS-SD name, ignoring query");
+            return;
+        };
+
+        let mut name = heapless::String::new();
+        unwrap!(name.push_str(full_name));
+
+        if state
+            .ot
+            .dnssd_proxy_events
+            .push_back(DnssdPendingProxyEvent { subscribe, name })
+            .is_err()
+        {
+            warn!("Too many pending DNS-SD proxy events, dropping event");
+        } else {
+            state.ot.dnssd_proxy_event.signal(());
+        }
+    }
+}
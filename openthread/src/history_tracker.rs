@@ -0,0 +1,547 @@
+//! History Tracker API (the `history-tracker` feature).
+//!
+//! The History Tracker records the most recent network events of the device in fixed-size
+//! ring buffers: role changes, unicast and multicast address changes, received and transmitted
+//! IPv6 messages, neighbor and router table changes, and Network Data changes.
+//!
+//! Each list is iterated from the most recent entry to the oldest one. Every entry is reported
+//! together with its age, relative to the moment the iteration started.
+
+use core::net::{Ipv6Addr, SocketAddrV6};
+
+use crate::sys::{
+    otHistoryTrackerAddressEvent,
+    otHistoryTrackerAddressEvent_OT_HISTORY_TRACKER_ADDRESS_EVENT_ADDED,
+    otHistoryTrackerExternalRouteInfo, otHistoryTrackerInitIterator,
+    otHistoryTrackerIterateExternalRouteHistory, otHistoryTrackerIterateMulticastAddressHistory,
+    otHistoryTrackerIterateNeighborHistory, otHistoryTrackerIterateNetInfoHistory,
+    otHistoryTrackerIterateOnMeshPrefixHistory, otHistoryTrackerIterateRouterHistory,
+    otHistoryTrackerIterateRxHistory, otHistoryTrackerIterateTxHistory,
+    otHistoryTrackerIterateUnicastAddressHistory, otHistoryTrackerIterator,
+    otHistoryTrackerMessageInfo, otHistoryTrackerMulticastAddressInfo,
+    otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_ADDED,
+    otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_CHANGED,
+    otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_REMOVED,
+    otHistoryTrackerNeighborInfo, otHistoryTrackerNetDataEvent,
+    otHistoryTrackerNetDataEvent_OT_HISTORY_TRACKER_NET_DATA_ENTRY_ADDED,
+    otHistoryTrackerNetworkInfo, otHistoryTrackerOnMeshPrefixInfo,
+    otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_ADDED,
+    otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_COST_CHANGED,
+    otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_NEXT_HOP_CHANGED,
+    otHistoryTrackerRouterInfo, otHistoryTrackerUnicastAddressInfo, otInstance,
+    OT_HISTORY_TRACKER_INFINITE_PATH_COST, OT_HISTORY_TRACKER_MAX_AGE,
+    OT_HISTORY_TRACKER_MSG_PRIORITY_HIGH, OT_HISTORY_TRACKER_MSG_PRIORITY_LOW,
+    OT_HISTORY_TRACKER_MSG_PRIORITY_NORMAL, OT_HISTORY_TRACKER_NO_NEXT_HOP, OT_RADIO_RSSI_INVALID,
+};
+use crate::{
+    to_sock_addr, DeviceRole, Ipv6AddrOrigin, OpenThread, OtBorderRouterConfig, OtError,
+    OtExternalRouteConfig,
+};
+
+/// An entry of a History Tracker list.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryEntry<T> {
+    /// The age of the entry in milliseconds, relative to the moment the iteration started,
+    /// or `None` if the entry is older than the maximum age tracked by OpenThread (~49 days).
+    pub age_millis: Option<u32>,
+    /// The recorded event
+    pub info: T,
+}
+
+/// A Thread network info entry (role, mode, RLOC16 and partition changes),
+/// as reported by [`OpenThread::history_net_info`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryNetInfo {
+    /// The device role.
+    pub role: DeviceRole,
+    /// Whether the device kept its receiver on when idle.
+    pub rx_on_when_idle: bool,
+    /// Whether the device was a Full Thread Device.
+    pub full_thread_device: bool,
+    /// Whether the device requested the full Network Data.
+    pub full_network_data: bool,
+    /// The RLOC16 of the device.
+    pub rloc16: u16,
+    /// The partition ID (valid when attached).
+    pub partition_id: u32,
+}
+
+impl From<&otHistoryTrackerNetworkInfo> for HistoryNetInfo {
+    fn from(info: &otHistoryTrackerNetworkInfo) -> Self {
+        Self {
+            role: info.mRole.into(),
+            rx_on_when_idle: info.mMode.mRxOnWhenIdle(),
+            full_thread_device: info.mMode.mDeviceType(),
+            full_network_data: info.mMode.mNetworkData(),
+            rloc16: info.mRloc16,
+            partition_id: info.mPartitionId,
+        }
+    }
+}
+
+/// Whether an IPv6 address or a Network Data entry was added or removed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HistoryEntryEvent {
+    /// The address/entry was added
+    Added,
+    /// The address/entry was removed
+    Removed,
+}
+
+impl HistoryEntryEvent {
+    fn from_address_event(event: otHistoryTrackerAddressEvent) -> Self {
+        if event == otHistoryTrackerAddressEvent_OT_HISTORY_TRACKER_ADDRESS_EVENT_ADDED {
+            Self::Added
+        } else {
+            Self::Removed
+        }
+    }
+
+    fn from_net_data_event(event: otHistoryTrackerNetDataEvent) -> Self {
+        if event == otHistoryTrackerNetDataEvent_OT_HISTORY_TRACKER_NET_DATA_ENTRY_ADDED {
+            Self::Added
+        } else {
+            Self::Removed
+        }
+    }
+}
+
+/// A unicast IPv6 address entry, as reported by [`OpenThread::history_unicast_addrs`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryUnicastAddrInfo {
+    /// Whether the address was added or removed.
+    pub event: HistoryEntryEvent,
+    /// The unicast IPv6 address.
+    pub addr: Ipv6Addr,
+    /// The prefix length (in bits).
+    pub prefix_len: u8,
+    /// The origin of the address.
+    pub origin: Ipv6AddrOrigin,
+    /// The IPv6 scope of the address.
+    pub scope: u8,
+    /// Whether the address was preferred.
+    pub preferred: bool,
+    /// Whether the address was valid.
+    pub valid: bool,
+    /// Whether the address was an RLOC.
+    pub rloc: bool,
+}
+
+impl From<&otHistoryTrackerUnicastAddressInfo> for HistoryUnicastAddrInfo {
+    fn from(info: &otHistoryTrackerUnicastAddressInfo) -> Self {
+        Self {
+            event: HistoryEntryEvent::from_address_event(info.mEvent),
+            addr: Ipv6Addr::from(unsafe { info.mAddress.mFields.m8 }),
+            prefix_len: info.mPrefixLength,
+            origin: info.mAddressOrigin.into(),
+            scope: info.mScope(),
+            preferred: info.mPreferred(),
+            valid: info.mValid(),
+            rloc: info.mRloc(),
+        }
+    }
+}
+
+/// A multicast IPv6 address entry, as reported by [`OpenThread::history_multicast_addrs`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryMulticastAddrInfo {
+    /// Whether the address was subscribed (added) or unsubscribed (removed).
+    pub event: HistoryEntryEvent,
+    /// The multicast IPv6 address.
+    pub addr: Ipv6Addr,
+    /// The origin of the address.
+    pub origin: Ipv6AddrOrigin,
+}
+
+impl From<&otHistoryTrackerMulticastAddressInfo> for HistoryMulticastAddrInfo {
+    fn from(info: &otHistoryTrackerMulticastAddressInfo) -> Self {
+        Self {
+            event: HistoryEntryEvent::from_address_event(info.mEvent),
+            addr: Ipv6Addr::from(unsafe { info.mAddress.mFields.m8 }),
+            origin: info.mAddressOrigin.into(),
+        }
+    }
+}
+
+/// The priority of a received or transmitted IPv6 message.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HistoryMessagePriority {
+    /// Low priority
+    Low,
+    /// Normal priority
+    Normal,
+    /// High priority
+    High,
+    /// Network control priority
+    Net,
+}
+
+impl From<u8> for HistoryMessagePriority {
+    fn from(value: u8) -> Self {
+        match value as u32 {
+            OT_HISTORY_TRACKER_MSG_PRIORITY_LOW => Self::Low,
+            OT_HISTORY_TRACKER_MSG_PRIORITY_NORMAL => Self::Normal,
+            OT_HISTORY_TRACKER_MSG_PRIORITY_HIGH => Self::High,
+            _ => Self::Net,
+        }
+    }
+}
+
+/// A summary of a received or transmitted IPv6 message,
+/// as reported by [`OpenThread::history_rx`] and [`OpenThread::history_tx`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryMessageInfo {
+    /// The IPv6 payload length (excluding the IPv6 header).
+    pub payload_len: u16,
+    /// The RLOC16 of the neighbor which sent/received the message, if any.
+    pub neighbor_rloc16: Option<u16>,
+    /// The source address and port (the port is only set for UDP/TCP).
+    pub source: SocketAddrV6,
+    /// The destination address and port (the port is only set for UDP/TCP).
+    pub destination: SocketAddrV6,
+    /// The message checksum (only valid for UDP/TCP/ICMPv6).
+    pub checksum: u16,
+    /// The IP protocol number.
+    pub ip_proto: u8,
+    /// The ICMPv6 type if the message is ICMPv6, zero otherwise.
+    pub icmp6_type: u8,
+    /// The average RSS (dBm) of the frames of a received message, if known.
+    pub avg_rx_rss: Option<i8>,
+    /// Whether link security was enabled.
+    pub link_security: bool,
+    /// Whether the transmission succeeded (transmitted messages only).
+    pub tx_success: bool,
+    /// The message priority.
+    pub priority: HistoryMessagePriority,
+}
+
+impl From<&otHistoryTrackerMessageInfo> for HistoryMessageInfo {
+    fn from(info: &otHistoryTrackerMessageInfo) -> Self {
+        Self {
+            payload_len: info.mPayloadLength,
+            neighbor_rloc16: (info.mNeighborRloc16 != 0xfffe).then_some(info.mNeighborRloc16),
+            source: to_sock_addr(&info.mSource.mAddress, info.mSource.mPort, 0),
+            destination: to_sock_addr(&info.mDestination.mAddress, info.mDestination.mPort, 0),
+            checksum: info.mChecksum,
+            ip_proto: info.mIpProto,
+            icmp6_type: info.mIcmp6Type,
+            avg_rx_rss: (info.mAveRxRss != OT_RADIO_RSSI_INVALID as i8).then_some(info.mAveRxRss),
+            link_security: info.mLinkSecurity(),
+            tx_success: info.mTxSuccess(),
+            priority: info.mPriority().into(),
+        }
+    }
+}
+
+/// What happened to a neighbor, as reported in a [`HistoryNeighborInfo`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HistoryNeighborEvent {
+    /// The neighbor was added
+    Added,
+    /// The neighbor was removed
+    Removed,
+    /// The neighbor changed (e.g. its link mode)
+    Changed,
+    /// The connection to the child is being restored after a (re)start of the device
+    Restoring,
+}
+
+impl From<u8> for HistoryNeighborEvent {
+    #[allow(non_upper_case_globals)]
+    fn from(value: u8) -> Self {
+        match value as u32 {
+            otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_ADDED => Self::Added,
+            otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_REMOVED => {
+                Self::Removed
+            }
+            otHistoryTrackerNeighborEvent_OT_HISTORY_TRACKER_NEIGHBOR_EVENT_CHANGED => {
+                Self::Changed
+            }
+            _ => Self::Restoring,
+        }
+    }
+}
+
+/// A neighbor table entry, as reported by [`OpenThread::history_neighbors`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryNeighborInfo {
+    /// What happened to the neighbor.
+    pub event: HistoryNeighborEvent,
+    /// The extended address of the neighbor.
+    pub ext_address: u64,
+    /// The RLOC16 of the neighbor.
+    pub rloc16: u16,
+    /// The average RSSI (dBm) of the frames from the neighbor at the time of the event, if known.
+    pub average_rssi: Option<i8>,
+    /// Whether the neighbor keeps its receiver on when idle.
+    pub rx_on_when_idle: bool,
+    /// Whether the neighbor is a Full Thread Device.
+    pub full_thread_device: bool,
+    /// Whether the neighbor requests the full Network Data.
+    pub full_network_data: bool,
+    /// Whether the neighbor is a child of this device.
+    pub is_child: bool,
+}
+
+impl From<&otHistoryTrackerNeighborInfo> for HistoryNeighborInfo {
+    fn from(info: &otHistoryTrackerNeighborInfo) -> Self {
+        Self {
+            event: info.mEvent().into(),
+            ext_address: u64::from_be_bytes(info.mExtAddress.m8),
+            rloc16: info.mRloc16,
+            average_rssi: (info.mAverageRssi != OT_RADIO_RSSI_INVALID as i8)
+                .then_some(info.mAverageRssi),
+            rx_on_when_idle: info.mRxOnWhenIdle(),
+            full_thread_device: info.mFullThreadDevice(),
+            full_network_data: info.mFullNetworkData(),
+            is_child: info.mIsChild(),
+        }
+    }
+}
+
+/// What happened to a router table entry, as reported in a [`HistoryRouterInfo`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HistoryRouterEvent {
+    /// The router was added
+    Added,
+    /// The router was removed
+    Removed,
+    /// The next hop (and the path cost) to the router changed
+    NextHopChanged,
+    /// The path cost to the router changed (with the same next hop)
+    CostChanged,
+}
+
+impl From<u8> for HistoryRouterEvent {
+    #[allow(non_upper_case_globals)]
+    fn from(value: u8) -> Self {
+        match value as u32 {
+            otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_ADDED => Self::Added,
+            otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_NEXT_HOP_CHANGED => {
+                Self::NextHopChanged
+            }
+            otHistoryTrackerRouterEvent_OT_HISTORY_TRACKER_ROUTER_EVENT_COST_CHANGED => {
+                Self::CostChanged
+            }
+            _ => Self::Removed,
+        }
+    }
+}
+
+/// A router table entry, as reported by [`OpenThread::history_routers`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HistoryRouterInfo {
+    /// What happened to the router table entry.
+    pub event: HistoryRouterEvent,
+    /// The Router ID.
+    pub router_id: u8,
+    /// The Router ID of the next hop, if any.
+    pub next_hop: Option<u8>,
+    /// The previous path cost, or `None` if infinite (no route).
+    pub old_path_cost: Option<u8>,
+    /// The new path cost, or `None` if infinite (no route).
+    pub path_cost: Option<u8>,
+}
+
+impl From<&otHistoryTrackerRouterInfo> for HistoryRouterInfo {
+    fn from(info: &otHistoryTrackerRouterInfo) -> Self {
+        let cost = |cost: u8| (cost != OT_HISTORY_TRACKER_INFINITE_PATH_COST as u8).then_some(cost);
+
+        Self {
+            event: info.mEvent().into(),
+            router_id: info.mRouterId(),
+            next_hop: (info.mNextHop != OT_HISTORY_TRACKER_NO_NEXT_HOP as u8)
+                .then_some(info.mNextHop),
+            old_path_cost: cost(info.mOldPathCost()),
+            path_cost: cost(info.mPathCost()),
+        }
+    }
+}
+
+/// A Network Data on-mesh prefix entry, as reported by [`OpenThread::history_on_mesh_prefixes`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct HistoryOnMeshPrefixInfo {
+    /// Whether the prefix was added or removed.
+    pub event: HistoryEntryEvent,
+    /// The on-mesh prefix.
+    pub prefix: OtBorderRouterConfig,
+}
+
+impl From<&otHistoryTrackerOnMeshPrefixInfo> for HistoryOnMeshPrefixInfo {
+    fn from(info: &otHistoryTrackerOnMeshPrefixInfo) -> Self {
+        Self {
+            event: HistoryEntryEvent::from_net_data_event(info.mEvent),
+            prefix: OtBorderRouterConfig::from_ot(info.mPrefix),
+        }
+    }
+}
+
+/// A Network Data external route entry, as reported by [`OpenThread::history_external_routes`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct HistoryExternalRouteInfo {
+    /// Whether the route was added or removed.
+    pub event: HistoryEntryEvent,
+    /// The external route.
+    pub route: OtExternalRouteConfig,
+}
+
+impl From<&otHistoryTrackerExternalRouteInfo> for HistoryExternalRouteInfo {
+    fn from(info: &otHistoryTrackerExternalRouteInfo) -> Self {
+        Self {
+            event: HistoryEntryEvent::from_net_data_event(info.mEvent),
+            route: OtExternalRouteConfig::from_ot(info.mRoute),
+        }
+    }
+}
+
+impl OpenThread<'_> {
+    /// Gets the network info history (role, mode, RLOC16 and partition changes)
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_net_info<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryNetInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateNetInfoHistory, f)
+    }
+
+    /// Gets the unicast IPv6 address history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_unicast_addrs<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryUnicastAddrInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateUnicastAddressHistory, f)
+    }
+
+    /// Gets the multicast IPv6 address history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_multicast_addrs<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryMulticastAddrInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateMulticastAddressHistory, f)
+    }
+
+    /// Gets the history of the received IPv6 messages
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_rx<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryMessageInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateRxHistory, f)
+    }
+
+    /// Gets the history of the transmitted IPv6 messages
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_tx<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryMessageInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateTxHistory, f)
+    }
+
+    /// Gets the neighbor table history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_neighbors<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryNeighborInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateNeighborHistory, f)
+    }
+
+    /// Gets the router table history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_routers<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryRouterInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateRouterHistory, f)
+    }
+
+    /// Gets the Network Data on-mesh prefix history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_on_mesh_prefixes<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryOnMeshPrefixInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateOnMeshPrefixHistory, f)
+    }
+
+    /// Gets the Network Data external route history
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry, from the most recent to the oldest.
+    ///   Once called for all entries, the closure will be called with `None`.
+    pub fn history_external_routes<F>(&self, f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&HistoryEntry<HistoryExternalRouteInfo>>) -> Result<(), OtError>,
+    {
+        self.history_iterate(otHistoryTrackerIterateExternalRouteHistory, f)
+    }
+
+    fn history_iterate<R, T, F>(
+        &self,
+        next: unsafe extern "C" fn(
+            *mut otInstance,
+            *mut otHistoryTrackerIterator,
+            *mut u32,
+        ) -> *const R,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        T: for<'r> From<&'r R>,
+        F: FnMut(Option<&HistoryEntry<T>>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut iter = otHistoryTrackerIterator::default();
+        unsafe { otHistoryTrackerInitIterator(&mut iter) };
+
+        let mut age = 0;
+
+        while let Some(info) = unsafe { next(state.ot.instance, &mut iter, &mut age).as_ref() } {
+            f(Some(&HistoryEntry {
+                age_millis: (age < OT_HISTORY_TRACKER_MAX_AGE).then_some(age),
+                info: info.into(),
+            }))?;
+        }
+
+        f(None)
+    }
+}
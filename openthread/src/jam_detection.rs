@@ -0,0 +1,150 @@
+//! Jam detection API (the `jam-detection` feature).
+//!
+//! The jam detector samples the RSSI of the current channel: a second is considered "busy" if the RSSI
+//! stayed above the RSSI threshold for its whole duration, and the channel is considered jammed when
+//! at least `busy_period` of the last `window` seconds were busy.
+//!
+//! The RSSI samples are instantaneous channel energy readings taken by the radio while receiving
+//! (see [`Radio::rssi`](crate::Radio::rssi)). Radios which do not report
+//! [`Capabilities::ENERGY_SCAN`](crate::Capabilities::ENERGY_SCAN) cannot take these, so the
+//! detector never considers the channel busy - and never reports it jammed - with such radios.
+
+use core::ffi::c_void;
+use core::future::poll_fn;
+
+use crate::sys::{
+    otInstance, otJamDetectionGetBusyPeriod, otJamDetectionGetHistoryBitmap,
+    otJamDetectionGetRssiThreshold, otJamDetectionGetState, otJamDetectionGetWindow,
+    otJamDetectionIsEnabled, otJamDetectionSetBusyPeriod, otJamDetectionSetRssiThreshold,
+    otJamDetectionSetWindow, otJamDetectionStart, otJamDetectionStop,
+};
+use crate::{ot, OpenThread, OtContext, OtError};
+
+impl OpenThread<'_> {
+    /// Set the RSSI threshold (in dBm) above which the channel is considered busy.
+    pub fn jam_detection_set_rssi_threshold(&self, rssi_threshold: i8) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otJamDetectionSetRssiThreshold(state.ot.instance, rssi_threshold) })
+    }
+
+    /// Return the RSSI threshold (in dBm) above which the channel is considered busy.
+    pub fn jam_detection_rssi_threshold(&self) -> i8 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionGetRssiThreshold(state.ot.instance) }
+    }
+
+    /// Set the detection window (in seconds), in the range [1, 63].
+    pub fn jam_detection_set_window(&self, window: u8) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otJamDetectionSetWindow(state.ot.instance, window) })
+    }
+
+    /// Return the detection window (in seconds).
+    pub fn jam_detection_window(&self) -> u8 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionGetWindow(state.ot.instance) }
+    }
+
+    /// Set the busy period (in seconds), i.e. the number of busy seconds within the detection window
+    /// after which the channel is considered jammed. Must not be greater than the detection window.
+    pub fn jam_detection_set_busy_period(&self, busy_period: u8) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otJamDetectionSetBusyPeriod(state.ot.instance, busy_period) })
+    }
+
+    /// Return the busy period (in seconds).
+    pub fn jam_detection_busy_period(&self) -> u8 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionGetBusyPeriod(state.ot.instance) }
+    }
+
+    /// Start the jam detection.
+    ///
+    /// The jamming state transitions are reported by [`OpenThread::jam_detection_wait_state_changed`].
+    ///
+    /// NOTE: With radios not reporting [`Capabilities::ENERGY_SCAN`](crate::Capabilities::ENERGY_SCAN),
+    /// the detector never reports the channel as jammed, see the module documentation.
+    pub fn jam_detection_start(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        // Clear any stale transition left over from a prior run
+        state.ot.jam_detection_state.reset();
+
+        ot!(unsafe {
+            otJamDetectionStart(
+                state.ot.instance,
+                Some(OtContext::plat_c_jam_detection_callback),
+                state.ot.instance as *mut _,
+            )
+        })
+    }
+
+    /// Stop the jam detection.
+    pub fn jam_detection_stop(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otJamDetectionStop(state.ot.instance) })
+    }
+
+    /// Return `true` if the jam detection is started.
+    pub fn jam_detection_is_enabled(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionIsEnabled(state.ot.instance) }
+    }
+
+    /// Return `true` if the channel is currently considered jammed.
+    pub fn jam_detection_state(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionGetState(state.ot.instance) }
+    }
+
+    /// Return the busy-period history bitmap of the last 63 seconds.
+    ///
+    /// Bit 0 corresponds to the last second, bit 1 to the second before it, and so on;
+    /// a set bit means the corresponding second was busy.
+    pub fn jam_detection_history_bitmap(&self) -> u64 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otJamDetectionGetHistoryBitmap(state.ot.instance) }
+    }
+
+    /// Wait for the next jamming state transition, and return the new state:
+    /// `true` if the channel became jammed, `false` if it became clear.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn jam_detection_wait_state_changed(&self) -> bool {
+        poll_fn(move |cx| self.activate().state().ot.jam_detection_state.poll_wait(cx)).await
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_jam_detection_callback(jam_state: bool, context: *mut c_void) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        debug!("Jam detection state: {}", jam_state);
+
+        ot.state().ot.jam_detection_state.signal(jam_state);
+    }
+}
@@ -15,7 +15,7 @@ use core::net::{Ipv6Addr, SocketAddrV6};
 use core::pin::pin;
 use core::ptr::addr_of_mut;
 
-use embassy_futures::select::{select, select3, Either, Either3};
+use embassy_futures::select::{select, select4, Either, Either4};
 
 use embassy_time::Instant;
 
@@ -29,12 +29,37 @@ use signal::Signal;
 
 pub use rand_core::RngCore as OtRngCore;
 
+#[cfg(feature = "border-routing")]
+pub use border_routing::*;
+pub use changes::*;
+#[cfg(feature = "coap")]
+pub use coap::*;
+#[cfg(feature = "coaps")]
+pub use coaps::*;
+#[cfg(feature = "commissioner")]
+pub use commissioner::*;
 pub use dataset::*;
 #[cfg(feature = "dns-client")]
 pub use dns::*;
+#[cfg(feature = "dnssd-server")]
+pub use dnssd::*;
 pub use fmt::Bytes as BytesFmt;
+#[cfg(feature = "history-tracker")]
+pub use history_tracker::*;
+#[cfg(any(feature = "link-metrics-initiator", feature = "link-metrics-subject"))]
+pub use link_metrics::*;
+#[cfg(feature = "mac-filter")]
+pub use mac_filter::*;
+#[cfg(feature = "mdns")]
+pub use mdns::*;
+#[cfg(feature = "mesh-diag")]
+pub use mesh_diag::*;
 pub use nat64::*;
 pub use netdata::*;
+#[cfg(feature = "netdata-publisher")]
+pub use netdata_publisher::*;
+#[cfg(feature = "netdiag-client")]
+pub use netdiag::*;
 pub use openthread_sys as sys;
 #[cfg(feature = "ping-sender")]
 pub use ping::*;
@@ -43,22 +68,55 @@ pub use scan::*;
 pub use settings::*;
 #[cfg(feature = "srp-client")]
 pub use srp::*;
+#[cfg(feature = "srp-server")]
+pub use srp_server::*;
+#[cfg(feature = "tcp")]
+pub use tcp::*;
 pub use udp::*;
 
 // This mod MUST go first, so that the others see its macros.
 pub(crate) mod fmt;
 
+#[cfg(feature = "border-routing")]
+mod border_routing;
+mod changes;
 #[cfg(feature = "cli")]
 mod cli;
+#[cfg(feature = "coap")]
+mod coap;
+#[cfg(feature = "coaps")]
+mod coaps;
+#[cfg(feature = "commissioner")]
+mod commissioner;
 mod dataset;
+#[cfg(any(feature = "dhcp6-client", feature = "dhcp6-server"))]
+mod dhcp6;
 #[cfg(feature = "dns-client")]
 mod dns;
+#[cfg(feature = "dnssd-server")]
+mod dnssd;
 #[cfg(feature = "embassy-net-driver-channel")]
 pub mod enet;
+#[cfg(feature = "history-tracker")]
+mod history_tracker;
+#[cfg(feature = "jam-detection")]
+mod jam_detection;
 #[cfg(feature = "joiner")]
 mod joiner;
+#[cfg(any(feature = "link-metrics-initiator", feature = "link-metrics-subject"))]
+mod link_metrics;
+#[cfg(feature = "mac-filter")]
+mod mac_filter;
+#[cfg(feature = "mdns")]
+mod mdns;
+#[cfg(feature = "mesh-diag")]
+mod mesh_diag;
 mod nat64;
 mod netdata;
+#[cfg(feature = "netdata-publisher")]
+mod netdata_publisher;
+#[cfg(feature = "netdiag-client")]
+mod netdiag;
 #[cfg(feature = "ping-sender")]
 mod ping;
 mod platform;
@@ -66,8 +124,14 @@ mod radio;
 mod scan;
 mod settings;
 mod signal;
+#[cfg(feature = "slaac")]
+mod slaac;
 #[cfg(feature = "srp-client")]
 mod srp;
+#[cfg(feature = "srp-server")]
+mod srp_server;
+#[cfg(feature = "tcp")]
+mod tcp;
 mod udp;
 
 use sys::{
@@ -81,11 +145,13 @@ use sys::{
     otIp6IsEnabled, otIp6NewMessageFromBuffer, otIp6Send, otIp6SetEnabled, otIp6SetReceiveCallback,
     otIpCounters, otLinkModeConfig, otMacCounters, otMessage, otMessageFree,
     otMessageGetBufferInfo, otMessagePriority_OT_MESSAGE_PRIORITY_NORMAL, otMessageRead,
-    otMessageSettings, otMleCounters, otOperationalDataset, otOperationalDatasetTlvs,
-    otPlatAlarmMilliFired, otPlatRadioEnergyScanDone, otPlatRadioReceiveDone, otPlatRadioTxDone,
-    otPlatRadioTxStarted, otRadioCaps, otRadioFrame, otSetStateChangedCallback, otTaskletsProcess,
-    otThreadGetDeviceRole, otThreadGetExtendedPanId, otThreadSetEnabled, otThreadSetLinkMode,
-    OT_RADIO_CAPS_ACK_TIMEOUT, OT_RADIO_FRAME_MAX_SIZE, OT_RADIO_RSSI_INVALID,
+    otMessageSettings, otMleCounters, otNetifAddress, otOperationalDataset,
+    otOperationalDatasetTlvs, otPlatAlarmMilliFired, otPlatRadioEnergyScanDone,
+    otPlatRadioReceiveDone, otPlatRadioTxDone, otPlatRadioTxStarted, otRadioCaps, otRadioFrame,
+    otSetStateChangedCallback, otTaskletsProcess, otThreadGetDeviceRole, otThreadGetExtendedPanId,
+    otThreadSetEnabled, otThreadSetLinkMode, OT_ADDRESS_ORIGIN_DHCPV6, OT_ADDRESS_ORIGIN_MANUAL,
+    OT_ADDRESS_ORIGIN_SLAAC, OT_ADDRESS_ORIGIN_THREAD, OT_RADIO_CAPS_ACK_TIMEOUT,
+    OT_RADIO_FRAME_MAX_SIZE, OT_RADIO_RSSI_INVALID,
 };
 
 /// A newtype wrapper over the native OpenThread error type (`otError`).
@@ -156,6 +222,10 @@ pub struct OpenThread<'a> {
     udp_state: Option<&'a RefCell<OtUdpState<'a>>>,
     #[cfg(feature = "srp-client")]
     srp_state: Option<&'a RefCell<OtSrpState<'a>>>,
+    #[cfg(feature = "coap")]
+    coap_state: Option<&'a RefCell<OtCoapState<'a>>>,
+    #[cfg(feature = "tcp")]
+    tcp_state: Option<&'a RefCell<OtTcpState<'a>>>,
 }
 
 impl<'a> OpenThread<'a> {
@@ -168,11 +238,11 @@ impl<'a> OpenThread<'a> {
     ///
     /// Returns:
     /// - In case there were no errors related to initializing the OpenThread library, the OpenThread instance.
-    pub fn new(
+    pub fn new<const CHANGE_SUBSCRIBERS: usize>(
         ieee_eui64: [u8; 8],
         rng: &'a mut dyn OtRngCore,
         settings: &'a mut dyn Settings,
-        resources: &'a mut OtResources,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
     ) -> Result<Self, OtError> {
         if OT_REFCNT
             .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
@@ -183,31 +253,7 @@ impl<'a> OpenThread<'a> {
             Err(OtError::new(otError_OT_ERROR_NO_BUFS))?;
         }
 
-        // Needed so that we convert from the the actual `'a` lifetime of `rng` to the fake `'static` lifetime in `OtResources`
-        let state = resources.init(
-            ieee_eui64,
-            unsafe {
-                core::mem::transmute::<&'a mut dyn OtRngCore, &'static mut dyn OtRngCore>(rng)
-            },
-            unsafe {
-                core::mem::transmute::<&'a mut dyn Settings, &'static mut dyn Settings>(settings)
-            },
-        );
-
-        let state = unsafe {
-            core::mem::transmute::<&RefCell<OtState<'static>>, &'a RefCell<OtState<'a>>>(state)
-        };
-
-        let mut this = Self {
-            state,
-            udp_state: None,
-            #[cfg(feature = "srp-client")]
-            srp_state: None,
-        };
-
-        this.init()?;
-
-        Ok(this)
+        Self::builder(ieee_eui64, rng, settings, resources).build()
     }
 
     /// Create a new OpenThread instance with support for native OpenThread UDP sockets.
@@ -220,45 +266,20 @@ impl<'a> OpenThread<'a> {
     ///
     /// Returns:
     /// - In case there were no errors related to initializing the OpenThread library, the OpenThread instance.
-    pub fn new_with_udp<const UDP_SOCKETS: usize, const UDP_RX_SZ: usize>(
+    pub fn new_with_udp<
+        const CHANGE_SUBSCRIBERS: usize,
+        const UDP_SOCKETS: usize,
+        const UDP_RX_SZ: usize,
+    >(
         ieee_eui64: [u8; 8],
         rng: &'a mut dyn OtRngCore,
         settings: &'a mut dyn Settings,
-        resources: &'a mut OtResources,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
         udp_resources: &'a mut OtUdpResources<UDP_SOCKETS, UDP_RX_SZ>,
     ) -> Result<Self, OtError> {
-        // Needed so that we convert from the the actual `'a` lifetime of `rng` to the fake `'static` lifetime in `OtResources`
-        let state = resources.init(
-            ieee_eui64,
-            unsafe {
-                core::mem::transmute::<&'a mut dyn OtRngCore, &'static mut dyn OtRngCore>(rng)
-            },
-            unsafe {
-                core::mem::transmute::<&'a mut dyn Settings, &'static mut dyn Settings>(settings)
-            },
-        );
-
-        let state = unsafe {
-            core::mem::transmute::<&RefCell<OtState<'static>>, &'a RefCell<OtState<'a>>>(state)
-        };
-
-        let udp_state = udp_resources.init();
-        let udp_state = unsafe {
-            core::mem::transmute::<&RefCell<OtUdpState<'static>>, &'a RefCell<OtUdpState<'a>>>(
-                udp_state,
-            )
-        };
-
-        let mut this = Self {
-            state,
-            udp_state: Some(udp_state),
-            #[cfg(feature = "srp-client")]
-            srp_state: None,
-        };
-
-        this.init()?;
-
-        Ok(this)
+        Self::builder(ieee_eui64, rng, settings, resources)
+            .udp(udp_resources)
+            .build()
     }
 
     /// Create a new OpenThread instance with support for native OpenThread SRP services.
@@ -272,44 +293,20 @@ impl<'a> OpenThread<'a> {
     /// Returns:
     /// - In case there were no errors related to initializing the OpenThread library, the OpenThread instance.
     #[cfg(feature = "srp-client")]
-    pub fn new_with_srp<const SRP_SVCS: usize, const SRP_BUF_SZ: usize>(
+    pub fn new_with_srp<
+        const CHANGE_SUBSCRIBERS: usize,
+        const SRP_SVCS: usize,
+        const SRP_BUF_SZ: usize,
+    >(
         ieee_eui64: [u8; 8],
         rng: &'a mut dyn OtRngCore,
         settings: &'a mut dyn Settings,
-        resources: &'a mut OtResources,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
         srp_resources: &'a mut OtSrpResources<SRP_SVCS, SRP_BUF_SZ>,
     ) -> Result<Self, OtError> {
-        // Needed so that we convert from the the actual `'a` lifetime of `rng` to the fake `'static` lifetime in `OtResources`
-        let state = resources.init(
-            ieee_eui64,
-            unsafe {
-                core::mem::transmute::<&'a mut dyn OtRngCore, &'static mut dyn OtRngCore>(rng)
-            },
-            unsafe {
-                core::mem::transmute::<&'a mut dyn Settings, &'static mut dyn Settings>(settings)
-            },
-        );
-
-        let state = unsafe {
-            core::mem::transmute::<&RefCell<OtState<'static>>, &'a RefCell<OtState<'a>>>(state)
-        };
-
-        let srp_state = srp_resources.init();
-        let srp_state = unsafe {
-            core::mem::transmute::<&RefCell<OtSrpState<'static>>, &'a RefCell<OtSrpState<'a>>>(
-                srp_state,
-            )
-        };
-
-        let mut this = Self {
-            state,
-            udp_state: None,
-            srp_state: Some(srp_state),
-        };
-
-        this.init()?;
-
-        Ok(this)
+        Self::builder(ieee_eui64, rng, settings, resources)
+            .srp(srp_resources)
+            .build()
     }
 
     /// Create a new OpenThread instance with support for native OpenThread UDP sockets and SRP services.
@@ -325,6 +322,7 @@ impl<'a> OpenThread<'a> {
     /// - In case there were no errors related to initializing the OpenThread library, the OpenThread instance.
     #[cfg(feature = "srp-client")]
     pub fn new_with_udp_srp<
+        const CHANGE_SUBSCRIBERS: usize,
         const UDP_SOCKETS: usize,
         const UDP_RX_SZ: usize,
         const SRP_SVCS: usize,
@@ -333,11 +331,52 @@ impl<'a> OpenThread<'a> {
         ieee_eui64: [u8; 8],
         rng: &'a mut dyn OtRngCore,
         settings: &'a mut dyn Settings,
-        resources: &'a mut OtResources,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
         udp_resources: &'a mut OtUdpResources<UDP_SOCKETS, UDP_RX_SZ>,
         srp_resources: &'a mut OtSrpResources<SRP_SVCS, SRP_BUF_SZ>,
     ) -> Result<Self, OtError> {
-        // Needed so that we convert from the the actual `'a` lifetime of `rng` to the fake `'static` lifetime in `OtResources`
+        Self::builder(ieee_eui64, rng, settings, resources)
+            .udp(udp_resources)
+            .srp(srp_resources)
+            .build()
+    }
+
+    /// Create a builder for an OpenThread instance with any combination of the optional
+    /// native UDP sockets, SRP services, CoAP agent and TCP sockets.
+    ///
+    /// The `new*` constructors are shorthands for the builder.
+    ///
+    /// Arguments:
+    /// - `ieee_eui64`: The IEEE EUI-64 address of the Radio device.
+    /// - `rng`: A mutable reference to a random number generator that will be used by OpenThread.
+    /// - `resources`: A mutable reference to the OpenThread resources.
+    pub fn builder<const CHANGE_SUBSCRIBERS: usize>(
+        ieee_eui64: [u8; 8],
+        rng: &'a mut dyn OtRngCore,
+        settings: &'a mut dyn Settings,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
+    ) -> OpenThreadBuilder<'a> {
+        OpenThreadBuilder {
+            state: Self::state(ieee_eui64, rng, settings, resources),
+            udp_state: None,
+            #[cfg(feature = "srp-client")]
+            srp_state: None,
+            #[cfg(feature = "coap")]
+            coap_state: None,
+            #[cfg(feature = "tcp")]
+            tcp_state: None,
+        }
+    }
+
+    /// Initialize the resources and return the OpenThread state with the actual `'a` lifetime.
+    ///
+    /// The OpenThread library itself is initialized later, by `init`.
+    fn state<const CHANGE_SUBSCRIBERS: usize>(
+        ieee_eui64: [u8; 8],
+        rng: &'a mut dyn OtRngCore,
+        settings: &'a mut dyn Settings,
+        resources: &'a mut OtResources<CHANGE_SUBSCRIBERS>,
+    ) -> &'a RefCell<OtState<'a>> {
         // Needed so that we convert from the the actual `'a` lifetime of `rng` to the fake `'static` lifetime in `OtResources`
         let state = resources.init(
             ieee_eui64,
@@ -349,33 +388,73 @@ impl<'a> OpenThread<'a> {
             },
         );
 
-        let state = unsafe {
+        unsafe {
             core::mem::transmute::<&RefCell<OtState<'static>>, &'a RefCell<OtState<'a>>>(state)
-        };
+        }
+    }
 
+    /// Initialize the UDP resources and return the UDP state with the actual `'a` lifetime.
+    fn udp_state<const UDP_SOCKETS: usize, const UDP_RX_SZ: usize>(
+        udp_resources: &'a mut OtUdpResources<UDP_SOCKETS, UDP_RX_SZ>,
+    ) -> &'a RefCell<OtUdpState<'a>> {
         let udp_state = udp_resources.init();
-        let udp_state = unsafe {
+
+        unsafe {
             core::mem::transmute::<&RefCell<OtUdpState<'static>>, &'a RefCell<OtUdpState<'a>>>(
                 udp_state,
             )
-        };
+        }
+    }
 
+    /// Initialize the SRP resources and return the SRP state with the actual `'a` lifetime.
+    #[cfg(feature = "srp-client")]
+    fn srp_state<const SRP_SVCS: usize, const SRP_BUF_SZ: usize>(
+        srp_resources: &'a mut OtSrpResources<SRP_SVCS, SRP_BUF_SZ>,
+    ) -> &'a RefCell<OtSrpState<'a>> {
         let srp_state = srp_resources.init();
-        let srp_state = unsafe {
+
+        unsafe {
             core::mem::transmute::<&RefCell<OtSrpState<'static>>, &'a RefCell<OtSrpState<'a>>>(
                 srp_state,
             )
-        };
+        }
+    }
 
-        let mut this = Self {
-            state,
-            udp_state: Some(udp_state),
-            srp_state: Some(srp_state),
-        };
+    /// Initialize the TCP resources and return the TCP state with the actual `'a` lifetime.
+    #[cfg(feature = "tcp")]
+    fn tcp_state<
+        const TCP_SOCKETS: usize,
+        const TCP_LISTENERS: usize,
+        const TCP_RX_SZ: usize,
+        const TCP_TX_SZ: usize,
+    >(
+        tcp_resources: &'a mut OtTcpResources<TCP_SOCKETS, TCP_LISTENERS, TCP_RX_SZ, TCP_TX_SZ>,
+    ) -> &'a RefCell<OtTcpState<'a>> {
+        let tcp_state = tcp_resources.init();
+
+        unsafe {
+            core::mem::transmute::<&RefCell<OtTcpState<'static>>, &'a RefCell<OtTcpState<'a>>>(
+                tcp_state,
+            )
+        }
+    }
 
-        this.init()?;
+    /// Initialize the CoAP resources and return the CoAP state with the actual `'a` lifetime.
+    #[cfg(feature = "coap")]
+    fn coap_state<
+        const COAP_RESOURCES: usize,
+        const COAP_REQUESTS: usize,
+        const COAP_BUF_SZ: usize,
+    >(
+        coap_resources: &'a mut OtCoapResources<COAP_RESOURCES, COAP_REQUESTS, COAP_BUF_SZ>,
+    ) -> &'a RefCell<OtCoapState<'a>> {
+        let coap_state = coap_resources.init();
 
-        Ok(this)
+        unsafe {
+            core::mem::transmute::<&RefCell<OtCoapState<'static>>, &'a RefCell<OtCoapState<'a>>>(
+                coap_state,
+            )
+        }
     }
 
     /// Return the IEEE EUI-64 address of the Radio device.
@@ -840,6 +919,117 @@ impl<'a> OpenThread<'a> {
         }
     }
 
+    /// Add a static IPv6 unicast address to the Thread interface (`otIp6AddUnicastAddress`).
+    ///
+    /// The address is reported by [`Self::ipv6_addrs_info`] with the [`Ipv6AddrOrigin::Manual`] origin.
+    ///
+    /// Adding an already-added address updates its prefix length, flags and lifetimes.
+    ///
+    /// An address with a finite preferred (valid) lifetime is deprecated (removed) by [`Self::run`]
+    /// once the lifetime runs out; at most [`IPV6_MAX_TIMED_UNICAST_ADDRS`] such addresses can be
+    /// added at the same time, and adding more fails with `OT_ERROR_NO_BUFS`.
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` if the valid lifetime is zero or shorter than the preferred lifetime.
+    pub fn add_unicast_addr(&self, config: &Ipv6UnicastAddrConfig) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let invalid = match (config.preferred_lifetime, config.valid_lifetime) {
+            (_, Some(valid)) if valid.as_ticks() == 0 => true,
+            (None, Some(_)) => true,
+            (Some(preferred), Some(valid)) => preferred > valid,
+            _ => false,
+        };
+
+        if invalid {
+            Err(OtError::new(sys::otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let now = Instant::now();
+        let timed = TimedUnicastAddr {
+            config: *config,
+            deprecate_at: config
+                .preferred_lifetime
+                .filter(|lifetime| lifetime.as_ticks() > 0)
+                .map(|lifetime| now + lifetime),
+            remove_at: config.valid_lifetime.map(|lifetime| now + lifetime),
+        };
+        let is_timed = timed.deprecate_at.is_some() || timed.remove_at.is_some();
+
+        let tracked = state
+            .ot
+            .timed_unicast_addrs
+            .iter()
+            .position(|timed| timed.config.addr == config.addr);
+
+        if is_timed && tracked.is_none() && state.ot.timed_unicast_addrs.is_full() {
+            Err(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+        }
+
+        let preferred = config
+            .preferred_lifetime
+            .is_none_or(|lifetime| lifetime.as_ticks() > 0);
+
+        ot!(unsafe { sys::otIp6AddUnicastAddress(state.ot.instance, &config.to_ot(preferred)) })?;
+
+        if let Some(index) = tracked {
+            state.ot.timed_unicast_addrs.swap_remove(index);
+        }
+
+        if is_timed {
+            unwrap!(state.ot.timed_unicast_addrs.push(timed).ok());
+        }
+
+        state.ot.timed_unicast_addrs_changed.signal(());
+
+        Ok(())
+    }
+
+    /// Remove a static IPv6 unicast address previously added with [`Self::add_unicast_addr`]
+    /// from the Thread interface (`otIp6RemoveUnicastAddress`).
+    ///
+    /// Fails with `OT_ERROR_NOT_FOUND` if the address was not added (or was already removed
+    /// because its valid lifetime ran out).
+    pub fn remove_unicast_addr(&self, addr: Ipv6Addr) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state
+            .ot
+            .timed_unicast_addrs
+            .retain(|timed| timed.config.addr != addr);
+        state.ot.timed_unicast_addrs_changed.signal(());
+
+        ot!(unsafe { sys::otIp6RemoveUnicastAddress(state.ot.instance, &to_ot_ip6_addr(addr)) })
+    }
+
+    /// Gets the list of IPv6 multicast groups the Thread interface is subscribed to,
+    /// including the ones subscribed to by OpenThread itself (e.g. `ff02::1`, `ff03::fc`)
+    /// as well as the ones joined with [`Self::join_multicast`]
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each multicast address.
+    ///   Once called for all addresses, the closure will be called with `None`.
+    pub fn ipv6_multicast_addrs<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<Ipv6Addr>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut addrs_ptr = unsafe { sys::otIp6GetMulticastAddresses(state.ot.instance) };
+
+        while !addrs_ptr.is_null() {
+            let addrs = unwrap!(unsafe { addrs_ptr.as_ref() });
+
+            f(Some(unsafe { addrs.mAddress.mFields.m8 }.into()))?;
+
+            addrs_ptr = addrs.mNext;
+        }
+
+        f(None)
+    }
+
     /// This function starts/stops the Thread protocol operation.
     ///
     /// TODO: The interface must be up when calling this function.
@@ -1036,6 +1226,8 @@ impl<'a> OpenThread<'a> {
 
     /// Gets the list of IPv6 addresses currently assigned to the Thread interface
     ///
+    /// See also [`OpenThread::ipv6_addrs_info`], which reports the origin, flags and scope of each address.
+    ///
     /// Arguments:
     /// - `f`: A closure that will be called for each IPv6 address available.
     ///   Once called for all addresses, the closure will be called with `None`.
@@ -1062,6 +1254,32 @@ impl<'a> OpenThread<'a> {
         f(None)
     }
 
+    /// Gets the list of IPv6 addresses currently assigned to the Thread interface,
+    /// along with their origin, flags and scope
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each IPv6 address available.
+    ///   Once called for all addresses, the closure will be called with `None`.
+    pub fn ipv6_addrs_info<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&Ipv6AddrInfo>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut addrs_ptr = unsafe { otIp6GetUnicastAddresses(state.ot.instance) };
+
+        while !addrs_ptr.is_null() {
+            let addrs = unwrap!(unsafe { addrs_ptr.as_ref() });
+
+            f(Some(&Ipv6AddrInfo::from_ot(addrs)))?;
+
+            addrs_ptr = addrs.mNext;
+        }
+
+        f(None)
+    }
+
     /// Run a closure with direct access to the raw `otInstance` pointer.
     ///
     /// An escape hatch for calling OpenThread C APIs (`otXxx`) this crate does
@@ -1094,10 +1312,15 @@ impl<'a> OpenThread<'a> {
 
     /// Wait for the OpenThread stack to change its state.
     ///
+    /// See also [`Self::wait_changed_flags`], which returns what has changed,
+    /// and [`ChangeSubscriber`] for waiting for changes from multiple async tasks.
+    ///
     /// NOTE:
     /// It is not advised to call this method concurrently from multiple async tasks
     /// because it uses a single waker registration. Thus, while the method will not panic,
     /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    /// Only [`ChangeSubscriber::wait`] supports multiple concurrent consumers, with a
+    /// [`ChangeSubscriber`] per task.
     pub async fn wait_changed(&self) {
         poll_fn(move |cx| self.activate().state().ot.changes.poll_wait(cx)).await;
     }
@@ -1128,11 +1351,12 @@ impl<'a> OpenThread<'a> {
         let mut radio = pin!(self.run_radio(radio));
         let mut alarm = pin!(self.run_alarm());
         let mut openthread = pin!(self.run_tasklets());
+        let mut addr_lifetimes = pin!(self.run_addr_lifetimes());
 
-        let result = select3(&mut radio, &mut alarm, &mut openthread).await;
+        let result = select4(&mut radio, &mut alarm, &mut openthread, &mut addr_lifetimes).await;
 
         match result {
-            Either3::First(r) | Either3::Second(r) | Either3::Third(r) => r,
+            Either4::First(r) | Either4::Second(r) | Either4::Third(r) | Either4::Fourth(r) => r,
         }
     }
 
@@ -1232,6 +1456,15 @@ impl<'a> OpenThread<'a> {
                 )
             }
 
+            #[cfg(feature = "nat64")]
+            unsafe {
+                crate::sys::otNat64SetReceiveIp4Callback(
+                    state.ot.instance,
+                    Some(OtContext::plat_c_nat64_receive_callback),
+                    state.ot.instance as *mut _,
+                )
+            }
+
             #[cfg(feature = "srp-client")]
             unsafe {
                 crate::sys::otSrpClientSetCallback(
@@ -1293,6 +1526,70 @@ impl<'a> OpenThread<'a> {
         }
     }
 
+    /// An async loop that deprecates and removes the static unicast addresses added with
+    /// `add_unicast_addr` once their preferred and valid lifetimes run out.
+    async fn run_addr_lifetimes(&self) -> ! {
+        let changed = || {
+            poll_fn(move |cx| {
+                self.activate()
+                    .state()
+                    .ot
+                    .timed_unicast_addrs_changed
+                    .poll_wait(cx)
+            })
+        };
+
+        loop {
+            let next = self
+                .activate()
+                .state()
+                .ot
+                .timed_unicast_addrs
+                .iter()
+                .flat_map(|timed| [timed.deprecate_at, timed.remove_at])
+                .flatten()
+                .min();
+
+            let Some(next) = next else {
+                changed().await;
+                continue;
+            };
+
+            if let Either::First(_) = select(changed(), embassy_time::Timer::at(next)).await {
+                continue;
+            }
+
+            let mut ot = self.activate();
+            let state = ot.state();
+            let instance = state.ot.instance;
+            let now = Instant::now();
+
+            state.ot.timed_unicast_addrs.retain_mut(|timed| {
+                if timed.remove_at.is_some_and(|at| at <= now) {
+                    debug!("Removing expired unicast address {}", timed.config.addr);
+
+                    unsafe {
+                        sys::otIp6RemoveUnicastAddress(instance, &to_ot_ip6_addr(timed.config.addr))
+                    };
+
+                    return false;
+                }
+
+                if timed.deprecate_at.is_some_and(|at| at <= now) {
+                    debug!("Deprecating unicast address {}", timed.config.addr);
+
+                    unsafe { sys::otIp6AddUnicastAddress(instance, &timed.config.to_ot(false)) };
+
+                    timed.deprecate_at = None;
+                }
+
+                timed.remove_at.is_some()
+            });
+
+            ot.process_tasklets();
+        }
+    }
+
     /// An async loop that sends or receives IEEE 802.15.4 frames, based on commands issued by the OT loop
     ///
     /// Needs to be a separate async loop, because OpenThread C is unaware of async/await and futures,
@@ -1334,12 +1631,17 @@ impl<'a> OpenThread<'a> {
         // more importantly, the timing headroom of its own executor.
         caps.mac.assert_required();
 
+        // Only radios able to measure the channel energy are asked for RSSI readings
+        let rssi_sampling = caps.phy.contains(radio::Capabilities::ENERGY_SCAN);
+
         {
             let mut activated = self.activate();
             let state = activated.state();
 
             state.ot.radio_caps = caps.phy.bits();
             state.ot.radio_sensitivity = caps.receive_sensitivity;
+            state.ot.radio_enh_ack_probing =
+                caps.mac.contains(radio::MacCapabilities::ENH_ACK_PROBING);
             state.ot.radio_cca_threshold = caps.default_cca_threshold;
             state.ot.radio_tx_power = caps.default_tx_power;
         }
@@ -1359,7 +1661,8 @@ impl<'a> OpenThread<'a> {
 
             let action = if let Some(rx_channel) = rx_channel {
                 let mut action = pin!(self.radio_action());
-                let mut rx = pin!(self.run_radio_rx(&mut radio, rx_channel, &mut psdu_buf));
+                let mut rx =
+                    pin!(self.run_radio_rx(&mut radio, rx_channel, rssi_sampling, &mut psdu_buf));
 
                 let Either::First(action) = select(&mut action, &mut rx).await;
 
@@ -1371,7 +1674,7 @@ impl<'a> OpenThread<'a> {
             };
 
             match action {
-                Either3::First(_) => {
+                Either4::First(_) => {
                     let conf = {
                         let mut ot = self.activate();
                         let state = ot.state();
@@ -1383,7 +1686,7 @@ impl<'a> OpenThread<'a> {
 
                     unwrap_dbg!(radio.set_config(&conf).await);
                 }
-                Either3::Second(_) => {
+                Either4::Second(_) => {
                     let src = {
                         let mut ot = self.activate();
                         let state = ot.state();
@@ -1395,7 +1698,22 @@ impl<'a> OpenThread<'a> {
 
                     unwrap_dbg!(radio.set_src_match_config(&src).await);
                 }
-                Either3::Third(cmd) => {
+                Either4::Third(_) => {
+                    let enh_ack_probing = {
+                        let mut ot = self.activate();
+                        let state = ot.state();
+
+                        state.ot.radio_conf_enh_ack_probing.clone()
+                    };
+
+                    trace!(
+                        "Radio Enhanced-ACK probing table changed: {:?}",
+                        enh_ack_probing
+                    );
+
+                    unwrap_dbg!(radio.set_enh_ack_probing_config(&enh_ack_probing).await);
+                }
+                Either4::Fourth(cmd) => {
                     trace!("Got radio command: {:?}", cmd);
 
                     let mut new_cmd = pin!(self.wait_new_radio_cmd());
@@ -1433,8 +1751,17 @@ impl<'a> OpenThread<'a> {
 
     /// Repeatedly receive IEEE 802.15.4 frames from the radio and pass them to the OpenThread C library.
     ///
+    /// If `rssi_sampling` is `true`, also sample the instantaneous RSSI of the channel whenever
+    /// OpenThread asks for it (`otPlatRadioGetRssi`), interrupting the reception of the frames.
+    ///
     /// This loop runs forever, unless cancelled by dropping the future.
-    async fn run_radio_rx<R>(&self, mut radio: R, channel: u8, psdu_buf: &mut [u8]) -> !
+    async fn run_radio_rx<R>(
+        &self,
+        mut radio: R,
+        channel: u8,
+        rssi_sampling: bool,
+        psdu_buf: &mut [u8],
+    ) -> !
     where
         R: Radio,
     {
@@ -1443,7 +1770,38 @@ impl<'a> OpenThread<'a> {
         loop {
             self.activate().process_tasklets();
 
-            let result = radio.receive(psdu_buf).await;
+            let result = if rssi_sampling {
+                let rssi_requested = poll_fn(move |cx| {
+                    self.activate()
+                        .state()
+                        .ot
+                        .radio_rssi_requested
+                        .poll_wait(cx)
+                });
+
+                match select(radio.receive(psdu_buf), rssi_requested).await {
+                    Either::First(result) => result,
+                    Either::Second(_) => {
+                        let rssi = match radio.rssi(channel).await {
+                            Ok(rssi) => rssi,
+                            Err(err) => {
+                                warn!("RSSI reading failed: {:?}", dbg2fmt!(err));
+                                OT_RADIO_RSSI_INVALID as i8
+                            }
+                        };
+
+                        trace!("RSSI reading: {}", rssi);
+
+                        self.activate().state().ot.radio_rssi = rssi;
+
+                        unwrap_dbg!(radio.set_receive(channel).await);
+
+                        continue;
+                    }
+                }
+            } else {
+                radio.receive(psdu_buf).await
+            };
 
             let mut ot = self.activate();
             let state = ot.state();
@@ -1460,10 +1818,6 @@ impl<'a> OpenThread<'a> {
 
                     let instance = state.ot.instance;
 
-                    if let Some(rssi) = rcv_psdu_meta.rssi {
-                        state.ot.last_rssi = rssi;
-                    }
-
                     // Computed before `radio_resources`
                     // takes its mutable borrow.
                     let acked_with_fp =
@@ -1555,10 +1909,6 @@ impl<'a> OpenThread<'a> {
             let mut ot = self.activate();
             let state = ot.state();
 
-            if let Some(rssi) = result.as_ref().ok().and_then(|m| m.and_then(|m| m.rssi)) {
-                state.ot.last_rssi = rssi;
-            }
-
             let radio_resources = &mut state.ot.radio_resources;
 
             match result {
@@ -1668,7 +2018,7 @@ impl<'a> OpenThread<'a> {
     /// Get the next radio action to be performed by the OpenThread stack.
     ///
     /// Await if there is no action to be performed yet.
-    async fn radio_action(&self) -> Either3<(), (), RadioCommand> {
+    async fn radio_action(&self) -> Either4<(), (), (), RadioCommand> {
         let mut conf = pin!(poll_fn(move |cx| self
             .activate()
             .state()
@@ -1681,6 +2031,12 @@ impl<'a> OpenThread<'a> {
             .ot
             .radio_conf_src_match_changed
             .poll_wait(cx)));
+        let mut enh_ack_probing = pin!(poll_fn(move |cx| self
+            .activate()
+            .state()
+            .ot
+            .radio_conf_enh_ack_probing_changed
+            .poll_wait(cx)));
         let mut cmd = pin!(poll_fn(move |cx| self
             .activate()
             .state()
@@ -1688,7 +2044,7 @@ impl<'a> OpenThread<'a> {
             .radio_cmd
             .poll_wait(cx)));
 
-        select3(&mut conf, &mut src, &mut cmd).await
+        select4(&mut conf, &mut src, &mut enh_ack_probing, &mut cmd).await
     }
 
     /// Await until the OpenThread stack cancels the radio excursion
@@ -1760,22 +2116,6 @@ impl<'a> OpenThread<'a> {
         psdu: &[u8],
         acked_with_fp: bool,
     ) {
-        /// Convert from RSSI (Received Signal Strength Indicator) to LQI (Link Quality
-        /// Indication)
-        ///
-        /// RSSI is a measure of incoherent (raw) RF power in a channel. LQI is a
-        /// cumulative value used in multi-hop networks to assess the cost of a link.
-        fn rssi_to_lqi(rssi: i8) -> u8 {
-            if rssi < -80 {
-                0
-            } else if rssi > -30 {
-                0xff
-            } else {
-                let lqi_convert = ((rssi as u32).wrapping_add(80)) * 255;
-                (lqi_convert / 50) as u8
-            }
-        }
-
         let rssi = psdu_meta.rssi.unwrap_or(0);
 
         frame_psdu[..psdu.len()].copy_from_slice(psdu);
@@ -1783,7 +2123,7 @@ impl<'a> OpenThread<'a> {
         frame.mRadioType = 1; // TODO: Figure out what is this
         frame.mChannel = psdu_meta.channel;
         frame.mInfo.mRxInfo.mRssi = rssi;
-        frame.mInfo.mRxInfo.mLqi = psdu_meta.lqi.unwrap_or_else(|| rssi_to_lqi(rssi));
+        frame.mInfo.mRxInfo.mLqi = psdu_meta.lqi.unwrap_or_else(|| radio::rssi_to_lqi(rssi));
         frame.mInfo.mRxInfo.mTimestamp = Instant::now().as_micros(); // TODO: Not precise
 
         // The flag is what makes the stack serve a sleepy child's data
@@ -1798,6 +2138,104 @@ impl<'a> OpenThread<'a> {
     }
 }
 
+/// A builder for an OpenThread instance, created with [`OpenThread::builder`].
+///
+/// Each of the optional native UDP sockets, SRP services, CoAP agent and TCP sockets
+/// is enabled by passing its resources to the corresponding method.
+pub struct OpenThreadBuilder<'a> {
+    state: &'a RefCell<OtState<'a>>,
+    udp_state: Option<&'a RefCell<OtUdpState<'a>>>,
+    #[cfg(feature = "srp-client")]
+    srp_state: Option<&'a RefCell<OtSrpState<'a>>>,
+    #[cfg(feature = "coap")]
+    coap_state: Option<&'a RefCell<OtCoapState<'a>>>,
+    #[cfg(feature = "tcp")]
+    tcp_state: Option<&'a RefCell<OtTcpState<'a>>>,
+}
+
+impl<'a> OpenThreadBuilder<'a> {
+    /// Enable the native OpenThread UDP sockets.
+    ///
+    /// Arguments:
+    /// - `udp_resources`: A mutable reference to the OpenThread UDP resources.
+    pub fn udp<const UDP_SOCKETS: usize, const UDP_RX_SZ: usize>(
+        mut self,
+        udp_resources: &'a mut OtUdpResources<UDP_SOCKETS, UDP_RX_SZ>,
+    ) -> Self {
+        self.udp_state = Some(OpenThread::udp_state(udp_resources));
+        self
+    }
+
+    /// Enable the native OpenThread SRP services.
+    ///
+    /// Arguments:
+    /// - `srp_resources`: A mutable reference to the OpenThread SRP resources.
+    #[cfg(feature = "srp-client")]
+    pub fn srp<const SRP_SVCS: usize, const SRP_BUF_SZ: usize>(
+        mut self,
+        srp_resources: &'a mut OtSrpResources<SRP_SVCS, SRP_BUF_SZ>,
+    ) -> Self {
+        self.srp_state = Some(OpenThread::srp_state(srp_resources));
+        self
+    }
+
+    /// Enable the native OpenThread CoAP agent.
+    ///
+    /// Arguments:
+    /// - `coap_resources`: A mutable reference to the OpenThread CoAP resources.
+    #[cfg(feature = "coap")]
+    pub fn coap<
+        const COAP_RESOURCES: usize,
+        const COAP_REQUESTS: usize,
+        const COAP_BUF_SZ: usize,
+    >(
+        mut self,
+        coap_resources: &'a mut OtCoapResources<COAP_RESOURCES, COAP_REQUESTS, COAP_BUF_SZ>,
+    ) -> Self {
+        self.coap_state = Some(OpenThread::coap_state(coap_resources));
+        self
+    }
+
+    /// Enable the native OpenThread TCP sockets.
+    ///
+    /// Arguments:
+    /// - `tcp_resources`: A mutable reference to the OpenThread TCP resources.
+    #[cfg(feature = "tcp")]
+    pub fn tcp<
+        const TCP_SOCKETS: usize,
+        const TCP_LISTENERS: usize,
+        const TCP_RX_SZ: usize,
+        const TCP_TX_SZ: usize,
+    >(
+        mut self,
+        tcp_resources: &'a mut OtTcpResources<TCP_SOCKETS, TCP_LISTENERS, TCP_RX_SZ, TCP_TX_SZ>,
+    ) -> Self {
+        self.tcp_state = Some(OpenThread::tcp_state(tcp_resources));
+        self
+    }
+
+    /// Create the OpenThread instance.
+    ///
+    /// Returns:
+    /// - In case there were no errors related to initializing the OpenThread library, the OpenThread instance.
+    pub fn build(self) -> Result<OpenThread<'a>, OtError> {
+        let mut ot = OpenThread {
+            state: self.state,
+            udp_state: self.udp_state,
+            #[cfg(feature = "srp-client")]
+            srp_state: self.srp_state,
+            #[cfg(feature = "coap")]
+            coap_state: self.coap_state,
+            #[cfg(feature = "tcp")]
+            tcp_state: self.tcp_state,
+        };
+
+        ot.init()?;
+
+        Ok(ot)
+    }
+}
+
 impl Drop for OpenThread<'_> {
     fn drop(&mut self) {
         if OT_REFCNT.load(Ordering::SeqCst) == 1 {
@@ -1825,6 +2263,10 @@ impl Clone for OpenThread<'_> {
             udp_state: self.udp_state,
             #[cfg(feature = "srp-client")]
             srp_state: self.srp_state,
+            #[cfg(feature = "coap")]
+            coap_state: self.coap_state,
+            #[cfg(feature = "tcp")]
+            tcp_state: self.tcp_state,
         }
     }
 }
@@ -1835,11 +2277,15 @@ impl Clone for OpenThread<'_> {
 /// thus avoiding expensive mem-moves.
 ///
 /// Can also be statically-allocated.
-pub struct OtResources {
+///
+/// `CHANGE_SUBSCRIBERS` is the maximum number of [`ChangeSubscriber`]s which can exist at the same time.
+pub struct OtResources<const CHANGE_SUBSCRIBERS: usize = 4> {
     /// The radio resources.
     radio_resources: MaybeUninit<RadioResources>,
     /// The dataset resources.
     dataset_resources: MaybeUninit<DatasetResources>,
+    /// The slots of up to `CHANGE_SUBSCRIBERS` change subscribers.
+    change_subscribers: MaybeUninit<[changes::ChangeSubscriberSlot; CHANGE_SUBSCRIBERS]>,
     /// The OpenThread state.
     ///
     /// This state borrows the radio and dataset resources thus
@@ -1850,12 +2296,13 @@ pub struct OtResources {
     state: MaybeUninit<RefCell<OtState<'static>>>,
 }
 
-impl OtResources {
+impl<const CHANGE_SUBSCRIBERS: usize> OtResources<CHANGE_SUBSCRIBERS> {
     /// Create a new `OtResources` instance.
     pub const fn new() -> Self {
         Self {
             radio_resources: MaybeUninit::uninit(),
             dataset_resources: MaybeUninit::uninit(),
+            change_subscribers: MaybeUninit::uninit(),
             state: MaybeUninit::uninit(),
         }
     }
@@ -1886,6 +2333,16 @@ impl OtResources {
 
         radio_resources.init();
 
+        let change_subscribers = self
+            .change_subscribers
+            .write([const { changes::ChangeSubscriberSlot::new() }; CHANGE_SUBSCRIBERS]);
+        let change_subscribers = unsafe {
+            core::mem::transmute::<
+                &mut [changes::ChangeSubscriberSlot],
+                &'static mut [changes::ChangeSubscriberSlot],
+            >(change_subscribers)
+        };
+
         self.state.write(RefCell::new(OtState {
             ieee_eui64,
             rng,
@@ -1897,6 +2354,16 @@ impl OtResources {
             detach_done: Signal::new(),
             #[cfg(feature = "joiner")]
             join_done: Signal::new(),
+            #[cfg(feature = "commissioner")]
+            commissioner_state: Signal::new(),
+            #[cfg(feature = "commissioner")]
+            commissioner_joiner_events: heapless::Deque::new(),
+            #[cfg(feature = "commissioner")]
+            commissioner_joiner_event: Signal::new(),
+            #[cfg(feature = "commissioner")]
+            commissioner_energy_report: Signal::new(),
+            #[cfg(feature = "commissioner")]
+            commissioner_pan_id_conflict: Signal::new(),
             #[cfg(feature = "ping-sender")]
             ping_callback: None,
             #[cfg(feature = "ping-sender")]
@@ -1905,6 +2372,48 @@ impl OtResources {
             dns_callback: None,
             #[cfg(feature = "dns-client")]
             dns_done: Signal::new(),
+            #[cfg(feature = "dnssd-server")]
+            dnssd_proxy_events: heapless::Deque::new(),
+            #[cfg(feature = "dnssd-server")]
+            dnssd_proxy_event: Signal::new(),
+            #[cfg(feature = "dnssd-server")]
+            dnssd_receiver: crate::sys::otUdpReceiver::default(),
+            #[cfg(feature = "dnssd-server")]
+            dnssd_stopped: false,
+            #[cfg(feature = "srp-server")]
+            srp_server_updates: heapless::Deque::new(),
+            #[cfg(feature = "srp-server")]
+            srp_server_update: Signal::new(),
+            #[cfg(feature = "mdns")]
+            mdns: mdns::MdnsState::new(),
+            #[cfg(feature = "border-routing")]
+            infra_if: border_routing::InfraIfState::new(),
+            #[cfg(feature = "netdata-publisher")]
+            netdata_publisher_events: heapless::Deque::new(),
+            #[cfg(feature = "netdata-publisher")]
+            netdata_publisher_event: Signal::new(),
+            #[cfg(feature = "netdata-publisher")]
+            netdata_publisher_changed: Signal::new(),
+            #[cfg(feature = "slaac")]
+            slaac_prefix_filter: None,
+            #[cfg(feature = "nat64")]
+            nat64_rx_enabled: false,
+            #[cfg(feature = "nat64")]
+            nat64_rx: Signal::new(),
+            #[cfg(feature = "link-metrics-initiator")]
+            link_metrics_initiator: link_metrics::LinkMetricsInitiatorState::new(),
+            #[cfg(feature = "link-metrics-subject")]
+            link_metrics_subject_changed: Signal::new(),
+            #[cfg(feature = "jam-detection")]
+            jam_detection_state: Signal::new(),
+            #[cfg(feature = "netdiag-client")]
+            netdiag_callback: None,
+            #[cfg(feature = "netdiag-client")]
+            netdiag_done: Signal::new(),
+            #[cfg(feature = "netdiag-client")]
+            netdiag_dest: None,
+            #[cfg(feature = "mesh-diag")]
+            mesh_diag: mesh_diag::MeshDiagState::new(),
             radio_resources,
             dataset_resources,
             instance: core::ptr::null_mut(),
@@ -1913,14 +2422,23 @@ impl OtResources {
             alarm: Signal::new(),
             tasklets: Signal::new(),
             changes: Signal::new(),
+            changed_flags: ChangedFlags::empty(),
+            changed_flags_signal: Signal::new(),
+            change_subscribers,
+            timed_unicast_addrs: heapless::Vec::new(),
+            timed_unicast_addrs_changed: Signal::new(),
             radio_conf: Config::new(),
             radio_conf_changed: Signal::new(),
             radio_conf_src_match: radio::SrcMatchConfig::default(),
             radio_conf_src_match_changed: Signal::new(),
+            radio_conf_enh_ack_probing: radio::EnhAckProbingConfig::new(),
+            radio_conf_enh_ack_probing_changed: Signal::new(),
+            radio_enh_ack_probing: false,
             radio_cmd: Signal::new(),
             radio_enabled: false,
             radio_receive_channel: None,
-            last_rssi: OT_RADIO_RSSI_INVALID as i8,
+            radio_rssi: OT_RADIO_RSSI_INVALID as i8,
+            radio_rssi_requested: Signal::new(),
             // The *initial* radio capabilities, before the actual radio is
             // brought up by `run_radio` and reports its real set.
             //
@@ -1950,7 +2468,7 @@ impl OtResources {
     }
 }
 
-impl Default for OtResources {
+impl<const CHANGE_SUBSCRIBERS: usize> Default for OtResources<CHANGE_SUBSCRIBERS> {
     fn default() -> Self {
         Self::new()
     }
@@ -2308,6 +2826,160 @@ impl From<&otIpCounters> for IpCounters {
     }
 }
 
+/// The origin of an IPv6 address assigned to the Thread interface.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Ipv6AddrOrigin {
+    /// The address was assigned by Thread (e.g. the ML-EID, the RLOC or an ALOC).
+    Thread,
+    /// The address was assigned by SLAAC.
+    Slaac,
+    /// The address was assigned by DHCPv6.
+    Dhcp6,
+    /// The address was assigned manually by the application.
+    Manual,
+    /// Some other origin.
+    Other(u8),
+}
+
+impl From<u8> for Ipv6AddrOrigin {
+    fn from(value: u8) -> Self {
+        match value as u32 {
+            OT_ADDRESS_ORIGIN_THREAD => Self::Thread,
+            OT_ADDRESS_ORIGIN_SLAAC => Self::Slaac,
+            OT_ADDRESS_ORIGIN_DHCPV6 => Self::Dhcp6,
+            OT_ADDRESS_ORIGIN_MANUAL => Self::Manual,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// An IPv6 address assigned to the Thread interface, as reported by [`OpenThread::ipv6_addrs_info`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ipv6AddrInfo {
+    /// The IPv6 address
+    pub addr: Ipv6Addr,
+    /// The prefix length of the address
+    pub prefix_len: u8,
+    /// The origin of the address
+    pub origin: Ipv6AddrOrigin,
+    /// Whether the address is preferred
+    pub preferred: bool,
+    /// Whether the address is valid
+    pub valid: bool,
+    /// The IPv6 scope of the address (as per RFC 4291, e.g. 2 for link-local and 14 for global)
+    pub scope: u8,
+    /// Whether the address is an RLOC
+    pub rloc: bool,
+    /// Whether the address is mesh-local
+    pub mesh_local: bool,
+}
+
+impl Ipv6AddrInfo {
+    /// The link-local IPv6 scope
+    const SCOPE_LINK_LOCAL: u8 = 2;
+    /// The global IPv6 scope
+    const SCOPE_GLOBAL: u8 = 14;
+
+    fn from_ot(addr: &otNetifAddress) -> Self {
+        let ip: Ipv6Addr = unsafe { addr.mAddress.mFields.m8 }.into();
+
+        let scope = if addr.mScopeOverrideValid() {
+            addr.mScopeOverride() as u8
+        } else if ip.is_unicast_link_local() {
+            Self::SCOPE_LINK_LOCAL
+        } else if ip.is_loopback() {
+            0
+        } else {
+            Self::SCOPE_GLOBAL
+        };
+
+        Self {
+            addr: ip,
+            prefix_len: addr.mPrefixLength,
+            origin: addr.mAddressOrigin.into(),
+            preferred: addr.mPreferred(),
+            valid: addr.mValid(),
+            scope,
+            rloc: addr.mRloc(),
+            mesh_local: addr.mMeshLocal(),
+        }
+    }
+}
+
+/// The maximum number of static IPv6 unicast addresses with a finite preferred or valid lifetime
+/// (see [`Ipv6UnicastAddrConfig`]) which can be added at the same time.
+pub const IPV6_MAX_TIMED_UNICAST_ADDRS: usize = 4;
+
+/// A static IPv6 unicast address to be added to the Thread interface with [`OpenThread::add_unicast_addr`].
+///
+/// OpenThread itself only tracks whether an address is preferred and valid; the lifetimes of the
+/// address are tracked by [`OpenThread::run`], which deprecates the address once its preferred
+/// lifetime runs out and removes it once its valid lifetime runs out.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ipv6UnicastAddrConfig {
+    /// The IPv6 address
+    pub addr: Ipv6Addr,
+    /// The prefix length of the address
+    pub prefix_len: u8,
+    /// The preferred lifetime of the address, or `None` for an infinite lifetime.
+    ///
+    /// Only preferred addresses are picked as source addresses.
+    /// A zero lifetime adds the address as deprecated (i.e. not preferred).
+    pub preferred_lifetime: Option<embassy_time::Duration>,
+    /// The valid lifetime of the address, or `None` for an infinite lifetime.
+    ///
+    /// Must not be shorter than the preferred lifetime.
+    pub valid_lifetime: Option<embassy_time::Duration>,
+    /// Whether the address is mesh-local
+    pub mesh_local: bool,
+}
+
+impl Ipv6UnicastAddrConfig {
+    /// Create a new non mesh-local address configuration with infinite lifetimes.
+    pub const fn new(addr: Ipv6Addr, prefix_len: u8) -> Self {
+        Self {
+            addr,
+            prefix_len,
+            preferred_lifetime: None,
+            valid_lifetime: None,
+            mesh_local: false,
+        }
+    }
+
+    fn to_ot(self, preferred: bool) -> otNetifAddress {
+        let mut addr = otNetifAddress {
+            mAddress: otIp6Address {
+                mFields: sys::otIp6Address__bindgen_ty_1 {
+                    m8: self.addr.octets(),
+                },
+            },
+            mPrefixLength: self.prefix_len,
+            mAddressOrigin: OT_ADDRESS_ORIGIN_MANUAL as _,
+            ..Default::default()
+        };
+
+        addr.set_mPreferred(preferred);
+        addr.set_mValid(true);
+        addr.set_mMeshLocal(self.mesh_local);
+
+        addr
+    }
+}
+
+/// A static IPv6 unicast address with a finite preferred or valid lifetime, tracked by `OpenThread::run`.
+struct TimedUnicastAddr {
+    /// The configuration the address was added with
+    config: Ipv6UnicastAddrConfig,
+    /// When the address is to be deprecated, or `None` if it is already deprecated
+    /// or its preferred lifetime is infinite
+    deprecate_at: Option<Instant>,
+    /// When the address is to be removed, or `None` if its valid lifetime is infinite
+    remove_at: Option<Instant>,
+}
+
 /// The device role in the OpenThread network.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -2360,6 +3032,12 @@ struct OtActiveState<'a> {
     /// The activated `OtSrpState` instance.
     #[cfg(feature = "srp-client")]
     srp: Option<RefMut<'a, OtSrpState<'a>>>,
+    /// The activated `OtCoapState` instance.
+    #[cfg(feature = "coap")]
+    coap: Option<RefMut<'a, OtCoapState<'a>>>,
+    /// The activated `OtTcpState` instance.
+    #[cfg(feature = "tcp")]
+    tcp: Option<RefMut<'a, OtTcpState<'a>>>,
 }
 
 #[allow(clippy::needless_lifetimes)]
@@ -2390,6 +3068,34 @@ impl<'a> OtActiveState<'a> {
 
         Ok(srp)
     }
+
+    /// A utility to get a reference to the CoAP state
+    ///
+    /// This method will return an error if the `OpenThread` instance was not
+    /// initialized with CoAP resources.
+    #[cfg(feature = "coap")]
+    pub(crate) fn coap(&mut self) -> Result<&mut OtCoapState<'a>, OtError> {
+        let coap = self
+            .coap
+            .as_mut()
+            .ok_or(OtError::new(crate::sys::otError_OT_ERROR_FAILED))?;
+
+        Ok(coap)
+    }
+
+    /// A utility to get a reference to the TCP state
+    ///
+    /// This method will return an error if the `OpenThread` instance was not
+    /// initialized with TCP resources.
+    #[cfg(feature = "tcp")]
+    pub(crate) fn tcp(&mut self) -> Result<&mut OtTcpState<'a>, OtError> {
+        let tcp = self
+            .tcp
+            .as_mut()
+            .ok_or(OtError::new(crate::sys::otError_OT_ERROR_FAILED))?;
+
+        Ok(tcp)
+    }
 }
 
 // A hack so that we can store `OtActiveState` in the global `OT_ACTIVE_STATE` variable
@@ -2437,6 +3143,10 @@ impl<'a> OtContext<'a> {
             udp: ot.udp_state.map(|u| u.borrow_mut()),
             #[cfg(feature = "srp-client")]
             srp: ot.srp_state.map(|s| s.borrow_mut()),
+            #[cfg(feature = "coap")]
+            coap: ot.coap_state.map(|c| c.borrow_mut()),
+            #[cfg(feature = "tcp")]
+            tcp: ot.tcp_state.map(|t| t.borrow_mut()),
         };
 
         // Needed so that we convert from the fake `'static` lifetime in `OT_ACTIVE_STATE` to the actual `'a` lifetime of `ot`
@@ -2637,6 +3347,8 @@ impl<'a> OtContext<'a> {
     fn plat_changed(&mut self, flags: u32) {
         trace!("Plat changed callback, flags: {:#x}", flags);
 
+        self.plat_changed_flags(flags);
+
         let state = self.state();
 
         state.ot.changes.signal(());
@@ -2701,7 +3413,13 @@ impl<'a> OtContext<'a> {
     }
 
     fn plat_radio_get_rssi(&mut self) -> i8 {
-        let rssi = self.state().ot.last_rssi;
+        let state = self.state();
+
+        // The reading is taken asynchronously by the radio runner (see `run_radio_rx`),
+        // so answer with the one sampled on the previous call, and request the next one
+        let rssi = core::mem::replace(&mut state.ot.radio_rssi, OT_RADIO_RSSI_INVALID as i8);
+        state.ot.radio_rssi_requested.signal(());
+
         trace!("Plat radio get RSSI callback, RSSI: {}", rssi);
 
         rssi
@@ -3237,6 +3955,22 @@ struct OtState<'a> {
     /// back to the awaiting future (signaled from the joiner C callback).
     #[cfg(feature = "joiner")]
     join_done: Signal<otError>,
+    /// Signaled on each Commissioner state change (see `commissioner.rs`).
+    #[cfg(feature = "commissioner")]
+    commissioner_state: Signal<()>,
+    /// Commissioner joiner events waiting to be picked up by the application.
+    #[cfg(feature = "commissioner")]
+    commissioner_joiner_events:
+        heapless::Deque<CommissionerJoinerEvent, COMMISSIONER_MAX_PENDING_JOINER_EVENTS>,
+    /// Signaled when a new joiner event is queued in `commissioner_joiner_events`.
+    #[cfg(feature = "commissioner")]
+    commissioner_joiner_event: Signal<()>,
+    /// Carries the energy report of an in-flight Commissioner energy scan.
+    #[cfg(feature = "commissioner")]
+    commissioner_energy_report: Signal<CommissionerEnergyReport>,
+    /// Carries the conflict report of an in-flight Commissioner PAN ID query.
+    #[cfg(feature = "commissioner")]
+    commissioner_pan_id_conflict: Signal<CommissionerPanIdConflict>,
     /// The callback to invoke for each received ping reply. Holds a
     /// lifetime-erased reference to the user closure for the duration of the
     /// in-flight ping (cleared when the ping completes). See `ping.rs`.
@@ -3257,6 +3991,81 @@ struct OtState<'a> {
     /// awaiting future (signaled from the DNS response C callback).
     #[cfg(feature = "dns-client")]
     dns_done: Signal<crate::sys::otError>,
+    /// Discovery proxy events waiting to be picked up by the application (see `dnssd.rs`).
+    #[cfg(feature = "dnssd-server")]
+    dnssd_proxy_events:
+        heapless::Deque<dnssd::DnssdPendingProxyEvent, DNSSD_MAX_PENDING_PROXY_EVENTS>,
+    /// Signaled when a new discovery proxy event is queued in `dnssd_proxy_events`.
+    #[cfg(feature = "dnssd-server")]
+    dnssd_proxy_event: Signal<()>,
+    /// The UDP receiver dropping the DNS queries while the DNS-SD server is stopped
+    /// (see `dnssd.rs`). Registered with OpenThread only while `dnssd_stopped` is set.
+    #[cfg(feature = "dnssd-server")]
+    dnssd_receiver: crate::sys::otUdpReceiver,
+    /// Whether the DNS-SD server was stopped with `OpenThread::dnssd_server_stop`.
+    #[cfg(feature = "dnssd-server")]
+    dnssd_stopped: bool,
+    /// SRP updates waiting to be vetted by the application (see `srp_server.rs`).
+    #[cfg(feature = "srp-server")]
+    srp_server_updates:
+        heapless::Deque<srp_server::SrpServerPendingUpdate, SRP_SERVER_MAX_PENDING_UPDATES>,
+    /// Signaled when a new SRP update is queued in `srp_server_updates`.
+    #[cfg(feature = "srp-server")]
+    srp_server_update: Signal<()>,
+    /// The state of the mDNS module bridge: socket I/O queues, in-flight
+    /// registration and queries (see `mdns.rs`).
+    #[cfg(feature = "mdns")]
+    mdns: mdns::MdnsState<'a>,
+    /// The state of the infrastructure network interface bridge of the Routing Manager:
+    /// interface addresses and outgoing ICMPv6 ND messages (see `border_routing.rs`).
+    #[cfg(feature = "border-routing")]
+    infra_if: border_routing::InfraIfState,
+    /// Network Data publisher events waiting to be picked up by the application
+    /// (see `netdata_publisher.rs`).
+    #[cfg(feature = "netdata-publisher")]
+    netdata_publisher_events:
+        heapless::Deque<NetDataPublisherEvent, NETDATA_PUBLISHER_MAX_PENDING_EVENTS>,
+    /// Signaled when a new publisher event is queued in `netdata_publisher_events`.
+    #[cfg(feature = "netdata-publisher")]
+    netdata_publisher_event: Signal<()>,
+    /// Signaled on each publisher event, for the `netdata_wait_*_added` methods.
+    #[cfg(feature = "netdata-publisher")]
+    netdata_publisher_changed: Signal<()>,
+    /// The SLAAC prefix filter installed by `slaac_set_prefix_filter`, if any.
+    #[cfg(feature = "slaac")]
+    slaac_prefix_filter: Option<fn(Ipv6Addr, u8) -> bool>,
+    /// Whether to egress the IPv4 packets translated by the NAT64 translator
+    /// (see `rx_ipv6_enabled` below).
+    #[cfg(feature = "nat64")]
+    nat64_rx_enabled: bool,
+    /// An IPv4 packet translated by the NAT64 translator and waiting to be ingressed somewhere else
+    #[cfg(feature = "nat64")]
+    nat64_rx: Signal<*mut otMessage>,
+    /// The pending Link Metrics reports and responses (see `link_metrics.rs`).
+    #[cfg(feature = "link-metrics-initiator")]
+    link_metrics_initiator: link_metrics::LinkMetricsInitiatorState,
+    /// Raised whenever the Enhanced-ACK Based Probing table changes (see `link_metrics.rs`).
+    #[cfg(feature = "link-metrics-subject")]
+    link_metrics_subject_changed: Signal<()>,
+    /// The latest jamming state transition reported by the jam detector (`true` = jammed).
+    #[cfg(feature = "jam-detection")]
+    jam_detection_state: Signal<bool>,
+    /// The callback to invoke for each received `DIAG_GET` response. Holds a
+    /// lifetime-erased reference to the user closure for the duration of the
+    /// in-flight request. See `netdiag.rs`.
+    #[cfg(feature = "netdiag-client")]
+    #[allow(clippy::type_complexity)]
+    netdiag_callback: Option<&'a mut dyn FnMut(&NetDiagResponse)>,
+    /// Carries the `otError` of each `DIAG_GET` response back to the awaiting future.
+    #[cfg(feature = "netdiag-client")]
+    netdiag_done: Signal<crate::sys::otError>,
+    /// The destination of the in-flight unicast `DIAG_GET` request, if any;
+    /// the responses coming from other nodes are ignored. See `netdiag.rs`.
+    #[cfg(feature = "netdiag-client")]
+    netdiag_dest: Option<Ipv6Addr>,
+    /// The in-flight Mesh Diagnostics query, if any (see `mesh_diag.rs`).
+    #[cfg(feature = "mesh-diag")]
+    mesh_diag: mesh_diag::MeshDiagState<'a>,
     /// Whether to egress IPv6 packets from OpenThread
     /// If not necessary, this should be disabled, because otherwise the signal below
     /// will be filled with a packet that is not consumed, and the packets of OpenThread
@@ -3271,6 +4080,16 @@ struct OtState<'a> {
     tasklets: Signal<()>,
     /// The OpenThread state has changed. Set by the OpenThread C library via the `otPlatStateChanged` callback
     changes: Signal<()>,
+    /// The OpenThread state changes accumulated since the last `wait_changed_flags` call
+    changed_flags: ChangedFlags,
+    /// Signaled when `changed_flags` is updated
+    changed_flags_signal: Signal<()>,
+    /// The slots of the `ChangeSubscriber`s
+    change_subscribers: &'a mut [changes::ChangeSubscriberSlot],
+    /// The static unicast addresses with a finite lifetime, deprecated and removed by `run_addr_lifetimes`
+    timed_unicast_addrs: heapless::Vec<TimedUnicastAddr, IPV6_MAX_TIMED_UNICAST_ADDRS>,
+    /// Signaled when `timed_unicast_addrs` is updated
+    timed_unicast_addrs_changed: Signal<()>,
     /// The latest radio configuration from the POV of OpenThread
     radio_conf: radio::Config,
     /// Raised whenever a standing radio-configuration policy changes; consumed by the radio runner.
@@ -3279,6 +4098,13 @@ struct OtState<'a> {
     radio_conf_src_match: radio::SrcMatchConfig,
     /// Raised whenever the source-address-match table changes; consumed by the radio runner.
     radio_conf_src_match_changed: Signal<()>,
+    /// The Enhanced-ACK Based Probing table (`otPlatRadioConfigureEnhAckProbing`).
+    radio_conf_enh_ack_probing: radio::EnhAckProbingConfig,
+    /// Raised whenever the Enhanced-ACK Based Probing table changes; consumed by the radio runner.
+    radio_conf_enh_ack_probing_changed: Signal<()>,
+    /// Whether the radio reports `MacCapabilities::ENH_ACK_PROBING`.
+    /// Fetched with the capabilities.
+    radio_enh_ack_probing: bool,
     /// Raised whenever the radio needs to execute the provided command.
     radio_cmd: Signal<RadioCommand>,
     /// Whether the radio is enabled (`otPlatRadioEnable`/`Disable`).
@@ -3287,9 +4113,11 @@ struct OtState<'a> {
     radio_enabled: bool,
     /// The channel the radio is commanded to receive on, or `None` if the radio is not commanded to receive.
     radio_receive_channel: Option<u8>,
-    /// The RSSI of the most recently received frame (ACKs included).
-    /// Used to answer `otPlatRadioGetRssi` which is synchronous.
-    last_rssi: i8,
+    /// The instantaneous RSSI sampled by the radio runner on the previous `otPlatRadioGetRssi` call,
+    /// which is synchronous; invalid once consumed.
+    radio_rssi: i8,
+    /// Raised by `otPlatRadioGetRssi` so that the radio runner samples the next RSSI reading.
+    radio_rssi_requested: Signal<()>,
     /// Radio capabilities reported to OpenThread via otPlatRadioGetCaps.
     /// Fetched from the actual radio trait in the `OpenThread::run` API.
     radio_caps: otRadioCaps,
@@ -3418,6 +4246,45 @@ fn to_sock_addr(addr: &otIp6Address, port: u16, netif: u32) -> SocketAddrV6 {
     SocketAddrV6::new(Ipv6Addr::from(unsafe { addr.mFields.m8 }), port, 0, netif)
 }
 
+/// Convert an `Ipv6Addr` to an `otIp6Address`.
+#[allow(unused)]
+fn to_ot_ip6_addr(addr: Ipv6Addr) -> otIp6Address {
+    otIp6Address {
+        mFields: sys::otIp6Address__bindgen_ty_1 { m8: addr.octets() },
+    }
+}
+
+/// Convert a NUL-terminated C string coming from OpenThread to a `&str`.
+///
+/// A null pointer is converted to an empty string. Fails with `OT_ERROR_INVALID_ARGS`
+/// if the string is not valid UTF-8 (e.g. a name received from a remote peer).
+#[allow(unused)]
+fn c_str<'a>(ptr: *const core::ffi::c_char) -> Result<&'a str, OtError> {
+    if ptr.is_null() {
+        Ok("")
+    } else {
+        unsafe { core::ffi::CStr::from_ptr(ptr) }
+            .to_str()
+            .map_err(|_| OtError::new(sys::otError_OT_ERROR_INVALID_ARGS))
+    }
+}
+
+/// Copy `str` into `buf` as a NUL-terminated C string and return a pointer to it.
+///
+/// Fails with `OT_ERROR_INVALID_ARGS` if `str` contains a NUL byte,
+/// or if `buf` is too small to hold `str` and its NUL terminator.
+#[allow(unused)]
+fn store_str(str: &str, buf: &mut [u8]) -> Result<*const core::ffi::c_char, OtError> {
+    if str.len() >= buf.len() || str.as_bytes().contains(&0) {
+        Err(OtError::new(sys::otError_OT_ERROR_INVALID_ARGS))?;
+    }
+
+    buf[..str.len()].copy_from_slice(str.as_bytes());
+    buf[str.len()] = 0;
+
+    Ok(buf.as_ptr().cast())
+}
+
 /// Convert a `SocketAddrV6` to an `otSockAddr`.
 ///
 /// Always compiled (it only uses unconditionally-available `sys` types) rather
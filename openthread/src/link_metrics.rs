@@ -0,0 +1,681 @@
+//! Link Metrics API (Thread 1.2; the `link-metrics-initiator` and `link-metrics-subject` features).
+//!
+//! As an Initiator (`link-metrics-initiator`), the device queries the Link Metrics of a neighbor (the Subject):
+//! - With Single Probe queries ([`OpenThread::link_metrics_query`] with series ID 0)
+//! - With Forward Tracking Series, configured on the Subject with
+//!   [`OpenThread::link_metrics_config_forward_tracking_series`], fed with
+//!   [`OpenThread::link_metrics_send_link_probe`] and queried with [`OpenThread::link_metrics_query`]
+//! - With Enhanced-ACK Based Probing, configured on the Subject with
+//!   [`OpenThread::link_metrics_config_enh_ack_probing`], which reports the metrics in the Enhanced ACKs
+//!   of the Subject ([`OpenThread::link_metrics_wait_enh_ack_report`])
+//!
+//! As a Subject (`link-metrics-subject`), OpenThread itself answers the queries of the Initiators and
+//! tracks their Forward Tracking Series. The Initiators which have configured Enhanced-ACK Based Probing
+//! with this device are handed to the radio ([`Radio::set_enh_ack_probing_config`](crate::Radio::set_enh_ack_probing_config)),
+//! which adds the Link Metrics IE to the Enhanced ACKs sent to them - [`MacRadio`](crate::MacRadio) does so in software.
+//! The Initiators are reported by [`OpenThread::link_metrics_subject_initiators`] and
+//! [`OpenThread::link_metrics_subject_wait_changed`].
+//!
+//! The radio has to report [`MacCapabilities::ENH_ACK_PROBING`](crate::MacCapabilities::ENH_ACK_PROBING);
+//! otherwise the Initiators trying to configure Enhanced-ACK Based Probing are answered with an error.
+
+#[cfg(feature = "link-metrics-initiator")]
+use core::ffi::c_void;
+use core::future::poll_fn;
+#[cfg(feature = "link-metrics-initiator")]
+use core::net::Ipv6Addr;
+#[cfg(feature = "link-metrics-initiator")]
+use core::task::Poll;
+
+use crate::fmt::bitflags;
+#[cfg(feature = "link-metrics-subject")]
+use crate::radio::EnhAckProbingInitiator;
+#[cfg(feature = "link-metrics-initiator")]
+use crate::signal::Signal;
+use crate::sys::otLinkMetrics;
+#[cfg(feature = "link-metrics-subject")]
+use crate::sys::{
+    otError, otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_CAPABLE, otError_OT_ERROR_NOT_FOUND,
+    otError_OT_ERROR_NO_BUFS,
+};
+#[cfg(feature = "link-metrics-initiator")]
+use crate::sys::{
+    otExtAddress, otInstance, otIp6Address, otLinkMetricsConfigEnhAckProbing,
+    otLinkMetricsConfigForwardTrackingSeries,
+    otLinkMetricsEnhAckFlags_OT_LINK_METRICS_ENH_ACK_CLEAR,
+    otLinkMetricsEnhAckFlags_OT_LINK_METRICS_ENH_ACK_REGISTER, otLinkMetricsQuery,
+    otLinkMetricsSendLinkProbe, otLinkMetricsSeriesFlags, otLinkMetricsStatus,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_CANNOT_SUPPORT_NEW_SERIES,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_NO_MATCHING_FRAMES_RECEIVED,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_OTHER_ERROR,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SERIESID_ALREADY_REGISTERED,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SERIESID_NOT_RECOGNIZED,
+    otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SUCCESS, otLinkMetricsValues, otShortAddress,
+};
+#[cfg(feature = "link-metrics-initiator")]
+use crate::{ot, to_ot_ip6_addr, OtError};
+use crate::{OpenThread, OtContext};
+
+/// The maximum number of Enhanced-ACK Based Probing reports (see [`OpenThread::link_metrics_wait_enh_ack_report`])
+/// which can be queued, waiting to be picked up by the application.
+///
+/// Reports arriving while the queue is full are dropped.
+#[cfg(feature = "link-metrics-initiator")]
+pub const LINK_METRICS_MAX_PENDING_ENH_ACK_REPORTS: usize = 4;
+
+bitflags! {
+    /// A set of Link Metrics - a mirror of the C `otLinkMetrics` flags.
+    #[repr(transparent)]
+    #[derive(Default)]
+    #[cfg_attr(not(feature = "defmt"), derive(Debug, Copy, Clone, Eq, PartialEq, Hash))]
+    pub struct LinkMetrics: u8 {
+        /// The count of the received PDUs (Layer 2 frames).
+        const PDU_COUNT = 0x01;
+        /// The Link Quality Indicator.
+        const LQI = 0x02;
+        /// The link margin (in dB).
+        const LINK_MARGIN = 0x04;
+        /// The RSSI (in dBm).
+        const RSSI = 0x08;
+    }
+}
+
+impl LinkMetrics {
+    #[cfg(feature = "link-metrics-initiator")]
+    fn to_ot(self) -> otLinkMetrics {
+        let mut metrics = otLinkMetrics::default();
+
+        metrics.set_mPduCount(self.contains(Self::PDU_COUNT));
+        metrics.set_mLqi(self.contains(Self::LQI));
+        metrics.set_mLinkMargin(self.contains(Self::LINK_MARGIN));
+        metrics.set_mRssi(self.contains(Self::RSSI));
+
+        metrics
+    }
+
+    fn from_ot(metrics: &otLinkMetrics) -> Self {
+        let mut this = Self::empty();
+
+        this.set(Self::PDU_COUNT, metrics.mPduCount());
+        this.set(Self::LQI, metrics.mLqi());
+        this.set(Self::LINK_MARGIN, metrics.mLinkMargin());
+        this.set(Self::RSSI, metrics.mRssi());
+
+        this
+    }
+}
+
+#[cfg(feature = "link-metrics-initiator")]
+bitflags! {
+    /// The frames accounted in a Forward Tracking Series - a mirror of the C `otLinkMetricsSeriesFlags` flags.
+    #[repr(transparent)]
+    #[derive(Default)]
+    #[cfg_attr(not(feature = "defmt"), derive(Debug, Copy, Clone, Eq, PartialEq, Hash))]
+    pub struct LinkMetricsSeriesFlags: u8 {
+        /// MLE Link Probe frames.
+        const LINK_PROBE = 0x01;
+        /// MAC Data frames.
+        const MAC_DATA = 0x02;
+        /// MAC Data Request frames.
+        const MAC_DATA_REQUEST = 0x04;
+        /// MAC ACK frames.
+        const MAC_ACK = 0x08;
+    }
+}
+
+#[cfg(feature = "link-metrics-initiator")]
+impl LinkMetricsSeriesFlags {
+    fn to_ot(self) -> otLinkMetricsSeriesFlags {
+        let mut flags = otLinkMetricsSeriesFlags::default();
+
+        flags.set_mLinkProbe(self.contains(Self::LINK_PROBE));
+        flags.set_mMacData(self.contains(Self::MAC_DATA));
+        flags.set_mMacDataRequest(self.contains(Self::MAC_DATA_REQUEST));
+        flags.set_mMacAck(self.contains(Self::MAC_ACK));
+
+        flags
+    }
+}
+
+/// Link Metrics values, as reported by a Subject.
+///
+/// Only the values of the queried metrics are present.
+#[cfg(feature = "link-metrics-initiator")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkMetricsValues {
+    /// The count of the received PDUs
+    pub pdu_count: Option<u32>,
+    /// The Link Quality Indicator
+    pub lqi: Option<u8>,
+    /// The link margin (in dB)
+    pub link_margin: Option<u8>,
+    /// The RSSI (in dBm)
+    pub rssi: Option<i8>,
+}
+
+#[cfg(feature = "link-metrics-initiator")]
+impl LinkMetricsValues {
+    fn from_ot(values: &otLinkMetricsValues) -> Self {
+        let metrics = LinkMetrics::from_ot(&values.mMetrics);
+
+        Self {
+            pdu_count: metrics
+                .contains(LinkMetrics::PDU_COUNT)
+                .then_some(values.mPduCountValue),
+            lqi: metrics
+                .contains(LinkMetrics::LQI)
+                .then_some(values.mLqiValue),
+            link_margin: metrics
+                .contains(LinkMetrics::LINK_MARGIN)
+                .then_some(values.mLinkMarginValue),
+            rssi: metrics
+                .contains(LinkMetrics::RSSI)
+                .then_some(values.mRssiValue),
+        }
+    }
+}
+
+/// The status of a Link Metrics report or Management Response.
+#[cfg(feature = "link-metrics-initiator")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinkMetricsStatus {
+    /// Success
+    Success,
+    /// The Subject cannot support a new series
+    CannotSupportNewSeries,
+    /// The series ID is already registered with the Subject
+    SeriesIdAlreadyRegistered,
+    /// The series ID is not recognized by the Subject
+    SeriesIdNotRecognized,
+    /// No frames matching the series were received by the Subject
+    NoMatchingFramesReceived,
+    /// Some other error
+    OtherError,
+    /// Some other status
+    Other(otLinkMetricsStatus),
+}
+
+#[cfg(feature = "link-metrics-initiator")]
+impl From<otLinkMetricsStatus> for LinkMetricsStatus {
+    #[allow(non_upper_case_globals)]
+    fn from(value: otLinkMetricsStatus) -> Self {
+        match value {
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SUCCESS => Self::Success,
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_CANNOT_SUPPORT_NEW_SERIES => {
+                Self::CannotSupportNewSeries
+            }
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SERIESID_ALREADY_REGISTERED => {
+                Self::SeriesIdAlreadyRegistered
+            }
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_SERIESID_NOT_RECOGNIZED => {
+                Self::SeriesIdNotRecognized
+            }
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_NO_MATCHING_FRAMES_RECEIVED => {
+                Self::NoMatchingFramesReceived
+            }
+            otLinkMetricsStatus_OT_LINK_METRICS_STATUS_OTHER_ERROR => Self::OtherError,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A Link Metrics report, as returned by [`OpenThread::link_metrics_query`].
+#[cfg(feature = "link-metrics-initiator")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkMetricsReport {
+    /// The address of the Subject
+    pub source: Ipv6Addr,
+    /// The reported values, or `None` if the Subject reported an error `status`
+    pub values: Option<LinkMetricsValues>,
+    /// The status of the report
+    pub status: LinkMetricsStatus,
+}
+
+/// A Link Metrics report received in an Enhanced ACK of a Subject, as returned by
+/// [`OpenThread::link_metrics_wait_enh_ack_report`].
+#[cfg(feature = "link-metrics-initiator")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkMetricsEnhAckReport {
+    /// The short address of the Subject
+    pub short_address: u16,
+    /// The extended address of the Subject
+    pub ext_address: u64,
+    /// The reported values
+    pub values: LinkMetricsValues,
+}
+
+/// An Initiator which has configured Enhanced-ACK Based Probing with this device,
+/// as reported by [`OpenThread::link_metrics_subject_initiators`].
+#[cfg(feature = "link-metrics-subject")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkMetricsInitiator {
+    /// The short address of the Initiator
+    pub short_address: u16,
+    /// The extended address of the Initiator
+    pub ext_address: u64,
+    /// The metrics reported in the Enhanced ACKs to the Initiator
+    pub metrics: LinkMetrics,
+}
+
+#[cfg(feature = "link-metrics-subject")]
+impl LinkMetricsInitiator {
+    fn from_radio(initiator: &EnhAckProbingInitiator) -> Self {
+        let mut metrics = LinkMetrics::empty();
+
+        metrics.set(LinkMetrics::LQI, initiator.lqi);
+        metrics.set(LinkMetrics::LINK_MARGIN, initiator.link_margin);
+        metrics.set(LinkMetrics::RSSI, initiator.rssi);
+
+        Self {
+            short_address: initiator.short_addr,
+            ext_address: initiator.ext_addr,
+            metrics,
+        }
+    }
+}
+
+/// The Link Metrics Initiator state of `OtState`.
+#[cfg(feature = "link-metrics-initiator")]
+pub(crate) struct LinkMetricsInitiatorState {
+    /// The report of the last `link_metrics_query`.
+    report: Signal<LinkMetricsReport>,
+    /// The Management Response of the last `link_metrics_config_*` request.
+    mgmt_response: Signal<LinkMetricsStatus>,
+    /// Enhanced-ACK Based Probing reports waiting to be picked up by the application.
+    enh_ack_reports:
+        heapless::Deque<LinkMetricsEnhAckReport, LINK_METRICS_MAX_PENDING_ENH_ACK_REPORTS>,
+    /// Signaled when a new report is queued in `enh_ack_reports`.
+    enh_ack_report: Signal<()>,
+}
+
+#[cfg(feature = "link-metrics-initiator")]
+impl LinkMetricsInitiatorState {
+    pub(crate) const fn new() -> Self {
+        Self {
+            report: Signal::new(),
+            mgmt_response: Signal::new(),
+            enh_ack_reports: heapless::Deque::new(),
+            enh_ack_report: Signal::new(),
+        }
+    }
+}
+
+impl OpenThread<'_> {
+    /// Send an MLE Data Request querying the Link Metrics of a neighbor, and wait for its report.
+    ///
+    /// Only one query can be in progress at a time.
+    ///
+    /// Arguments:
+    /// - `dest`: The link-local address of the neighbor (the Subject)
+    /// - `series_id`: The ID of the Forward Tracking Series to query, or 0 for a Single Probe query
+    /// - `metrics`: The metrics to query (for a Single Probe query)
+    ///
+    /// NOTE: The returned future completes only if a report is received;
+    /// use a timeout to bound the wait.
+    #[cfg(feature = "link-metrics-initiator")]
+    pub async fn link_metrics_query(
+        &self,
+        dest: Ipv6Addr,
+        series_id: u8,
+        metrics: LinkMetrics,
+    ) -> Result<LinkMetricsReport, OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            // Clear any stale report left over from a prior query whose future was dropped
+            state.ot.link_metrics_initiator.report.reset();
+
+            ot!(unsafe {
+                otLinkMetricsQuery(
+                    state.ot.instance,
+                    &to_ot_ip6_addr(dest),
+                    series_id,
+                    &metrics.to_ot(),
+                    Some(OtContext::plat_c_link_metrics_report_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        Ok(poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .ot
+                .link_metrics_initiator
+                .report
+                .poll_wait(cx)
+        })
+        .await)
+    }
+
+    /// Send an MLE Link Metrics Management Request configuring (or clearing) a Forward Tracking Series
+    /// on a neighbor, and wait for its response.
+    ///
+    /// Only one Management Request can be in progress at a time.
+    ///
+    /// Arguments:
+    /// - `dest`: The link-local address of the neighbor (the Subject)
+    /// - `series_id`: The ID of the series, in the range [1, 254]
+    /// - `series_flags`: The frames to be accounted in the series; empty to clear the series
+    /// - `metrics`: The metrics to be tracked; ignored when clearing the series
+    ///
+    /// NOTE: The returned future completes only if a response is received;
+    /// use a timeout to bound the wait.
+    #[cfg(feature = "link-metrics-initiator")]
+    pub async fn link_metrics_config_forward_tracking_series(
+        &self,
+        dest: Ipv6Addr,
+        series_id: u8,
+        series_flags: LinkMetricsSeriesFlags,
+        metrics: LinkMetrics,
+    ) -> Result<LinkMetricsStatus, OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            // Clear any stale response left over from a prior request whose future was dropped
+            state.ot.link_metrics_initiator.mgmt_response.reset();
+
+            let metrics = metrics.to_ot();
+
+            ot!(unsafe {
+                otLinkMetricsConfigForwardTrackingSeries(
+                    state.ot.instance,
+                    &to_ot_ip6_addr(dest),
+                    series_id,
+                    series_flags.to_ot(),
+                    if series_flags.is_empty() {
+                        core::ptr::null()
+                    } else {
+                        &metrics
+                    },
+                    Some(OtContext::plat_c_link_metrics_mgmt_response_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        Ok(self.link_metrics_wait_mgmt_response().await)
+    }
+
+    /// Send an MLE Link Metrics Management Request registering (or clearing) Enhanced-ACK Based Probing
+    /// on a neighbor, and wait for its response.
+    ///
+    /// Once registered, the metrics reported in the Enhanced ACKs of the neighbor are returned
+    /// by [`OpenThread::link_metrics_wait_enh_ack_report`].
+    ///
+    /// Only one Management Request can be in progress at a time.
+    ///
+    /// Arguments:
+    /// - `dest`: The link-local address of the neighbor (the Subject)
+    /// - `metrics`: The metrics to be reported (at most two), or `None` to clear the probing
+    ///
+    /// NOTE: The returned future completes only if a response is received;
+    /// use a timeout to bound the wait.
+    #[cfg(feature = "link-metrics-initiator")]
+    pub async fn link_metrics_config_enh_ack_probing(
+        &self,
+        dest: Ipv6Addr,
+        metrics: Option<LinkMetrics>,
+    ) -> Result<LinkMetricsStatus, OtError> {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            // Clear any stale response left over from a prior request whose future was dropped
+            state.ot.link_metrics_initiator.mgmt_response.reset();
+
+            let ot_metrics = metrics.map(LinkMetrics::to_ot);
+
+            ot!(unsafe {
+                otLinkMetricsConfigEnhAckProbing(
+                    state.ot.instance,
+                    &to_ot_ip6_addr(dest),
+                    if metrics.is_some() {
+                        otLinkMetricsEnhAckFlags_OT_LINK_METRICS_ENH_ACK_REGISTER
+                    } else {
+                        otLinkMetricsEnhAckFlags_OT_LINK_METRICS_ENH_ACK_CLEAR
+                    },
+                    ot_metrics
+                        .as_ref()
+                        .map_or(core::ptr::null(), |metrics| metrics as *const _),
+                    Some(OtContext::plat_c_link_metrics_mgmt_response_callback),
+                    state.ot.instance as *mut _,
+                    Some(OtContext::plat_c_link_metrics_enh_ack_report_callback),
+                    state.ot.instance as *mut _,
+                )
+            })?;
+        }
+
+        Ok(self.link_metrics_wait_mgmt_response().await)
+    }
+
+    /// Send an MLE Link Probe message to a neighbor, to be accounted in a Forward Tracking Series
+    /// configured with [`LinkMetricsSeriesFlags::LINK_PROBE`].
+    ///
+    /// Arguments:
+    /// - `dest`: The link-local address of the neighbor (the Subject)
+    /// - `series_id`: The ID of the series, in the range [1, 254]
+    /// - `len`: The length of the payload of the probe, in the range [0, 64]
+    #[cfg(feature = "link-metrics-initiator")]
+    pub fn link_metrics_send_link_probe(
+        &self,
+        dest: Ipv6Addr,
+        series_id: u8,
+        len: u8,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe {
+            otLinkMetricsSendLinkProbe(state.ot.instance, &to_ot_ip6_addr(dest), series_id, len)
+        })
+    }
+
+    /// Wait for the next Link Metrics report received in an Enhanced ACK of a neighbor
+    /// with which Enhanced-ACK Based Probing is registered.
+    ///
+    /// See [`LINK_METRICS_MAX_PENDING_ENH_ACK_REPORTS`].
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    #[cfg(feature = "link-metrics-initiator")]
+    pub async fn link_metrics_wait_enh_ack_report(&self) -> LinkMetricsEnhAckReport {
+        poll_fn(|cx| {
+            let mut ot = self.activate();
+            let initiator = &mut ot.state().ot.link_metrics_initiator;
+
+            if let Some(report) = initiator.enh_ack_reports.pop_front() {
+                return Poll::Ready(report);
+            }
+
+            initiator.enh_ack_report.reset();
+            let _ = initiator.enh_ack_report.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Gets the list of the Initiators which have configured Enhanced-ACK Based Probing with this device
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each Initiator. Once called for all Initiators,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "link-metrics-subject")]
+    pub fn link_metrics_subject_initiators<F>(&self, mut f: F)
+    where
+        F: FnMut(Option<&LinkMetricsInitiator>),
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        for initiator in &state.ot.radio_conf_enh_ack_probing.initiators {
+            f(Some(&LinkMetricsInitiator::from_radio(initiator)));
+        }
+
+        f(None);
+    }
+
+    /// Wait for the Initiators which have configured Enhanced-ACK Based Probing with this device to change.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    #[cfg(feature = "link-metrics-subject")]
+    pub async fn link_metrics_subject_wait_changed(&self) {
+        poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .ot
+                .link_metrics_subject_changed
+                .poll_wait(cx)
+        })
+        .await
+    }
+
+    #[cfg(feature = "link-metrics-initiator")]
+    async fn link_metrics_wait_mgmt_response(&self) -> LinkMetricsStatus {
+        poll_fn(move |cx| {
+            self.activate()
+                .state()
+                .ot
+                .link_metrics_initiator
+                .mgmt_response
+                .poll_wait(cx)
+        })
+        .await
+    }
+}
+
+impl OtContext<'_> {
+    #[cfg(feature = "link-metrics-initiator")]
+    unsafe extern "C" fn plat_c_link_metrics_report_callback(
+        source: *const otIp6Address,
+        values: *const otLinkMetricsValues,
+        status: otLinkMetricsStatus,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        let report = LinkMetricsReport {
+            source: unsafe { (*source).mFields.m8 }.into(),
+            values: unsafe { values.as_ref() }.map(LinkMetricsValues::from_ot),
+            status: status.into(),
+        };
+
+        debug!("Link Metrics report: {:?}", report);
+
+        ot.state().ot.link_metrics_initiator.report.signal(report);
+    }
+
+    #[cfg(feature = "link-metrics-initiator")]
+    unsafe extern "C" fn plat_c_link_metrics_mgmt_response_callback(
+        _source: *const otIp6Address,
+        status: otLinkMetricsStatus,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+
+        ot.state()
+            .ot
+            .link_metrics_initiator
+            .mgmt_response
+            .signal(status.into());
+    }
+
+    #[cfg(feature = "link-metrics-initiator")]
+    unsafe extern "C" fn plat_c_link_metrics_enh_ack_report_callback(
+        short_address: otShortAddress,
+        ext_address: *const otExtAddress,
+        values: *const otLinkMetricsValues,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+        let initiator = &mut ot.state().ot.link_metrics_initiator;
+
+        let report = LinkMetricsEnhAckReport {
+            short_address,
+            ext_address: u64::from_be_bytes(unsafe { (*ext_address).m8 }),
+            values: LinkMetricsValues::from_ot(unsafe { &*values }),
+        };
+
+        if initiator.enh_ack_reports.push_back(report).is_err() {
+            warn!("Too many pending Enhanced-ACK Link Metrics reports, dropping report");
+        } else {
+            initiator.enh_ack_report.signal(());
+        }
+    }
+
+    /// Register, update or clear (with empty `metrics`) the Enhanced-ACK Based Probing of an Initiator.
+    #[cfg(feature = "link-metrics-subject")]
+    pub(crate) fn plat_radio_configure_enh_ack_probing(
+        &mut self,
+        metrics: &otLinkMetrics,
+        short_address: u16,
+        ext_address: u64,
+    ) -> otError {
+        let metrics = LinkMetrics::from_ot(metrics);
+
+        debug!(
+            "Enhanced-ACK probing of initiator {:04x}/{:016x}: {:?}",
+            short_address, ext_address, metrics
+        );
+
+        let state = self.state();
+
+        if !state.ot.radio_enh_ack_probing {
+            warn!("Radio does not support Enhanced-ACK probing");
+            return otError_OT_ERROR_NOT_CAPABLE;
+        }
+
+        let initiators = &mut state.ot.radio_conf_enh_ack_probing.initiators;
+
+        let pos = initiators
+            .iter()
+            .position(|initiator| initiator.ext_addr == ext_address);
+
+        let initiator = EnhAckProbingInitiator {
+            short_addr: short_address,
+            ext_addr: ext_address,
+            lqi: metrics.contains(LinkMetrics::LQI),
+            link_margin: metrics.contains(LinkMetrics::LINK_MARGIN),
+            rssi: metrics.contains(LinkMetrics::RSSI),
+        };
+
+        let res = match (pos, metrics.is_empty()) {
+            (Some(pos), true) => {
+                initiators.swap_remove(pos);
+                otError_OT_ERROR_NONE
+            }
+            (None, true) => otError_OT_ERROR_NOT_FOUND,
+            (Some(pos), false) => {
+                initiators[pos] = initiator;
+                otError_OT_ERROR_NONE
+            }
+            (None, false) => {
+                if initiators.push(initiator).is_ok() {
+                    otError_OT_ERROR_NONE
+                } else {
+                    otError_OT_ERROR_NO_BUFS
+                }
+            }
+        };
+
+        if res == otError_OT_ERROR_NONE {
+            state.ot.radio_conf_enh_ack_probing_changed.signal(());
+            state.ot.link_metrics_subject_changed.signal(());
+        }
+
+        res
+    }
+}
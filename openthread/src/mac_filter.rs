@@ -0,0 +1,232 @@
+//! MAC filter API (the `mac-filter` feature).
+//!
+//! The MAC filter restricts the neighbors a device hears: either with an allowlist or a denylist
+//! of extended addresses, and/or by overriding the received signal strength (RSS) of the frames
+//! of specific neighbors (or of all neighbors) with a fixed value.
+
+use crate::sys::{
+    otExtAddress, otLinkFilterAddAddress, otLinkFilterAddRssIn, otLinkFilterClearAddresses,
+    otLinkFilterClearAllRssIn, otLinkFilterClearDefaultRssIn, otLinkFilterGetAddressMode,
+    otLinkFilterGetNextAddress, otLinkFilterGetNextRssIn, otLinkFilterRemoveAddress,
+    otLinkFilterRemoveRssIn, otLinkFilterSetAddressMode, otLinkFilterSetDefaultRssIn,
+    otMacFilterAddressMode, otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_ALLOWLIST,
+    otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_DENYLIST,
+    otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_DISABLED, otMacFilterEntry,
+    otMacFilterIterator, OT_MAC_FILTER_FIXED_RSS_DISABLED, OT_MAC_FILTER_ITERATOR_INIT,
+};
+use crate::{ot, OpenThread, OtError};
+
+/// The address mode of the MAC filter.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MacFilterAddressMode {
+    /// Address filtering is disabled
+    Disabled,
+    /// Only the frames of the neighbors in the address list are received
+    Allowlist,
+    /// The frames of the neighbors in the address list are dropped
+    Denylist,
+}
+
+impl MacFilterAddressMode {
+    fn to_ot(self) -> otMacFilterAddressMode {
+        match self {
+            Self::Disabled => otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_DISABLED,
+            Self::Allowlist => otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_ALLOWLIST,
+            Self::Denylist => otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_DENYLIST,
+        }
+    }
+}
+
+impl From<otMacFilterAddressMode> for MacFilterAddressMode {
+    #[allow(non_upper_case_globals)]
+    fn from(value: otMacFilterAddressMode) -> Self {
+        match value {
+            otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_ALLOWLIST => Self::Allowlist,
+            otMacFilterAddressMode_OT_MAC_FILTER_ADDRESS_MODE_DENYLIST => Self::Denylist,
+            _ => Self::Disabled,
+        }
+    }
+}
+
+/// An entry of the address list of the MAC filter, as returned by [`OpenThread::mac_filter_addrs`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MacFilterEntry {
+    /// The extended address of the neighbor
+    pub ext_address: u64,
+    /// The fixed received signal strength (in dBm) of the neighbor, if any
+    pub rss_in: Option<i8>,
+}
+
+impl MacFilterEntry {
+    fn from_ot(entry: &otMacFilterEntry) -> Self {
+        Self {
+            ext_address: u64::from_be_bytes(entry.mExtAddress.m8),
+            rss_in: (entry.mRssIn != OT_MAC_FILTER_FIXED_RSS_DISABLED as i8)
+                .then_some(entry.mRssIn),
+        }
+    }
+}
+
+/// An entry of the fixed received signal strength list of the MAC filter,
+/// as returned by [`OpenThread::mac_filter_rss_ins`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MacFilterRssEntry {
+    /// The extended address of the neighbor, or `None` for the default entry applying to all other neighbors
+    pub ext_address: Option<u64>,
+    /// The fixed received signal strength (in dBm)
+    pub rss_in: i8,
+}
+
+impl OpenThread<'_> {
+    /// Return the address mode of the MAC filter.
+    pub fn mac_filter_address_mode(&self) -> MacFilterAddressMode {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otLinkFilterGetAddressMode(state.ot.instance) }.into()
+    }
+
+    /// Set the address mode of the MAC filter.
+    pub fn mac_filter_set_address_mode(&self, mode: MacFilterAddressMode) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otLinkFilterSetAddressMode(state.ot.instance, mode.to_ot()) }
+    }
+
+    /// Add an extended address to the address list of the MAC filter.
+    ///
+    /// Adding an already-added address is a no-op (reported as success).
+    pub fn mac_filter_add_addr(&self, ext_address: u64) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        match ot!(unsafe { otLinkFilterAddAddress(state.ot.instance, &to_ot(ext_address)) }) {
+            Err(e) if e.into_inner() == crate::sys::otError_OT_ERROR_ALREADY => Ok(()),
+            other => other,
+        }
+    }
+
+    /// Remove an extended address from the address list of the MAC filter.
+    pub fn mac_filter_remove_addr(&self, ext_address: u64) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otLinkFilterRemoveAddress(state.ot.instance, &to_ot(ext_address)) }
+    }
+
+    /// Remove all extended addresses from the address list of the MAC filter.
+    pub fn mac_filter_clear_addrs(&self) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otLinkFilterClearAddresses(state.ot.instance) }
+    }
+
+    /// Gets the address list of the MAC filter
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry. Once called for all entries,
+    ///   the closure will be called with `None`.
+    pub fn mac_filter_addrs<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&MacFilterEntry>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut iter: otMacFilterIterator = OT_MAC_FILTER_ITERATOR_INIT as _;
+        let mut entry = otMacFilterEntry::default();
+
+        while unsafe { otLinkFilterGetNextAddress(state.ot.instance, &mut iter, &mut entry) }
+            == crate::sys::otError_OT_ERROR_NONE
+        {
+            f(Some(&MacFilterEntry::from_ot(&entry)))?;
+        }
+
+        f(None)
+    }
+
+    /// Set a fixed received signal strength for the frames of a neighbor,
+    /// or - with `ext_address` being `None` - the default one for the frames of all other neighbors.
+    ///
+    /// Arguments:
+    /// - `ext_address`: The extended address of the neighbor, or `None` for the default
+    /// - `rss_in`: The fixed received signal strength (in dBm)
+    pub fn mac_filter_set_rss_in(
+        &self,
+        ext_address: Option<u64>,
+        rss_in: i8,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        if let Some(ext_address) = ext_address {
+            ot!(unsafe { otLinkFilterAddRssIn(state.ot.instance, &to_ot(ext_address), rss_in) })
+        } else {
+            unsafe { otLinkFilterSetDefaultRssIn(state.ot.instance, rss_in) };
+
+            Ok(())
+        }
+    }
+
+    /// Remove the fixed received signal strength of a neighbor,
+    /// or - with `ext_address` being `None` - the default one.
+    pub fn mac_filter_remove_rss_in(&self, ext_address: Option<u64>) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        if let Some(ext_address) = ext_address {
+            unsafe { otLinkFilterRemoveRssIn(state.ot.instance, &to_ot(ext_address)) }
+        } else {
+            unsafe { otLinkFilterClearDefaultRssIn(state.ot.instance) }
+        }
+    }
+
+    /// Remove all fixed received signal strengths, including the default one.
+    pub fn mac_filter_clear_rss_ins(&self) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otLinkFilterClearAllRssIn(state.ot.instance) }
+    }
+
+    /// Gets the fixed received signal strength list of the MAC filter
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each entry. Once called for all entries,
+    ///   the closure will be called with `None`.
+    pub fn mac_filter_rss_ins<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&MacFilterRssEntry>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut iter: otMacFilterIterator = OT_MAC_FILTER_ITERATOR_INIT as _;
+        let mut entry = otMacFilterEntry::default();
+
+        while unsafe { otLinkFilterGetNextRssIn(state.ot.instance, &mut iter, &mut entry) }
+            == crate::sys::otError_OT_ERROR_NONE
+        {
+            // The default entry is reported with the broadcast extended address
+            let ext_address = u64::from_be_bytes(entry.mExtAddress.m8);
+
+            f(Some(&MacFilterRssEntry {
+                ext_address: (ext_address != u64::MAX).then_some(ext_address),
+                rss_in: entry.mRssIn,
+            }))?;
+        }
+
+        f(None)
+    }
+}
+
+fn to_ot(ext_address: u64) -> otExtAddress {
+    otExtAddress {
+        m8: ext_address.to_be_bytes(),
+    }
+}
@@ -0,0 +1,1291 @@
+//! Native mDNS API (`OPENTHREAD_CONFIG_MULTICAST_DNS_ENABLE` / the `mdns` feature).
+//!
+//! OpenThread's mDNS module publishes hosts, services and keys, and browses/resolves services, on
+//! the *infrastructure* network (i.e. the non-Thread link of a border router, typically Ethernet
+//! or Wi-Fi). It does not do any I/O by itself: it sends and receives mDNS messages via the
+//! `otPlatMdns*` platform API, which is bridged here to the [`MdnsSocket`] trait.
+//!
+//! Usage:
+//! - Run [`OpenThread::run_mdns`] with an [`MdnsSocket`] implementation, concurrently with
+//!   [`OpenThread::run`]. With the `std` feature, [`UdpMdnsSocket`] is a ready-made implementation
+//!   over the host UDP stack (Unix).
+//! - Enable the module on the infrastructure network interface with [`OpenThread::mdns_set_enabled`],
+//!   and report the addresses of that interface with [`OpenThread::mdns_set_host_addrs`].
+//! - Register entries with [`OpenThread::mdns_register_host`] / [`OpenThread::mdns_register_service`] /
+//!   [`OpenThread::mdns_register_key`], and browse/resolve with [`OpenThread::mdns_browse`],
+//!   [`OpenThread::mdns_resolve_srv`], [`OpenThread::mdns_resolve_txt`] and
+//!   [`OpenThread::mdns_resolve_ip6`].
+//!
+//! Only one registration and one query of each kind (browse, SRV, TXT, IPv6 address) may be in
+//! flight at a time per `OpenThread` instance; starting another while one is pending returns
+//! `BUSY`.
+//!
+//! Browse/resolve results carrying names which are not valid UTF-8 (as any host on the
+//! infrastructure link may send) are skipped with a warning.
+//!
+//! NOTE: Like [`OpenThread::scan`], the futures returned by the query methods are NOT
+//! `core::mem::forget`-safe. They install a lifetime-erased reference to the user closure in
+//! shared state, relying on their `Drop` to clear it. Do not `mem::forget` them.
+
+use core::ffi::{c_char, CStr};
+use core::fmt::Debug;
+use core::future::poll_fn;
+use core::net::{Ipv6Addr, SocketAddrV6};
+use core::task::Poll;
+
+use embassy_futures::select::{select, Either};
+
+use crate::signal::Signal;
+use crate::sys::{
+    otError, otError_OT_ERROR_BUSY, otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE,
+    otError_OT_ERROR_NO_BUFS, otInstance, otIp6NewMessage, otMdnsAddressResolver, otMdnsBrowser,
+    otMdnsGetLocalHostName, otMdnsHost, otMdnsIsEnabled, otMdnsIsQuestionUnicastAllowed, otMdnsKey,
+    otMdnsRegisterHost, otMdnsRegisterKey, otMdnsRegisterService, otMdnsService, otMdnsSetEnabled,
+    otMdnsSetLocalHostName, otMdnsSetQuestionUnicastAllowed, otMdnsSrvResolver, otMdnsStartBrowser,
+    otMdnsStartIp6AddressResolver, otMdnsStartSrvResolver, otMdnsStartTxtResolver,
+    otMdnsStopBrowser, otMdnsStopIp6AddressResolver, otMdnsStopSrvResolver, otMdnsStopTxtResolver,
+    otMdnsTxtResolver, otMdnsUnregisterHost, otMdnsUnregisterKey, otMdnsUnregisterService,
+    otMessage, otMessageAppend, otMessageFree, otMessageGetLength, otMessageRead,
+    otPlatDnssdAddressAndTtl, otPlatDnssdAddressResult, otPlatDnssdBrowseResult,
+    otPlatDnssdSrvResult, otPlatDnssdTxtResult, otPlatMdnsAddressInfo,
+    otPlatMdnsHandleHostAddressEvent, otPlatMdnsHandleHostAddressRemoveAll,
+    otPlatMdnsHandleReceive, OT_DNS_MAX_NAME_SIZE,
+};
+use crate::{c_str, ot, to_ot_ip6_addr, to_sock_addr, OpenThread, OtContext, OtError};
+
+#[cfg(feature = "std")]
+mod udp;
+
+#[cfg(feature = "std")]
+pub use udp::UdpMdnsSocket;
+
+/// The UDP port of mDNS.
+pub const MDNS_PORT: u16 = 5353;
+
+/// The IPv6 mDNS multicast group (`ff02::fb`).
+pub const MDNS_IP6_GROUP: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0xfb);
+
+/// The maximum size of an mDNS message sent or received by [`OpenThread::run_mdns`].
+pub const MDNS_MAX_MESSAGE_SIZE: usize = 1500;
+
+/// The maximum number of outgoing mDNS messages which can be queued by OpenThread,
+/// waiting to be sent by [`OpenThread::run_mdns`].
+///
+/// Messages arriving while the queue is full are dropped.
+pub const MDNS_MAX_PENDING_TX: usize = 4;
+
+/// The maximum number of sub-types of a service registered with [`OpenThread::mdns_register_service`].
+pub const MDNS_MAX_SUBTYPES: usize = 8;
+
+/// The maximum number of addresses of a host registered with [`OpenThread::mdns_register_host`].
+pub const MDNS_MAX_HOST_ADDRS: usize = 8;
+
+/// A socket for sending and receiving mDNS messages on the infrastructure network interface.
+///
+/// Driven by [`OpenThread::run_mdns`]. With the `std` feature, [`UdpMdnsSocket`] is a ready-made
+/// implementation over the host UDP stack.
+pub trait MdnsSocket {
+    /// The error type for socket operations.
+    type Error: Debug;
+
+    /// Start or stop listening for mDNS messages.
+    ///
+    /// Arguments:
+    /// - `infra_if_index`: `Some` with the index of the infrastructure network interface to listen on
+    ///   (and to send from) or `None` to stop listening.
+    ///
+    /// While listening, the socket must receive the messages sent to the mDNS multicast group
+    /// on port [`MDNS_PORT`], as well as the unicast messages sent to it.
+    async fn set_listening(&mut self, infra_if_index: Option<u32>) -> Result<(), Self::Error>;
+
+    /// Send an mDNS message.
+    ///
+    /// Arguments:
+    /// - `data`: The message.
+    /// - `dest`: The unicast destination of the message, or `None` if the message should be sent
+    ///   to the mDNS multicast group on port [`MDNS_PORT`].
+    async fn send(&mut self, data: &[u8], dest: Option<SocketAddrV6>) -> Result<(), Self::Error>;
+
+    /// Receive an mDNS message.
+    ///
+    /// Only called while listening.
+    ///
+    /// The returned future must be cancel-safe: [`OpenThread::run_mdns`] drops it whenever
+    /// OpenThread has an mDNS message to send (or the listening state changes), and calls
+    /// `recv` again afterwards. Dropping the future must therefore not lose a message which
+    /// had already been received by the implementation.
+    ///
+    /// Arguments:
+    /// - `buf`: A buffer where the received message will be stored.
+    ///
+    /// Returns:
+    /// - The length of the received message, its source address and whether
+    ///   it was received by unicast (as opposed to multicast).
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, SocketAddrV6, bool), Self::Error>;
+}
+
+impl<T> MdnsSocket for &mut T
+where
+    T: MdnsSocket,
+{
+    type Error = T::Error;
+
+    async fn set_listening(&mut self, infra_if_index: Option<u32>) -> Result<(), Self::Error> {
+        T::set_listening(self, infra_if_index).await
+    }
+
+    async fn send(&mut self, data: &[u8], dest: Option<SocketAddrV6>) -> Result<(), Self::Error> {
+        T::send(self, data, dest).await
+    }
+
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, SocketAddrV6, bool), Self::Error> {
+        T::recv(self, buf).await
+    }
+}
+
+/// A host to register with [`OpenThread::mdns_register_host`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsHost<'a> {
+    /// The host name, without the domain (e.g. `my-host`).
+    pub name: &'a str,
+    /// The IPv6 addresses of the host (at most [`MDNS_MAX_HOST_ADDRS`]).
+    /// An empty list is equivalent to unregistering the host.
+    pub addrs: &'a [Ipv6Addr],
+    /// The TTL of the records in seconds, or 0 for the default (120 seconds).
+    pub ttl: u32,
+}
+
+/// A service to register with [`OpenThread::mdns_register_service`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsService<'a> {
+    /// The service instance label (e.g. `My Light`).
+    pub instance: &'a str,
+    /// The service type, without the domain (e.g. `_matter._tcp`).
+    pub service_type: &'a str,
+    /// The host name providing the service, without the domain (e.g. `my-host`),
+    /// or `None` for the local host of the mDNS module.
+    pub host_name: Option<&'a str>,
+    /// The sub-type labels of the service (at most [`MDNS_MAX_SUBTYPES`]).
+    pub subtypes: &'a [&'a str],
+    /// The encoded TXT data of the service.
+    pub txt_data: &'a [u8],
+    /// The service port.
+    pub port: u16,
+    /// The service priority.
+    pub priority: u16,
+    /// The service weight.
+    pub weight: u16,
+    /// The TTL of the records in seconds, or 0 for the default (120 seconds).
+    pub ttl: u32,
+}
+
+/// A key record to register with [`OpenThread::mdns_register_key`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsKey<'a> {
+    /// The host name or the service instance label the key belongs to.
+    pub name: &'a str,
+    /// The service type if the key belongs to a service, or `None` if it belongs to a host.
+    pub service_type: Option<&'a str>,
+    /// The key record data.
+    pub key_data: &'a [u8],
+    /// The TTL of the record in seconds, or 0 for the default (120 seconds).
+    pub ttl: u32,
+}
+
+/// A service instance discovered by [`OpenThread::mdns_browse`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsBrowseResult<'a> {
+    /// The service type.
+    pub service_type: &'a str,
+    /// The sub-type label, if browsing for a sub-type.
+    pub subtype: Option<&'a str>,
+    /// The service instance label.
+    pub instance: &'a str,
+    /// The TTL of the record in seconds. A TTL of 0 means the instance was removed.
+    pub ttl: u32,
+}
+
+/// An SRV record resolved by [`OpenThread::mdns_resolve_srv`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsSrvResult<'a> {
+    /// The service instance label.
+    pub instance: &'a str,
+    /// The service type.
+    pub service_type: &'a str,
+    /// The host name of the service.
+    pub host_name: &'a str,
+    /// The service port.
+    pub port: u16,
+    /// The service priority.
+    pub priority: u16,
+    /// The service weight.
+    pub weight: u16,
+    /// The TTL of the record in seconds. A TTL of 0 means the service was removed,
+    /// and the other fields should be ignored.
+    pub ttl: u32,
+}
+
+/// A TXT record resolved by [`OpenThread::mdns_resolve_txt`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MdnsTxtResult<'a> {
+    /// The service instance label.
+    pub instance: &'a str,
+    /// The service type.
+    pub service_type: &'a str,
+    /// The encoded TXT data.
+    pub txt_data: &'a [u8],
+    /// The TTL of the record in seconds. A TTL of 0 means the service was removed.
+    pub ttl: u32,
+}
+
+/// The IPv6 addresses of a host resolved by [`OpenThread::mdns_resolve_ip6`].
+#[derive(Clone)]
+pub struct MdnsAddressResult<'a> {
+    /// The host name.
+    pub host_name: &'a str,
+    addrs: &'a [otPlatDnssdAddressAndTtl],
+}
+
+impl MdnsAddressResult<'_> {
+    /// Return an iterator over the addresses of the host and their TTLs in seconds.
+    ///
+    /// The iterator is empty if all addresses of the host were removed.
+    pub fn addrs(&self) -> impl Iterator<Item = (Ipv6Addr, u32)> + '_ {
+        self.addrs.iter().map(|addr| {
+            (
+                Ipv6Addr::from(unsafe { addr.mAddress.mFields.m8 }),
+                addr.mTtl,
+            )
+        })
+    }
+}
+
+impl Debug for MdnsAddressResult<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Addrs<'a, 'r>(&'r MdnsAddressResult<'a>);
+
+        impl Debug for Addrs<'_, '_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.0.addrs()).finish()
+            }
+        }
+
+        f.debug_struct("MdnsAddressResult")
+            .field("host_name", &self.host_name)
+            .field("addrs", &Addrs(self))
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for MdnsAddressResult<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "MdnsAddressResult {{ host_name: {}, addrs: [",
+            self.host_name
+        );
+
+        for (index, (addr, ttl)) in self.addrs().enumerate() {
+            if index > 0 {
+                defmt::write!(f, ", ");
+            }
+
+            defmt::write!(f, "({}, {})", addr, ttl);
+        }
+
+        defmt::write!(f, "] }}");
+    }
+}
+
+/// The result of an in-flight mDNS query, as dispatched to the installed closure.
+pub(crate) enum MdnsQueryResult<'a> {
+    Browse(MdnsBrowseResult<'a>),
+    Srv(MdnsSrvResult<'a>),
+    Txt(MdnsTxtResult<'a>),
+    Ip6(MdnsAddressResult<'a>),
+}
+
+impl MdnsQueryResult<'_> {
+    const BROWSE: usize = 0;
+    const SRV: usize = 1;
+    const TXT: usize = 2;
+    const IP6: usize = 3;
+    const KINDS: usize = 4;
+
+    /// The index of the query slot in `MdnsState::queries` for this kind of result.
+    const fn kind(&self) -> usize {
+        match self {
+            Self::Browse(_) => Self::BROWSE,
+            Self::Srv(_) => Self::SRV,
+            Self::Txt(_) => Self::TXT,
+            Self::Ip6(_) => Self::IP6,
+        }
+    }
+}
+
+/// The slot of an in-flight mDNS query of a given kind.
+pub(crate) struct MdnsQuery<'a> {
+    /// The callback to invoke for each result. Holds a lifetime-erased reference to the
+    /// user closure for the duration of the query. Returns `true` once the query is done.
+    #[allow(clippy::type_complexity)]
+    callback: Option<&'a mut dyn FnMut(&MdnsQueryResult) -> bool>,
+    /// Signaled when the user closure reports that the query is done.
+    done: Signal<()>,
+}
+
+impl MdnsQuery<'_> {
+    const fn new() -> Self {
+        Self {
+            callback: None,
+            done: Signal::new(),
+        }
+    }
+}
+
+/// The mDNS state of `OtState`.
+pub(crate) struct MdnsState<'a> {
+    /// The infrastructure network interface index the mDNS module was last enabled on.
+    infra_if_index: u32,
+    /// The infrastructure network interface index OpenThread wants the socket to listen on
+    /// (`otPlatMdnsSetListeningEnabled`), if any.
+    listening: Option<u32>,
+    /// Outgoing messages waiting to be sent by `run_mdns`, with their unicast destinations.
+    tx: heapless::Deque<(*mut otMessage, Option<SocketAddrV6>), MDNS_MAX_PENDING_TX>,
+    /// Signaled when `listening` changes or a new message is queued in `tx`.
+    changed: Signal<()>,
+    /// The request ID of the in-flight registration, if any.
+    register_id: Option<u32>,
+    /// The request ID to use for the next registration.
+    next_register_id: u32,
+    /// Carries the outcome of the in-flight registration back to the awaiting future.
+    register_done: Signal<otError>,
+    /// The in-flight queries, one per `MdnsQueryResult` kind.
+    queries: [MdnsQuery<'a>; MdnsQueryResult::KINDS],
+}
+
+impl MdnsState<'_> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            infra_if_index: 0,
+            listening: None,
+            tx: heapless::Deque::new(),
+            changed: Signal::new(),
+            register_id: None,
+            next_register_id: 0,
+            register_done: Signal::new(),
+            queries: [const { MdnsQuery::new() }; MdnsQueryResult::KINDS],
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Run the mDNS I/O of OpenThread with the provided socket.
+    ///
+    /// Needs to run concurrently with [`OpenThread::run`] for the mDNS module to operate.
+    ///
+    /// Arguments:
+    /// - `socket`: The socket to send and receive mDNS messages with.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn run_mdns<S>(&self, mut socket: S) -> !
+    where
+        S: MdnsSocket,
+    {
+        let mut buf = [0; MDNS_MAX_MESSAGE_SIZE];
+        let mut listening = None;
+
+        loop {
+            let wanted = self.activate().state().ot.mdns.listening;
+
+            if wanted != listening {
+                debug!("mDNS listening on: {:?}", wanted);
+
+                if let Err(e) = socket.set_listening(wanted).await {
+                    warn!("Setting mDNS listening failed: {:?}", debug2format!(e));
+                }
+
+                listening = wanted;
+                continue;
+            }
+
+            if let Some((len, dest)) = self.mdns_take_tx(&mut buf) {
+                if let Err(e) = socket.send(&buf[..len], dest).await {
+                    warn!("Sending mDNS message failed: {:?}", debug2format!(e));
+                }
+
+                continue;
+            }
+
+            let Some(infra_if_index) = listening else {
+                self.mdns_wait_changed(listening).await;
+                continue;
+            };
+
+            match select(self.mdns_wait_changed(listening), socket.recv(&mut buf)).await {
+                Either::First(_) => (),
+                Either::Second(Ok((len, src, unicast))) => {
+                    self.mdns_rx(&buf[..len], src, unicast, infra_if_index)
+                }
+                Either::Second(Err(e)) => {
+                    warn!("Receiving mDNS message failed: {:?}", debug2format!(e))
+                }
+            }
+        }
+    }
+
+    /// Enable or disable the mDNS module.
+    ///
+    /// Disabling the module removes all registered entries (without "goodbye" announcements)
+    /// and stops all browsers and resolvers.
+    ///
+    /// Arguments:
+    /// - `enable`: `true` to enable the module, `false` to disable it.
+    /// - `infra_if_index`: The index of the infrastructure network interface to operate on.
+    ///   Ignored when disabling.
+    pub fn mdns_set_enabled(&self, enable: bool, infra_if_index: u32) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        if enable {
+            state.ot.mdns.infra_if_index = infra_if_index;
+        }
+
+        ot!(unsafe { otMdnsSetEnabled(state.ot.instance, enable, infra_if_index) })
+    }
+
+    /// Return `true` if the mDNS module is enabled.
+    pub fn mdns_is_enabled(&self) -> bool {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe { otMdnsIsEnabled(instance) }
+    }
+
+    /// Allow or disallow the mDNS module to send "QU" questions (i.e. questions requesting unicast responses).
+    ///
+    /// Can be disallowed when the socket cannot receive unicast responses on the mDNS port.
+    pub fn mdns_set_question_unicast_allowed(&self, allow: bool) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe { otMdnsSetQuestionUnicastAllowed(instance, allow) }
+    }
+
+    /// Return `true` if the mDNS module is allowed to send "QU" questions.
+    pub fn mdns_is_question_unicast_allowed(&self) -> bool {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe { otMdnsIsQuestionUnicastAllowed(instance) }
+    }
+
+    /// Set the local host name of the mDNS module.
+    ///
+    /// Can only be set while the module is disabled. If not set, the module generates a name itself.
+    ///
+    /// Arguments:
+    /// - `name`: The local host name, or `None` to let the module generate one.
+    pub fn mdns_set_local_host_name(&self, name: Option<&'static CStr>) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe {
+            otMdnsSetLocalHostName(instance, name.map_or(core::ptr::null(), CStr::as_ptr))
+        })
+    }
+
+    /// Get the local host name of the mDNS module.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called with the local host name.
+    pub fn mdns_local_host_name<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&str) -> R,
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        // The local host name is always set locally from a `&str`, hence it is valid UTF-8
+        f(c_str(unsafe { otMdnsGetLocalHostName(instance) }).unwrap_or(""))
+    }
+
+    /// Set the IPv6 addresses of the local host on the infrastructure network interface.
+    ///
+    /// The mDNS module advertises these as the addresses of its local host name. Should be
+    /// called whenever the addresses of the interface change.
+    ///
+    /// Arguments:
+    /// - `addrs`: The addresses of the infrastructure network interface.
+    pub fn mdns_set_host_addrs(&self, addrs: &[Ipv6Addr]) {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+        let infra_if_index = state.ot.mdns.infra_if_index;
+
+        unsafe {
+            otPlatMdnsHandleHostAddressRemoveAll(instance, infra_if_index);
+        }
+
+        for addr in addrs {
+            let addr = to_ot_ip6_addr(*addr);
+
+            unsafe {
+                otPlatMdnsHandleHostAddressEvent(instance, &addr, true, infra_if_index);
+            }
+        }
+    }
+
+    /// Register (or update) a host.
+    ///
+    /// Completes once the host is registered, i.e. once probing for its name completed.
+    /// Returns an `DUPLICATED` error if the name is claimed by another mDNS responder.
+    ///
+    /// Arguments:
+    /// - `host`: The host to register.
+    pub async fn mdns_register_host(&self, host: &MdnsHost<'_>) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ OT_DNS_MAX_NAME_SIZE as usize }>::new();
+        let ot_host = self.mdns_host(host, &mut names)?;
+
+        self.mdns_register(|instance, id| unsafe {
+            otMdnsRegisterHost(
+                instance,
+                &ot_host,
+                id,
+                Some(OtContext::plat_c_mdns_register_callback),
+            )
+        })
+        .await
+    }
+
+    /// Unregister a host.
+    ///
+    /// Arguments:
+    /// - `name`: The host name, without the domain.
+    pub fn mdns_unregister_host(&self, name: &str) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let ot_host = otMdnsHost {
+            mHostName: names.push(name)?,
+            ..Default::default()
+        };
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe { otMdnsUnregisterHost(instance, &ot_host) })
+    }
+
+    /// Register (or update) a service.
+    ///
+    /// Completes once the service is registered, i.e. once probing for its name completed.
+    /// Returns an `DUPLICATED` error if the name is claimed by another mDNS responder.
+    ///
+    /// Arguments:
+    /// - `service`: The service to register.
+    pub async fn mdns_register_service(&self, service: &MdnsService<'_>) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ 3 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+        let mut subtypes = heapless::Vec::<*const c_char, MDNS_MAX_SUBTYPES>::new();
+        let ot_service = self.mdns_service(service, &mut names, &mut subtypes)?;
+
+        self.mdns_register(|instance, id| unsafe {
+            otMdnsRegisterService(
+                instance,
+                &ot_service,
+                id,
+                Some(OtContext::plat_c_mdns_register_callback),
+            )
+        })
+        .await
+    }
+
+    /// Unregister a service.
+    ///
+    /// Arguments:
+    /// - `instance`: The service instance label.
+    /// - `service_type`: The service type, without the domain.
+    pub fn mdns_unregister_service(
+        &self,
+        instance: &str,
+        service_type: &str,
+    ) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let ot_service = otMdnsService {
+            mServiceInstance: names.push(instance)?,
+            mServiceType: names.push(service_type)?,
+            ..Default::default()
+        };
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe { otMdnsUnregisterService(instance, &ot_service) })
+    }
+
+    /// Register (or update) a key record.
+    ///
+    /// Completes once the key is registered, i.e. once probing for its name completed.
+    /// Returns an `DUPLICATED` error if the name is claimed by another mDNS responder.
+    ///
+    /// Arguments:
+    /// - `key`: The key to register.
+    pub async fn mdns_register_key(&self, key: &MdnsKey<'_>) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+        let ot_key = Self::mdns_key(key.name, key.service_type, &mut names)?;
+
+        if key.key_data.len() > u16::MAX as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let ot_key = otMdnsKey {
+            mKeyData: key.key_data.as_ptr(),
+            mKeyDataLength: key.key_data.len() as _,
+            mTtl: key.ttl,
+            ..ot_key
+        };
+
+        self.mdns_register(|instance, id| unsafe {
+            otMdnsRegisterKey(
+                instance,
+                &ot_key,
+                id,
+                Some(OtContext::plat_c_mdns_register_callback),
+            )
+        })
+        .await
+    }
+
+    /// Unregister a key record.
+    ///
+    /// Arguments:
+    /// - `name`: The host name or the service instance label the key belongs to.
+    /// - `service_type`: The service type if the key belongs to a service, or `None` if it belongs to a host.
+    pub fn mdns_unregister_key(
+        &self,
+        name: &str,
+        service_type: Option<&str>,
+    ) -> Result<(), OtError> {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+        let ot_key = Self::mdns_key(name, service_type, &mut names)?;
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe { otMdnsUnregisterKey(instance, &ot_key) })
+    }
+
+    /// Browse for the instances of a service.
+    ///
+    /// The browser runs until the closure returns `true` (or until the returned future is dropped).
+    /// The closure may be called with cached results right away.
+    ///
+    /// Arguments:
+    /// - `service_type`: The service type, without the domain (e.g. `_matter._tcp`).
+    /// - `subtype`: The sub-type label to browse for, or `None` to browse for the base service.
+    /// - `f`: A closure called with each added or removed (TTL 0) service instance.
+    ///   Returns `true` to stop browsing.
+    pub async fn mdns_browse<F>(
+        &self,
+        service_type: &str,
+        subtype: Option<&str>,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&MdnsBrowseResult) -> bool,
+    {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let browser = otMdnsBrowser {
+            mServiceType: names.push(service_type)?,
+            mSubTypeLabel: subtype.map_or(Ok(core::ptr::null()), |s| names.push(s))?,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+            mCallback: Some(OtContext::plat_c_mdns_browse_callback),
+        };
+
+        self.mdns_query(
+            MdnsQueryResult::BROWSE,
+            |result| match result {
+                MdnsQueryResult::Browse(result) => f(result),
+                _ => false,
+            },
+            |instance| unsafe { otMdnsStartBrowser(instance, &browser) },
+            |instance| unsafe { otMdnsStopBrowser(instance, &browser) },
+        )
+        .await
+    }
+
+    /// Resolve the SRV record of a service instance.
+    ///
+    /// The resolver runs until the closure returns `true` (or until the returned future is dropped).
+    /// The closure may be called with a cached result right away.
+    ///
+    /// Arguments:
+    /// - `instance`: The service instance label.
+    /// - `service_type`: The service type, without the domain.
+    /// - `f`: A closure called with each update of the record. Returns `true` to stop resolving.
+    pub async fn mdns_resolve_srv<F>(
+        &self,
+        instance: &str,
+        service_type: &str,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&MdnsSrvResult) -> bool,
+    {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let resolver = otMdnsSrvResolver {
+            mServiceInstance: names.push(instance)?,
+            mServiceType: names.push(service_type)?,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+            mCallback: Some(OtContext::plat_c_mdns_srv_callback),
+        };
+
+        self.mdns_query(
+            MdnsQueryResult::SRV,
+            |result| match result {
+                MdnsQueryResult::Srv(result) => f(result),
+                _ => false,
+            },
+            |instance| unsafe { otMdnsStartSrvResolver(instance, &resolver) },
+            |instance| unsafe { otMdnsStopSrvResolver(instance, &resolver) },
+        )
+        .await
+    }
+
+    /// Resolve the TXT record of a service instance.
+    ///
+    /// The resolver runs until the closure returns `true` (or until the returned future is dropped).
+    /// The closure may be called with a cached result right away.
+    ///
+    /// Arguments:
+    /// - `instance`: The service instance label.
+    /// - `service_type`: The service type, without the domain.
+    /// - `f`: A closure called with each update of the record. Returns `true` to stop resolving.
+    pub async fn mdns_resolve_txt<F>(
+        &self,
+        instance: &str,
+        service_type: &str,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&MdnsTxtResult) -> bool,
+    {
+        let mut names = NameBuf::<{ 2 * OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let resolver = otMdnsTxtResolver {
+            mServiceInstance: names.push(instance)?,
+            mServiceType: names.push(service_type)?,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+            mCallback: Some(OtContext::plat_c_mdns_txt_callback),
+        };
+
+        self.mdns_query(
+            MdnsQueryResult::TXT,
+            |result| match result {
+                MdnsQueryResult::Txt(result) => f(result),
+                _ => false,
+            },
+            |instance| unsafe { otMdnsStartTxtResolver(instance, &resolver) },
+            |instance| unsafe { otMdnsStopTxtResolver(instance, &resolver) },
+        )
+        .await
+    }
+
+    /// Resolve the IPv6 addresses of a host.
+    ///
+    /// The resolver runs until the closure returns `true` (or until the returned future is dropped).
+    /// The closure may be called with a cached result right away.
+    ///
+    /// Arguments:
+    /// - `host_name`: The host name, without the domain.
+    /// - `f`: A closure called with the updated list of addresses whenever addresses are added
+    ///   or removed. Returns `true` to stop resolving.
+    pub async fn mdns_resolve_ip6<F>(&self, host_name: &str, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(&MdnsAddressResult) -> bool,
+    {
+        let mut names = NameBuf::<{ OT_DNS_MAX_NAME_SIZE as usize }>::new();
+
+        let resolver = otMdnsAddressResolver {
+            mHostName: names.push(host_name)?,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+            mCallback: Some(OtContext::plat_c_mdns_ip6_callback),
+        };
+
+        self.mdns_query(
+            MdnsQueryResult::IP6,
+            |result| match result {
+                MdnsQueryResult::Ip6(result) => f(result),
+                _ => false,
+            },
+            |instance| unsafe { otMdnsStartIp6AddressResolver(instance, &resolver) },
+            |instance| unsafe { otMdnsStopIp6AddressResolver(instance, &resolver) },
+        )
+        .await
+    }
+
+    fn mdns_host(
+        &self,
+        host: &MdnsHost,
+        names: &mut NameBuf<{ OT_DNS_MAX_NAME_SIZE as usize }>,
+    ) -> Result<otMdnsHost, OtError> {
+        if host.addrs.len() > MDNS_MAX_HOST_ADDRS {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        Ok(otMdnsHost {
+            mHostName: names.push(host.name)?,
+            mAddresses: host.addrs.as_ptr() as _,
+            mAddressesLength: host.addrs.len() as _,
+            mTtl: host.ttl,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+        })
+    }
+
+    fn mdns_service<const N: usize>(
+        &self,
+        service: &MdnsService,
+        names: &mut NameBuf<N>,
+        subtypes: &mut heapless::Vec<*const c_char, MDNS_MAX_SUBTYPES>,
+    ) -> Result<otMdnsService, OtError> {
+        if service.txt_data.len() > u16::MAX as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        for subtype in service.subtypes {
+            subtypes
+                .push(names.push(subtype)?)
+                .map_err(|_| OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        Ok(otMdnsService {
+            mHostName: service
+                .host_name
+                .map_or(Ok(core::ptr::null()), |h| names.push(h))?,
+            mServiceInstance: names.push(service.instance)?,
+            mServiceType: names.push(service.service_type)?,
+            mSubTypeLabels: subtypes.as_ptr(),
+            mSubTypeLabelsLength: subtypes.len() as _,
+            mTxtData: service.txt_data.as_ptr(),
+            mTxtDataLength: service.txt_data.len() as _,
+            mPort: service.port,
+            mPriority: service.priority,
+            mWeight: service.weight,
+            mTtl: service.ttl,
+            mInfraIfIndex: self.activate().state().ot.mdns.infra_if_index,
+        })
+    }
+
+    fn mdns_key<const N: usize>(
+        name: &str,
+        service_type: Option<&str>,
+        names: &mut NameBuf<N>,
+    ) -> Result<otMdnsKey, OtError> {
+        Ok(otMdnsKey {
+            mName: names.push(name)?,
+            mServiceType: service_type.map_or(Ok(core::ptr::null()), |s| names.push(s))?,
+            // The `IN` class
+            mClass: 1,
+            ..Default::default()
+        })
+    }
+
+    async fn mdns_register<R>(&self, register: R) -> Result<(), OtError>
+    where
+        R: FnOnce(*mut otInstance, u32) -> otError,
+    {
+        let id = {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if state.ot.mdns.register_id.is_some() {
+                warn!("Another mDNS registration in progress");
+                return Err(OtError::new(otError_OT_ERROR_BUSY));
+            }
+
+            state.ot.mdns.register_done.reset();
+
+            let id = state.ot.mdns.next_register_id;
+            state.ot.mdns.next_register_id = id.wrapping_add(1);
+            state.ot.mdns.register_id = Some(id);
+
+            let res = register(state.ot.instance, id);
+
+            if res != otError_OT_ERROR_NONE {
+                state.ot.mdns.register_id = None;
+                ot!(res)?;
+            }
+
+            id
+        };
+
+        let _guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let mdns = &mut ot.state().ot.mdns;
+
+            if mdns.register_id == Some(id) {
+                mdns.register_id = None;
+            }
+        });
+
+        let error =
+            poll_fn(move |cx| self.activate().state().ot.mdns.register_done.poll_wait(cx)).await;
+
+        ot!(error)
+    }
+
+    async fn mdns_query<F, S, T>(
+        &self,
+        kind: usize,
+        mut f: F,
+        start: S,
+        stop: T,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&MdnsQueryResult) -> bool,
+        S: FnOnce(*mut otInstance) -> otError,
+        T: FnOnce(*mut otInstance) -> otError,
+    {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+            let query = &mut state.ot.mdns.queries[kind];
+
+            if query.callback.is_some() {
+                warn!("Another mDNS query of the same kind in progress");
+                return Err(OtError::new(otError_OT_ERROR_BUSY));
+            }
+
+            query.done.reset();
+
+            // Install the (lifetime-erased) user closure. Same pattern and the
+            // same `mem::forget` caveat as `scan` (see `scan.rs`).
+            let f: &mut dyn FnMut(&MdnsQueryResult) -> bool = &mut f;
+            query.callback = Some(unsafe {
+                core::mem::transmute::<
+                    &mut dyn FnMut(&MdnsQueryResult) -> bool,
+                    &'a mut dyn FnMut(&MdnsQueryResult) -> bool,
+                >(f)
+            });
+
+            let instance = state.ot.instance;
+            let res = start(instance);
+
+            if res != otError_OT_ERROR_NONE {
+                // Query never started; release the slot.
+                state.ot.mdns.queries[kind].callback = None;
+                ot!(res)?;
+            }
+        }
+
+        let _guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            state.ot.mdns.queries[kind].callback = None;
+
+            let _ = stop(state.ot.instance);
+        });
+
+        poll_fn(move |cx| {
+            self.activate().state().ot.mdns.queries[kind]
+                .done
+                .poll_wait(cx)
+        })
+        .await;
+
+        Ok(())
+    }
+
+    async fn mdns_wait_changed(&self, listening: Option<u32>) {
+        poll_fn(move |cx| {
+            let mut ot = self.activate();
+            let mdns = &mut ot.state().ot.mdns;
+
+            if mdns.listening != listening || !mdns.tx.is_empty() {
+                return Poll::Ready(());
+            }
+
+            mdns.changed.reset();
+            let _ = mdns.changed.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn mdns_take_tx(&self, buf: &mut [u8]) -> Option<(usize, Option<SocketAddrV6>)> {
+        let mut ot = self.activate();
+        let (msg, dest) = ot.state().ot.mdns.tx.pop_front()?;
+
+        let len = unsafe { otMessageGetLength(msg) } as usize;
+
+        let res = if len > buf.len() {
+            warn!("Dropping TX mDNS message, too large: {}", len);
+            None
+        } else {
+            unsafe { otMessageRead(msg, 0, buf.as_mut_ptr() as *mut _, len as _) };
+
+            Some((len, dest))
+        };
+
+        unsafe {
+            otMessageFree(msg);
+        }
+
+        res
+    }
+
+    fn mdns_rx(&self, data: &[u8], src: SocketAddrV6, unicast: bool, infra_if_index: u32) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let msg = unsafe { otIp6NewMessage(instance, core::ptr::null()) };
+
+        if msg.is_null() {
+            warn!("Dropping RX mDNS message, no buffers");
+            return;
+        }
+
+        if unsafe { otMessageAppend(msg, data.as_ptr() as *const _, data.len() as _) }
+            != otError_OT_ERROR_NONE
+        {
+            warn!("Dropping RX mDNS message, no buffers");
+
+            unsafe {
+                otMessageFree(msg);
+            }
+
+            return;
+        }
+
+        let address = otPlatMdnsAddressInfo {
+            mAddress: to_ot_ip6_addr(*src.ip()),
+            mPort: src.port(),
+            mInfraIfIndex: infra_if_index,
+        };
+
+        // Ownership of the message is passed to OpenThread
+        unsafe {
+            otPlatMdnsHandleReceive(instance, msg, unicast, &address);
+        }
+    }
+}
+
+impl OtContext<'_> {
+    pub(crate) fn plat_mdns_set_listening(&mut self, enable: bool, infra_if_index: u32) {
+        let mdns = &mut self.state().ot.mdns;
+
+        mdns.listening = enable.then_some(infra_if_index);
+        mdns.changed.signal(());
+    }
+
+    pub(crate) fn plat_mdns_send(
+        &mut self,
+        msg: *mut otMessage,
+        dest: Option<&otPlatMdnsAddressInfo>,
+    ) {
+        let mdns = &mut self.state().ot.mdns;
+
+        let dest = dest.map(|dest| to_sock_addr(&dest.mAddress, dest.mPort, dest.mInfraIfIndex));
+
+        if mdns.tx.push_back((msg, dest)).is_err() {
+            warn!("Dropping TX mDNS message, buffer full");
+
+            unsafe {
+                otMessageFree(msg);
+            }
+        } else {
+            mdns.changed.signal(());
+        }
+    }
+
+    unsafe extern "C" fn plat_c_mdns_register_callback(
+        instance: *mut otInstance,
+        request_id: u32,
+        error: otError,
+    ) {
+        let mut ot = Self::callback(instance);
+        let mdns = &mut ot.state().ot.mdns;
+
+        if mdns.register_id == Some(request_id) {
+            mdns.register_id = None;
+            mdns.register_done.signal(error);
+        }
+    }
+
+    unsafe extern "C" fn plat_c_mdns_browse_callback(
+        instance: *mut otInstance,
+        result: *const otPlatDnssdBrowseResult,
+    ) {
+        let result = unsafe { &*result };
+
+        let (Ok(service_type), Ok(subtype), Ok(service_instance)) = (
+            c_str(result.mServiceType),
+            if result.mSubTypeLabel.is_null() {
+                Ok(None)
+            } else {
+                c_str(result.mSubTypeLabel).map(Some)
+            },
+            c_str(result.mServiceInstance),
+        ) else {
+            warn!("Invalid UTF-8 in mDNS name, skipping result");
+            return;
+        };
+
+        Self::mdns_dispatch(
+            instance,
+            MdnsQueryResult::Browse(MdnsBrowseResult {
+                service_type,
+                subtype,
+                instance: service_instance,
+                ttl: result.mTtl,
+            }),
+        );
+    }
+
+    unsafe extern "C" fn plat_c_mdns_srv_callback(
+        instance: *mut otInstance,
+        result: *const otPlatDnssdSrvResult,
+    ) {
+        let result = unsafe { &*result };
+
+        let (Ok(service_instance), Ok(service_type), Ok(host_name)) = (
+            c_str(result.mServiceInstance),
+            c_str(result.mServiceType),
+            c_str(result.mHostName),
+        ) else {
+            warn!("Invalid UTF-8 in mDNS name, skipping result");
+            return;
+        };
+
+        Self::mdns_dispatch(
+            instance,
+            MdnsQueryResult::Srv(MdnsSrvResult {
+                instance: service_instance,
+                service_type,
+                host_name,
+                port: result.mPort,
+                priority: result.mPriority,
+                weight: result.mWeight,
+                ttl: result.mTtl,
+            }),
+        );
+    }
+
+    unsafe extern "C" fn plat_c_mdns_txt_callback(
+        instance: *mut otInstance,
+        result: *const otPlatDnssdTxtResult,
+    ) {
+        let result = unsafe { &*result };
+
+        let txt_data = if result.mTxtData.is_null() {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(result.mTxtData, result.mTxtDataLength as _) }
+        };
+
+        let (Ok(service_instance), Ok(service_type)) =
+            (c_str(result.mServiceInstance), c_str(result.mServiceType))
+        else {
+            warn!("Invalid UTF-8 in mDNS name, skipping result");
+            return;
+        };
+
+        Self::mdns_dispatch(
+            instance,
+            MdnsQueryResult::Txt(MdnsTxtResult {
+                instance: service_instance,
+                service_type,
+                txt_data,
+                ttl: result.mTtl,
+            }),
+        );
+    }
+
+    unsafe extern "C" fn plat_c_mdns_ip6_callback(
+        instance: *mut otInstance,
+        result: *const otPlatDnssdAddressResult,
+    ) {
+        let result = unsafe { &*result };
+
+        let addrs = if result.mAddresses.is_null() {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(result.mAddresses, result.mAddressesLength as _) }
+        };
+
+        let Ok(host_name) = c_str(result.mHostName) else {
+            warn!("Invalid UTF-8 in mDNS name, skipping result");
+            return;
+        };
+
+        Self::mdns_dispatch(
+            instance,
+            MdnsQueryResult::Ip6(MdnsAddressResult { host_name, addrs }),
+        );
+    }
+
+    /// Common tail for the mDNS query trampolines: invoke the installed user closure
+    /// with the result, and signal the awaiting future once the closure reports it is done.
+    fn mdns_dispatch(instance: *mut otInstance, result: MdnsQueryResult) {
+        let mut ot = Self::callback(instance);
+        let query = &mut ot.state().ot.mdns.queries[result.kind()];
+
+        if let Some(f) = query.callback.as_mut() {
+            if f(&result) {
+                query.callback = None;
+                query.done.signal(());
+            }
+        }
+    }
+}
+
+/// A stack-allocated buffer of null-terminated names to hand to the C API.
+struct NameBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> NameBuf<N> {
+    const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Append `name` to the buffer, returning a pointer to its null-terminated copy.
+    ///
+    /// The pointer stays valid for as long as the buffer is not moved or dropped.
+    fn push(&mut self, name: &str) -> Result<*const c_char, OtError> {
+        // Reject interior NUL bytes: they would truncate the name at the C boundary
+        if name.as_bytes().contains(&0) {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let start = self.len;
+        let end = start + name.len();
+
+        if end >= N {
+            Err(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+        }
+
+        self.buf[start..end].copy_from_slice(name.as_bytes());
+        self.buf[end] = 0;
+        self.len = end + 1;
+
+        Ok(self.buf[start..].as_ptr() as _)
+    }
+}
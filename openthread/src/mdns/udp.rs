@@ -0,0 +1,185 @@
+//! [`UdpMdnsSocket`]: a host (`std`) [`MdnsSocket`] over the UDP stack of the host, so
+//! that the mDNS module of OpenThread can be exercised on a Linux/macOS host.
+//!
+//! Mirrors the mDNS socket of OpenThread's POSIX platform: a multicast socket bound to the
+//! mDNS port (shared with any other mDNS responder running on the host, e.g. Avahi, via
+//! `SO_REUSEADDR` / `SO_REUSEPORT`) and joined to the mDNS group on the infrastructure
+//! network interface, plus a unicast socket bound to an ephemeral port for the unicast
+//! traffic. Messages received on the former are reported as multicast, on the latter as unicast.
+//!
+//! Currently IPv6 only, and **Unix only** (`#[cfg(unix)]`).
+
+#![cfg(unix)]
+
+// The crate is `#![no_std]`; this module (gated on the `std` feature) opts back
+// into `std`.
+extern crate std;
+
+use core::net::{Ipv6Addr, SocketAddrV6};
+
+use core::mem::size_of;
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::os::fd::{AsFd, AsRawFd};
+
+use async_io::Async;
+
+use nix::libc;
+use nix::sys::socket::{self, sockopt, AddressFamily, SockFlag, SockType, SockaddrIn6};
+
+use embassy_futures::select::{select, Either};
+
+use super::{MdnsSocket, MDNS_IP6_GROUP, MDNS_PORT};
+
+/// An [`MdnsSocket`] over the UDP stack of the host (Unix).
+///
+/// The sockets are opened when OpenThread starts listening on an infrastructure network
+/// interface, and closed when it stops.
+pub struct UdpMdnsSocket {
+    sockets: Option<Sockets>,
+}
+
+/// The open sockets of a listening [`UdpMdnsSocket`].
+struct Sockets {
+    /// Bound to the mDNS port and joined to the mDNS group; sends the multicast messages.
+    mcast: Async<UdpSocket>,
+    /// Bound to an ephemeral port; sends the unicast messages.
+    ucast: Async<UdpSocket>,
+    /// The infrastructure network interface index.
+    infra_if_index: u32,
+}
+
+impl UdpMdnsSocket {
+    /// Create a new, not yet listening, `UdpMdnsSocket`.
+    pub const fn new() -> Self {
+        Self { sockets: None }
+    }
+
+    fn open(infra_if_index: u32) -> io::Result<Sockets> {
+        let mcast = bind(
+            SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, MDNS_PORT, 0, 0),
+            true,
+        )?;
+
+        mcast.join_multicast_v6(&MDNS_IP6_GROUP, infra_if_index)?;
+        mcast.set_multicast_loop_v6(false)?;
+
+        let ucast = bind(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0), false)?;
+
+        for socket in [&mcast, &ucast] {
+            // RFC 6762: mDNS messages are sent with a hop limit of 255
+            socket::setsockopt(socket, sockopt::Ipv6MulticastHops, &255)?;
+
+            // Not covered by `nix::sys::socket::sockopt` (or only on some of the Unix platforms)
+            set_ipv6_opt(socket, libc::IPV6_MULTICAST_IF, infra_if_index as _)?;
+            set_ipv6_opt(socket, libc::IPV6_UNICAST_HOPS, 255)?;
+        }
+
+        Ok(Sockets {
+            mcast: Async::new(mcast)?,
+            ucast: Async::new(ucast)?,
+            infra_if_index,
+        })
+    }
+}
+
+impl Default for UdpMdnsSocket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MdnsSocket for UdpMdnsSocket {
+    type Error = io::Error;
+
+    async fn set_listening(&mut self, infra_if_index: Option<u32>) -> Result<(), Self::Error> {
+        self.sockets = None;
+
+        if let Some(infra_if_index) = infra_if_index {
+            self.sockets = Some(Self::open(infra_if_index)?);
+        }
+
+        Ok(())
+    }
+
+    async fn send(&mut self, data: &[u8], dest: Option<SocketAddrV6>) -> Result<(), Self::Error> {
+        let sockets = self.sockets.as_ref().ok_or(io::ErrorKind::NotConnected)?;
+
+        if let Some(dest) = dest {
+            sockets.ucast.send_to(data, dest).await?;
+        } else {
+            let dest = SocketAddrV6::new(MDNS_IP6_GROUP, MDNS_PORT, 0, sockets.infra_if_index);
+
+            sockets.mcast.send_to(data, dest).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, SocketAddrV6, bool), Self::Error> {
+        let sockets = self.sockets.as_ref().ok_or(io::ErrorKind::NotConnected)?;
+
+        loop {
+            let (socket, unicast) =
+                match select(sockets.mcast.readable(), sockets.ucast.readable()).await {
+                    Either::First(res) => (res.map(|_| &sockets.mcast)?, false),
+                    Either::Second(res) => (res.map(|_| &sockets.ucast)?, true),
+                };
+
+            match socket.get_ref().recv_from(buf) {
+                Ok((len, SocketAddr::V6(src))) => break Ok((len, src, unicast)),
+                Ok((len, SocketAddr::V4(src))) => {
+                    break Ok((
+                        len,
+                        SocketAddrV6::new(src.ip().to_ipv6_mapped(), src.port(), 0, 0),
+                        unicast,
+                    ))
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => break Err(e),
+            }
+        }
+    }
+}
+
+/// Create a UDP socket bound to `addr`, optionally allowing the address to be shared
+/// with other sockets (`SO_REUSEADDR` / `SO_REUSEPORT`).
+///
+/// `std` cannot set socket options before binding, hence `nix`.
+fn bind(addr: SocketAddrV6, reuse: bool) -> io::Result<UdpSocket> {
+    let fd = socket::socket(
+        AddressFamily::Inet6,
+        SockType::Datagram,
+        SockFlag::empty(),
+        None,
+    )?;
+
+    socket::setsockopt(&fd, sockopt::Ipv6V6Only, &true)?;
+
+    if reuse {
+        socket::setsockopt(&fd, sockopt::ReuseAddr, &true)?;
+        socket::setsockopt(&fd, sockopt::ReusePort, &true)?;
+    }
+
+    socket::bind(fd.as_raw_fd(), &SockaddrIn6::from(addr))?;
+
+    Ok(UdpSocket::from(fd))
+}
+
+fn set_ipv6_opt(fd: &impl AsFd, opt: libc::c_int, value: libc::c_int) -> io::Result<()> {
+    if unsafe {
+        libc::setsockopt(
+            fd.as_fd().as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            opt,
+            &value as *const _ as *const libc::c_void,
+            size_of::<libc::c_int>() as _,
+        )
+    } < 0
+    {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
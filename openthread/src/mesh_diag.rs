@@ -0,0 +1,463 @@
+//! Mesh Diagnostics API (`OPENTHREAD_CONFIG_MESH_DIAG_ENABLE` / the `mesh-diag` feature).
+//!
+//! Allows discovering the topology of the Thread mesh: every router of the partition, with
+//! its link qualities to the other routers and - optionally - its IPv6 addresses and children
+//! ([`OpenThread::mesh_diag_discover_topology`]), as well as querying the IPv6 addresses of the
+//! MTD children of a router ([`OpenThread::mesh_diag_query_children_ip6_addrs`]).
+//!
+//! The results are handed to a user closure as borrowed accessors, because the underlying
+//! OpenThread data is only valid for the duration of the callback and must not be retained.
+//!
+//! Only one mesh diagnostic query may be in flight at a time per `OpenThread` instance;
+//! starting another while one is pending returns [`otError`] `BUSY`.
+//!
+//! NOTE: Like [`OpenThread::scan`], the futures returned by these query methods are currently
+//! NOT `core::mem::forget`-safe. Do not `mem::forget` them.
+//!
+//! [`otError`]: crate::sys::otError
+
+use core::ffi::c_void;
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+
+use crate::signal::Signal;
+use crate::sys::{
+    otError, otError_OT_ERROR_BUSY, otError_OT_ERROR_NONE, otError_OT_ERROR_PENDING, otInstance,
+    otIp6Address, otMeshDiagCancel, otMeshDiagChildInfo, otMeshDiagChildIterator,
+    otMeshDiagDiscoverConfig, otMeshDiagDiscoverTopology, otMeshDiagGetNextChildInfo,
+    otMeshDiagGetNextIp6Address, otMeshDiagGetResponseTimeout, otMeshDiagIp6AddrIterator,
+    otMeshDiagQueryChildrenIp6Addrs, otMeshDiagRouterInfo, otMeshDiagSetResponseTimeout,
+    OT_MESH_DIAG_VERSION_UNKNOWN,
+};
+use crate::{ot, OpenThread, OtContext, OtError};
+
+/// What to discover in addition to the routers and their links,
+/// when calling [`OpenThread::mesh_diag_discover_topology`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeshDiagDiscoverConfig {
+    /// Whether to discover the IPv6 addresses of each router.
+    pub ip6_addrs: bool,
+    /// Whether to discover the children of each router.
+    pub children: bool,
+}
+
+impl MeshDiagDiscoverConfig {
+    fn to_ot(self) -> otMeshDiagDiscoverConfig {
+        let mut config = otMeshDiagDiscoverConfig::default();
+
+        config.set_mDiscoverIp6Addresses(self.ip6_addrs);
+        config.set_mDiscoverChildTable(self.children);
+
+        config
+    }
+}
+
+/// A borrowed accessor over a list of IPv6 addresses discovered by the Mesh Diagnostics.
+///
+/// Valid only within the query callback closure; do not retain it.
+pub struct MeshDiagIp6Addrs(*mut otMeshDiagIp6AddrIterator);
+
+impl MeshDiagIp6Addrs {
+    /// Gets the IPv6 addresses
+    ///
+    /// The addresses can only be iterated once.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each address. Once called for all addresses,
+    ///   the closure will be called with `None`.
+    pub fn addrs<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<Ipv6Addr>) -> Result<(), OtError>,
+    {
+        if !self.0.is_null() {
+            let mut addr = otIp6Address::default();
+
+            while unsafe { otMeshDiagGetNextIp6Address(self.0, &mut addr) } == otError_OT_ERROR_NONE
+            {
+                f(Some(Ipv6Addr::from(unsafe { addr.mFields.m8 })))?;
+            }
+        }
+
+        f(None)
+    }
+}
+
+/// A child of a router discovered by [`OpenThread::mesh_diag_discover_topology`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeshDiagChildInfo {
+    /// The RLOC16 of the child.
+    pub rloc16: u16,
+    /// The incoming link quality (0..=3) from the child to its parent.
+    pub link_quality: u8,
+    /// Whether the child keeps its receiver on when idle.
+    pub rx_on_when_idle: bool,
+    /// Whether the child is a Full Thread Device.
+    pub full_thread_device: bool,
+    /// Whether the child requests the full Network Data.
+    pub full_network_data: bool,
+    /// Whether the child is this device.
+    pub is_this_device: bool,
+    /// Whether the child acts as a border router providing external route entries.
+    pub is_border_router: bool,
+}
+
+impl From<&otMeshDiagChildInfo> for MeshDiagChildInfo {
+    fn from(info: &otMeshDiagChildInfo) -> Self {
+        Self {
+            rloc16: info.mRloc16,
+            link_quality: info.mLinkQuality,
+            rx_on_when_idle: info.mMode.mRxOnWhenIdle(),
+            full_thread_device: info.mMode.mDeviceType(),
+            full_network_data: info.mMode.mNetworkData(),
+            is_this_device: info.mIsThisDevice(),
+            is_border_router: info.mIsBorderRouter(),
+        }
+    }
+}
+
+/// A borrowed accessor over a router discovered by [`OpenThread::mesh_diag_discover_topology`].
+///
+/// Valid only within the discovery callback closure; do not retain it.
+pub struct MeshDiagRouterInfo(*const otMeshDiagRouterInfo);
+
+impl MeshDiagRouterInfo {
+    /// The extended MAC address of the router.
+    pub fn ext_address(&self) -> u64 {
+        u64::from_be_bytes(self.info().mExtAddress.m8)
+    }
+
+    /// The RLOC16 of the router.
+    pub fn rloc16(&self) -> u16 {
+        self.info().mRloc16
+    }
+
+    /// The Router ID of the router.
+    pub fn router_id(&self) -> u8 {
+        self.info().mRouterId
+    }
+
+    /// The Thread version of the router, if known.
+    pub fn version(&self) -> Option<u16> {
+        let version = self.info().mVersion;
+
+        (version != OT_MESH_DIAG_VERSION_UNKNOWN as u16).then_some(version)
+    }
+
+    /// Whether the router is this device.
+    pub fn is_this_device(&self) -> bool {
+        self.info().mIsThisDevice()
+    }
+
+    /// Whether the router is the parent of this device (when this device is a child).
+    pub fn is_this_device_parent(&self) -> bool {
+        self.info().mIsThisDeviceParent()
+    }
+
+    /// Whether the router is the leader.
+    pub fn is_leader(&self) -> bool {
+        self.info().mIsLeader()
+    }
+
+    /// Whether the router acts as a border router providing external route entries.
+    pub fn is_border_router(&self) -> bool {
+        self.info().mIsBorderRouter()
+    }
+
+    /// The incoming link quality (0..=3) the router sees from the router with Router ID `router_id`,
+    /// with zero meaning that there is no link between the two routers.
+    ///
+    /// The outgoing link quality of a link is the incoming link quality reported
+    /// by the router at the other end of the link.
+    pub fn link_quality(&self, router_id: u8) -> u8 {
+        self.info()
+            .mLinkQualities
+            .get(router_id as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// An iterator over the links of the router to the other routers,
+    /// as `(Router ID, incoming link quality)` pairs.
+    pub fn links(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.info()
+            .mLinkQualities
+            .iter()
+            .enumerate()
+            .filter(|(_, link_quality)| **link_quality > 0)
+            .map(|(router_id, link_quality)| (router_id as u8, *link_quality))
+    }
+
+    /// The IPv6 addresses of the router.
+    ///
+    /// Empty unless [`MeshDiagDiscoverConfig::ip6_addrs`] was requested.
+    pub fn ip6_addrs(&self) -> MeshDiagIp6Addrs {
+        MeshDiagIp6Addrs(self.info().mIp6AddrIterator)
+    }
+
+    /// Gets the children of the router
+    ///
+    /// The children can only be iterated once, and only if [`MeshDiagDiscoverConfig::children`]
+    /// was requested.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each child. Once called for all children,
+    ///   the closure will be called with `None`.
+    pub fn children<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&MeshDiagChildInfo>) -> Result<(), OtError>,
+    {
+        let iter: *mut otMeshDiagChildIterator = self.info().mChildIterator;
+
+        if !iter.is_null() {
+            let mut info = otMeshDiagChildInfo::default();
+
+            while unsafe { otMeshDiagGetNextChildInfo(iter, &mut info) } == otError_OT_ERROR_NONE {
+                f(Some(&(&info).into()))?;
+            }
+        }
+
+        f(None)
+    }
+
+    fn info(&self) -> &otMeshDiagRouterInfo {
+        unsafe { &*self.0 }
+    }
+}
+
+/// The state of the in-flight Mesh Diagnostics query, if any.
+pub(crate) struct MeshDiagState<'a> {
+    /// The callback to invoke for each discovered router. Holds a lifetime-erased
+    /// reference to the user closure for the duration of the in-flight discovery.
+    discover_callback: Option<&'a mut dyn FnMut(&MeshDiagRouterInfo)>,
+    /// The callback to invoke for each MTD child with its IPv6 addresses. Holds a
+    /// lifetime-erased reference to the user closure for the duration of the in-flight query.
+    #[allow(clippy::type_complexity)]
+    child_ip6_addrs_callback: Option<&'a mut dyn FnMut(u16, &MeshDiagIp6Addrs)>,
+    /// Carries the terminal `otError` of the in-flight query back to the awaiting future.
+    done: Signal<otError>,
+    /// Whether OpenThread has reported the in-flight query as finished.
+    ///
+    /// The callback slots are only released by the scopeguard of the awaiting future, so that
+    /// no new query can start (and reset `done`) before that future has picked up its result.
+    completed: bool,
+}
+
+impl MeshDiagState<'_> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            discover_callback: None,
+            child_ip6_addrs_callback: None,
+            done: Signal::new(),
+            completed: false,
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.discover_callback.is_some() || self.child_ip6_addrs_callback.is_some()
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Discover the topology of the Thread mesh: `f` is invoked with each router of the partition.
+    ///
+    /// The future completes once all routers were reported, or with a `RESPONSE_TIMEOUT` error
+    /// if one or more routers did not respond. Dropping the future cancels the discovery.
+    ///
+    /// NOTE: The future returned by this method is currently NOT `core::mem::forget` safe.
+    /// Its constructor MUST run, so don't call `core::mem::forget` on it.
+    pub async fn mesh_diag_discover_topology<F>(
+        &self,
+        config: &MeshDiagDiscoverConfig,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&MeshDiagRouterInfo),
+    {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if state.ot.mesh_diag.is_busy() {
+                warn!("Another mesh diagnostic query in progress");
+                return Err(OtError::new(otError_OT_ERROR_BUSY));
+            }
+
+            // Clear any stale completion left over from a prior query whose future
+            // was dropped after the callback signalled but before `poll_wait` consumed it.
+            state.ot.mesh_diag.done.reset();
+            state.ot.mesh_diag.completed = false;
+
+            let f: &mut dyn FnMut(&MeshDiagRouterInfo) = &mut f;
+            state.ot.mesh_diag.discover_callback = Some(unsafe {
+                core::mem::transmute::<
+                    &mut dyn FnMut(&MeshDiagRouterInfo),
+                    &'a mut dyn FnMut(&MeshDiagRouterInfo),
+                >(f)
+            });
+
+            let res = ot!(unsafe {
+                otMeshDiagDiscoverTopology(
+                    state.ot.instance,
+                    &config.to_ot(),
+                    Some(OtContext::plat_c_mesh_diag_discover_callback),
+                    state.ot.instance as *mut _,
+                )
+            });
+
+            if res.is_err() {
+                // Discovery never started; release the slot.
+                state.ot.mesh_diag.discover_callback = None;
+                res?;
+            }
+        }
+
+        // See the forget-safety note in `OpenThread::scan` - the same caveat
+        // applies to the closure reference stashed here.
+        let _guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            state.ot.mesh_diag.discover_callback = None;
+
+            if !state.ot.mesh_diag.completed {
+                unsafe { otMeshDiagCancel(state.ot.instance) };
+            }
+        });
+
+        let error =
+            poll_fn(move |cx| self.activate().state().ot.mesh_diag.done.poll_wait(cx)).await;
+
+        ot!(error)
+    }
+
+    /// Query a router (identified by `parent_rloc16`) for the IPv6 addresses of all its MTD children:
+    /// `f` is invoked with the RLOC16 and the IPv6 addresses of each MTD child.
+    ///
+    /// The future completes once all children were reported. Dropping the future cancels the query.
+    ///
+    /// NOTE: The future returned by this method is currently NOT `core::mem::forget` safe.
+    /// Its constructor MUST run, so don't call `core::mem::forget` on it.
+    pub async fn mesh_diag_query_children_ip6_addrs<F>(
+        &self,
+        parent_rloc16: u16,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(u16, &MeshDiagIp6Addrs),
+    {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if state.ot.mesh_diag.is_busy() {
+                warn!("Another mesh diagnostic query in progress");
+                return Err(OtError::new(otError_OT_ERROR_BUSY));
+            }
+
+            state.ot.mesh_diag.done.reset();
+            state.ot.mesh_diag.completed = false;
+
+            let f: &mut dyn FnMut(u16, &MeshDiagIp6Addrs) = &mut f;
+            state.ot.mesh_diag.child_ip6_addrs_callback = Some(unsafe {
+                core::mem::transmute::<
+                    &mut dyn FnMut(u16, &MeshDiagIp6Addrs),
+                    &'a mut dyn FnMut(u16, &MeshDiagIp6Addrs),
+                >(f)
+            });
+
+            let res = ot!(unsafe {
+                otMeshDiagQueryChildrenIp6Addrs(
+                    state.ot.instance,
+                    parent_rloc16,
+                    Some(OtContext::plat_c_mesh_diag_child_ip6_addrs_callback),
+                    state.ot.instance as *mut _,
+                )
+            });
+
+            if res.is_err() {
+                state.ot.mesh_diag.child_ip6_addrs_callback = None;
+                res?;
+            }
+        }
+
+        let _guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            state.ot.mesh_diag.child_ip6_addrs_callback = None;
+
+            if !state.ot.mesh_diag.completed {
+                unsafe { otMeshDiagCancel(state.ot.instance) };
+            }
+        });
+
+        let error =
+            poll_fn(move |cx| self.activate().state().ot.mesh_diag.done.poll_wait(cx)).await;
+
+        ot!(error)
+    }
+
+    /// Set the response timeout (in milliseconds) of the future mesh diagnostic queries.
+    ///
+    /// The value is clamped by OpenThread to the range [50 ms, 10 min].
+    pub fn mesh_diag_set_response_timeout(&self, timeout_millis: u32) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otMeshDiagSetResponseTimeout(state.ot.instance, timeout_millis) }
+    }
+
+    /// Return the response timeout (in milliseconds) of the mesh diagnostic queries.
+    pub fn mesh_diag_response_timeout(&self) -> u32 {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otMeshDiagGetResponseTimeout(state.ot.instance) }
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_mesh_diag_discover_callback(
+        error: otError,
+        router_info: *mut otMeshDiagRouterInfo,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+        let state = ot.state();
+
+        if !router_info.is_null() {
+            if let Some(f) = state.ot.mesh_diag.discover_callback.as_mut() {
+                f(&MeshDiagRouterInfo(router_info));
+            }
+        }
+
+        if error != otError_OT_ERROR_PENDING {
+            state.ot.mesh_diag.completed = true;
+            state.ot.mesh_diag.done.signal(error);
+        }
+    }
+
+    unsafe extern "C" fn plat_c_mesh_diag_child_ip6_addrs_callback(
+        error: otError,
+        child_rloc16: u16,
+        ip6_addr_iter: *mut otMeshDiagIp6AddrIterator,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+        let state = ot.state();
+
+        // No iterator on a timeout, or when the parent has no MTD children at all
+        if !ip6_addr_iter.is_null() {
+            if let Some(f) = state.ot.mesh_diag.child_ip6_addrs_callback.as_mut() {
+                f(child_rloc16, &MeshDiagIp6Addrs(ip6_addr_iter));
+            }
+        }
+
+        if error != otError_OT_ERROR_PENDING {
+            state.ot.mesh_diag.completed = true;
+            state.ot.mesh_diag.done.signal(error);
+        }
+    }
+}
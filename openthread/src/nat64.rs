@@ -1,13 +1,46 @@
+//! NAT64 API.
+//!
+//! Address synthesis and extraction (RFC 6052) are always available.
+//!
+//! With the `nat64` feature, the NAT64 translator of OpenThread is available too: it translates
+//! the IPv6 packets of the Thread network destined to the preferred NAT64 prefix to IPv4 packets
+//! (with a source address from the configured IPv4 CIDR pool), which are then received by the
+//! application with [`OpenThread::nat64_rx`] and forwarded to the IPv4 infrastructure network.
+//! In the other direction, IPv4 packets from the infrastructure network are ingressed with
+//! [`OpenThread::nat64_tx`].
+
+#[cfg(feature = "nat64")]
+use core::future::poll_fn;
 use core::{
     fmt,
     net::{Ipv4Addr, Ipv6Addr},
 };
 
+#[cfg(feature = "nat64")]
+use openthread_sys::{
+    otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND, otError_OT_ERROR_NO_BUFS, otInstance,
+    otIp4Cidr, otIp4NewMessage, otMessage, otMessageAppend, otMessageFree,
+    otMessagePriority_OT_MESSAGE_PRIORITY_NORMAL, otMessageRead, otMessageSettings,
+    otNat64AddressMapping, otNat64AddressMappingIterator, otNat64ClearIp4Cidr, otNat64Counters,
+    otNat64DropReason_OT_NAT64_DROP_REASON_ILLEGAL_PACKET,
+    otNat64DropReason_OT_NAT64_DROP_REASON_NO_MAPPING,
+    otNat64DropReason_OT_NAT64_DROP_REASON_UNKNOWN,
+    otNat64DropReason_OT_NAT64_DROP_REASON_UNSUPPORTED_PROTO, otNat64ErrorCounters, otNat64GetCidr,
+    otNat64GetCounters, otNat64GetErrorCounters, otNat64GetNextAddressMapping,
+    otNat64GetTranslatorState, otNat64InitAddressMappingIterator, otNat64ProtocolCounters,
+    otNat64Send, otNat64SetEnabled, otNat64SetIp4Cidr, otNat64State,
+    otNat64State_OT_NAT64_STATE_ACTIVE, otNat64State_OT_NAT64_STATE_DISABLED,
+    otNat64State_OT_NAT64_STATE_IDLE, otNat64State_OT_NAT64_STATE_NOT_RUNNING,
+};
 use openthread_sys::{
     otIp4Address, otIp4Address__bindgen_ty_1, otIp4ExtractFromIp6Address, otIp6Address,
     otIp6Address__bindgen_ty_1, otNat64SynthesizeIp6Address,
 };
 
+#[cfg(feature = "nat64")]
+use crate::fmt::Bytes;
+#[cfg(feature = "nat64")]
+use crate::OtContext;
 use crate::{ot, OpenThread, OtError};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -50,6 +83,431 @@ impl OpenThread<'_> {
 
         Ok(ot_ipv6_to_ipv6(&ipv6))
     }
+
+    /// Enable or disable the NAT64 functions of OpenThread
+    /// (the translator and - with the `border-routing` feature - the NAT64 prefix manager).
+    ///
+    /// Disabling the translator resets its address mapping table.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_set_enabled(&self, enable: bool) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNat64SetEnabled(state.ot.instance, enable) }
+    }
+
+    /// Return the state of the NAT64 translator.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_translator_state(&self) -> Nat64State {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNat64GetTranslatorState(state.ot.instance) }.into()
+    }
+
+    /// Return the state of the NAT64 prefix manager, i.e. of the publishing of a NAT64 prefix
+    /// to the Network Data by the Border Routing Manager.
+    #[cfg(all(feature = "nat64", feature = "border-routing"))]
+    pub fn nat64_prefix_manager_state(&self) -> Nat64State {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { crate::sys::otNat64GetPrefixManagerState(state.ot.instance) }.into()
+    }
+
+    /// Set the IPv4 CIDR from which the NAT64 translator allocates the source addresses
+    /// of the translated (outgoing) IPv4 packets.
+    ///
+    /// Setting the CIDR resets the translator, expiring all existing address mappings.
+    ///
+    /// Arguments:
+    /// - `cidr`: The IPv4 address of the CIDR block and its (non-zero) prefix length
+    #[cfg(feature = "nat64")]
+    pub fn nat64_set_cidr(&self, cidr: (Ipv4Addr, u8)) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let cidr = otIp4Cidr {
+            mAddress: ipv4_to_ot_ipv4(&cidr.0),
+            mLength: cidr.1,
+        };
+
+        ot!(unsafe { otNat64SetIp4Cidr(state.ot.instance, &cidr) })
+    }
+
+    /// Clear the IPv4 CIDR of the NAT64 translator.
+    ///
+    /// Clearing the CIDR resets the translator, expiring all existing address mappings.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_clear_cidr(&self) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNat64ClearIp4Cidr(state.ot.instance) }
+    }
+
+    /// Return the IPv4 CIDR of the NAT64 translator and its prefix length,
+    /// or `None` if no CIDR is configured.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_cidr(&self) -> Result<Option<(Ipv4Addr, u8)>, OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut cidr = otIp4Cidr::default();
+
+        match ot!(unsafe { otNat64GetCidr(state.ot.instance, &mut cidr) }) {
+            Ok(()) => Ok(Some((ot_ipv4_to_ipv4(&cidr.mAddress), cidr.mLength))),
+            Err(e) if e.into_inner() == otError_OT_ERROR_NOT_FOUND => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets the list of the active address mappings of the NAT64 translator
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each address mapping. Once called for all mappings,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_mappings<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&Nat64AddressMapping>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut iter = otNat64AddressMappingIterator::default();
+        let mut mapping = otNat64AddressMapping::default();
+
+        unsafe { otNat64InitAddressMappingIterator(state.ot.instance, &mut iter) };
+
+        while unsafe { otNat64GetNextAddressMapping(state.ot.instance, &mut iter, &mut mapping) }
+            == otError_OT_ERROR_NONE
+        {
+            f(Some(&(&mapping).into()))?;
+        }
+
+        f(None)
+    }
+
+    /// Return the packet counters of the NAT64 translator, per protocol.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_counters(&self) -> Nat64ProtocolCounters {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut counters = otNat64ProtocolCounters::default();
+
+        unsafe { otNat64GetCounters(state.ot.instance, &mut counters) };
+
+        (&counters).into()
+    }
+
+    /// Return the counters of the packets dropped by the NAT64 translator, per drop reason.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_error_counters(&self) -> Nat64ErrorCounters {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut counters = otNat64ErrorCounters::default();
+
+        unsafe { otNat64GetErrorCounters(state.ot.instance, &mut counters) };
+
+        Nat64ErrorCounters {
+            ip4_to_ip6: counters.mCount4To6.into(),
+            ip6_to_ip4: counters.mCount6To4.into(),
+        }
+    }
+
+    /// Enable or disable the reception of the IPv4 packets translated by the NAT64 translator.
+    ///
+    /// If not necessary, reception should be disabled, because this consumes memory.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_enable_rx(&self, enable: bool) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.ot.nat64_rx_enabled = enable;
+
+        if !enable {
+            if let Some(msg) = state.ot.nat64_rx.try_take() {
+                unsafe {
+                    otMessageFree(msg);
+                }
+            }
+        }
+    }
+
+    /// Receive an IPv4 packet translated by the NAT64 translator, to be forwarded
+    /// to the IPv4 infrastructure network.
+    /// If there is no packet available, this function will async-wait until a packet is available.
+    ///
+    /// Arguments:
+    /// - `buf`: A mutable reference to a buffer where the received packet will be stored.
+    ///
+    /// Returns:
+    /// - The length of the received packet.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    #[cfg(feature = "nat64")]
+    pub async fn nat64_rx(&self, buf: &mut [u8]) -> Result<usize, OtError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        trace!("Waiting for NAT64 IPv4 packet reception");
+
+        let msg = poll_fn(move |cx| self.activate().state().ot.nat64_rx.poll_wait(cx)).await;
+
+        let _ot = self.activate();
+
+        let len = unsafe { otMessageRead(msg, 0, buf.as_mut_ptr() as *mut _, buf.len() as _) as _ };
+
+        unsafe {
+            otMessageFree(msg);
+        }
+
+        trace!("Received NAT64 IPv4 packet: {}", Bytes(&buf[..len]));
+
+        Ok(len)
+    }
+
+    /// Transmit an IPv4 packet received from the IPv4 infrastructure network, by having
+    /// the NAT64 translator translate it to an IPv6 packet sent to the Thread network.
+    ///
+    /// Arguments:
+    /// - `packet`: The IPv4 packet to be translated and transmitted.
+    #[cfg(feature = "nat64")]
+    pub fn nat64_tx(&self, packet: &[u8]) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let msg = unsafe {
+            otIp4NewMessage(
+                state.ot.instance,
+                &otMessageSettings {
+                    mLinkSecurityEnabled: true,
+                    mPriority: otMessagePriority_OT_MESSAGE_PRIORITY_NORMAL as _,
+                },
+            )
+        };
+
+        if msg.is_null() {
+            return Err(OtError::new(otError_OT_ERROR_NO_BUFS));
+        }
+
+        if let Err(e) =
+            ot!(unsafe { otMessageAppend(msg, packet.as_ptr() as *const _, packet.len() as _) })
+        {
+            unsafe {
+                otMessageFree(msg);
+            }
+
+            return Err(e);
+        }
+
+        // `otNat64Send` takes ownership of the message, even on error
+        ot!(unsafe { otNat64Send(state.ot.instance, msg) })?;
+
+        trace!("Transmitted NAT64 IPv4 packet: {}", Bytes(packet));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "nat64")]
+impl OtContext<'_> {
+    pub(crate) unsafe extern "C" fn plat_c_nat64_receive_callback(
+        msg: *mut otMessage,
+        context: *mut core::ffi::c_void,
+    ) {
+        let instance = context as *mut otInstance;
+
+        Self::callback(instance).plat_nat64_received(msg);
+    }
+
+    fn plat_nat64_received(&mut self, msg: *mut otMessage) {
+        trace!("Got NAT64 IPv4 packet");
+
+        let state = self.state();
+
+        if !state.ot.nat64_rx_enabled || state.ot.nat64_rx.signaled() {
+            if state.ot.nat64_rx_enabled {
+                // Drop the message because the previous one is not consumed yet
+                warn!("Dropping RX NAT64 IPv4 message, buffer full");
+            } else {
+                // Drop the message because the RX is disabled
+                trace!("Dropping RX NAT64 IPv4 message, RX disabled");
+            }
+
+            unsafe {
+                otMessageFree(msg);
+            }
+        } else {
+            state.ot.nat64_rx.signal(msg);
+        }
+    }
+}
+
+/// The state of the NAT64 translator or of the NAT64 prefix manager.
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Nat64State {
+    /// Disabled
+    Disabled,
+    /// Enabled, but not running (e.g. the translator has no valid CIDR or NAT64 prefix)
+    NotRunning,
+    /// Enabled, but idle (e.g. the prefix manager is not publishing a NAT64 prefix)
+    Idle,
+    /// Enabled and active (translating packets / publishing a NAT64 prefix)
+    Active,
+    /// Some other state
+    Other(otNat64State),
+}
+
+#[cfg(feature = "nat64")]
+impl From<otNat64State> for Nat64State {
+    #[allow(non_upper_case_globals)]
+    fn from(value: otNat64State) -> Self {
+        match value {
+            otNat64State_OT_NAT64_STATE_DISABLED => Self::Disabled,
+            otNat64State_OT_NAT64_STATE_NOT_RUNNING => Self::NotRunning,
+            otNat64State_OT_NAT64_STATE_IDLE => Self::Idle,
+            otNat64State_OT_NAT64_STATE_ACTIVE => Self::Active,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// The packet counters of the NAT64 translator, in both directions.
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nat64Counters {
+    /// Number of packets translated from IPv4 to IPv6.
+    pub ip4_to_ip6_packets: u64,
+    /// Number of bytes translated from IPv4 to IPv6.
+    pub ip4_to_ip6_bytes: u64,
+    /// Number of packets translated from IPv6 to IPv4.
+    pub ip6_to_ip4_packets: u64,
+    /// Number of bytes translated from IPv6 to IPv4.
+    pub ip6_to_ip4_bytes: u64,
+}
+
+#[cfg(feature = "nat64")]
+impl From<&otNat64Counters> for Nat64Counters {
+    fn from(c: &otNat64Counters) -> Self {
+        Self {
+            ip4_to_ip6_packets: c.m4To6Packets,
+            ip4_to_ip6_bytes: c.m4To6Bytes,
+            ip6_to_ip4_packets: c.m6To4Packets,
+            ip6_to_ip4_bytes: c.m6To4Bytes,
+        }
+    }
+}
+
+/// The packet counters of the NAT64 translator, per protocol.
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nat64ProtocolCounters {
+    /// The counters of all packets.
+    pub total: Nat64Counters,
+    /// The counters of the ICMP packets.
+    pub icmp: Nat64Counters,
+    /// The counters of the UDP packets.
+    pub udp: Nat64Counters,
+    /// The counters of the TCP packets.
+    pub tcp: Nat64Counters,
+}
+
+#[cfg(feature = "nat64")]
+impl From<&otNat64ProtocolCounters> for Nat64ProtocolCounters {
+    fn from(c: &otNat64ProtocolCounters) -> Self {
+        Self {
+            total: (&c.mTotal).into(),
+            icmp: (&c.mIcmp).into(),
+            udp: (&c.mUdp).into(),
+            tcp: (&c.mTcp).into(),
+        }
+    }
+}
+
+/// The counters of the packets dropped by the NAT64 translator, per drop reason.
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nat64DropCounters {
+    /// Packets dropped for an unknown reason.
+    pub unknown: u64,
+    /// Packets dropped because of a failure to parse them.
+    pub illegal_packet: u64,
+    /// Packets dropped because of an unsupported IP protocol.
+    pub unsupported_proto: u64,
+    /// Packets dropped because no address mapping was found, or none could be created.
+    pub no_mapping: u64,
+}
+
+#[cfg(feature = "nat64")]
+impl From<[u64; 4]> for Nat64DropCounters {
+    fn from(c: [u64; 4]) -> Self {
+        Self {
+            unknown: c[otNat64DropReason_OT_NAT64_DROP_REASON_UNKNOWN as usize],
+            illegal_packet: c[otNat64DropReason_OT_NAT64_DROP_REASON_ILLEGAL_PACKET as usize],
+            unsupported_proto: c[otNat64DropReason_OT_NAT64_DROP_REASON_UNSUPPORTED_PROTO as usize],
+            no_mapping: c[otNat64DropReason_OT_NAT64_DROP_REASON_NO_MAPPING as usize],
+        }
+    }
+}
+
+/// The counters of the packets dropped by the NAT64 translator, in both directions.
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nat64ErrorCounters {
+    /// The packets dropped when translating from IPv4 to IPv6.
+    pub ip4_to_ip6: Nat64DropCounters,
+    /// The packets dropped when translating from IPv6 to IPv4.
+    pub ip6_to_ip4: Nat64DropCounters,
+}
+
+/// An active address mapping of the NAT64 translator, as returned by [`OpenThread::nat64_mappings`].
+#[cfg(feature = "nat64")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nat64AddressMapping {
+    /// The unique ID of the mapping
+    pub id: u64,
+    /// The IPv4 address of the mapping
+    pub ip4: Ipv4Addr,
+    /// The IPv6 address of the mapping
+    pub ip6: Ipv6Addr,
+    /// The source port or ICMP ID of the IPv6 side
+    pub src_port_or_id: u16,
+    /// The translated port or ICMP ID of the IPv4 side
+    pub translated_port_or_id: u16,
+    /// The remaining time before the mapping expires, in milliseconds
+    pub remaining_time_ms: u32,
+    /// The packet counters of the mapping
+    pub counters: Nat64ProtocolCounters,
+}
+
+#[cfg(feature = "nat64")]
+impl From<&otNat64AddressMapping> for Nat64AddressMapping {
+    fn from(m: &otNat64AddressMapping) -> Self {
+        Self {
+            id: m.mId,
+            ip4: ot_ipv4_to_ipv4(&m.mIp4),
+            ip6: ot_ipv6_to_ipv6(&m.mIp6),
+            src_port_or_id: m.mSrcPortOrId,
+            translated_port_or_id: m.mTranslatedPortOrId,
+            remaining_time_ms: m.mRemainingTimeMs,
+            counters: (&m.mCounters).into(),
+        }
+    }
 }
 
 /// Returns IPv4 address by performing NAT64 address translation from IPv6 as specified in RFC 6052.
@@ -4,12 +4,28 @@ use core::{
 };
 
 use openthread_sys::{
-    otBorderRouterConfig, otError_OT_ERROR_NONE, otNetDataGetNextOnMeshPrefix,
+    otBorderRouterConfig, otError_OT_ERROR_NONE, otExternalRouteConfig, otIp6Address,
+    otNetDataGetNextOnMeshPrefix, otNetDataGetNextRoute, otNetDataGetNextService,
     otRoutePreference_OT_ROUTE_PREFERENCE_HIGH, otRoutePreference_OT_ROUTE_PREFERENCE_LOW,
-    otRoutePreference_OT_ROUTE_PREFERENCE_MED, OT_NETWORK_DATA_ITERATOR_INIT,
+    otRoutePreference_OT_ROUTE_PREFERENCE_MED, otServiceConfig, otThreadGetServiceAloc,
+    OT_NETWORK_DATA_ITERATOR_INIT, OT_SERVER_DATA_MAX_SIZE, OT_SERVICE_DATA_MAX_SIZE,
 };
 
-use crate::{OpenThread, OtError};
+#[cfg(feature = "border-router")]
+use openthread_sys::{
+    otBorderRouterAddOnMeshPrefix, otBorderRouterAddRoute, otBorderRouterGetNextOnMeshPrefix,
+    otBorderRouterGetNextRoute, otBorderRouterRegister, otBorderRouterRemoveOnMeshPrefix,
+    otBorderRouterRemoveRoute, otIp6Address__bindgen_ty_1, otIp6Prefix,
+};
+
+#[cfg(feature = "service")]
+use openthread_sys::{
+    otError_OT_ERROR_INVALID_ARGS, otServerAddService, otServerGetNextService, otServerRegister,
+    otServerRemoveService,
+};
+
+use crate::fmt::Bytes;
+use crate::{ot, OpenThread, OtError};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum OtRoutePreference {
@@ -24,7 +40,7 @@ pub enum OtRoutePreference {
 
 #[allow(non_upper_case_globals, clippy::unnecessary_cast)]
 impl OtRoutePreference {
-    fn from_ot_int(input: i32) -> Self {
+    pub(crate) fn from_ot_int(input: i32) -> Self {
         // These cast are needed as otRoutePreference_OT_ROUTE_PREFERENCE_* are i8 for thumbv*
         // but i32 for riscv32im*
         const value_low: i32 = otRoutePreference_OT_ROUTE_PREFERENCE_LOW as i32;
@@ -37,6 +53,21 @@ impl OtRoutePreference {
             _ => OtRoutePreference::Unkown,
         }
     }
+
+    #[cfg(feature = "border-router")]
+    #[allow(clippy::unnecessary_cast)]
+    fn to_ot_int(&self) -> i32 {
+        match self {
+            OtRoutePreference::OtRoutePreferenceLow => {
+                otRoutePreference_OT_ROUTE_PREFERENCE_LOW as i32
+            }
+            OtRoutePreference::OtRoutePreferenceHigh => {
+                otRoutePreference_OT_ROUTE_PREFERENCE_HIGH as i32
+            }
+            // Unknown preferences are published as medium, the default of OpenThread
+            _ => otRoutePreference_OT_ROUTE_PREFERENCE_MED as i32,
+        }
+    }
 }
 
 impl Display for OtRoutePreference {
@@ -94,7 +125,7 @@ pub struct OtBorderRouterConfig {
 }
 
 impl OtBorderRouterConfig {
-    fn from_ot(config: otBorderRouterConfig) -> Self {
+    pub(crate) fn from_ot(config: otBorderRouterConfig) -> Self {
         Self {
             prefix: (
                 unsafe { config.mPrefix.mPrefix.mFields.m8 }.into(),
@@ -113,6 +144,28 @@ impl OtBorderRouterConfig {
             rloc16: config.mRloc16,
         }
     }
+
+    #[cfg(feature = "border-router")]
+    pub(crate) fn to_ot(&self) -> otBorderRouterConfig {
+        let mut config = otBorderRouterConfig {
+            mPrefix: prefix_to_ot(self.prefix),
+            mRloc16: self.rloc16,
+            ..Default::default()
+        };
+
+        config.set_mPreference(self.preference.to_ot_int() as _);
+        config.set_mPreferred(self.prefered);
+        config.set_mSlaac(self.slaac);
+        config.set_mDhcp(self.dhcp);
+        config.set_mConfigure(self.configure);
+        config.set_mDefaultRoute(self.default_route);
+        config.set_mOnMesh(self.on_mesh);
+        config.set_mStable(self.stable);
+        config.set_mNdDns(self.nd_dns);
+        config.set_mDp(self.domain_prefix);
+
+        config
+    }
 }
 
 impl fmt::Display for OtBorderRouterConfig {
@@ -186,6 +239,224 @@ impl defmt::Format for OtBorderRouterConfig {
     }
 }
 
+/// Represents an External Route configuration
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OtExternalRouteConfig {
+    /// The IPv6 prefix
+    pub prefix: (Ipv6Addr, u8),
+    /// A 2-bit signed int preference
+    pub preference: OtRoutePreference,
+    /// Whether this is a NAT64 prefix
+    pub nat64: bool,
+    /// Whether this configuration is considered Stable Network Data
+    pub stable: bool,
+    /// Whether the next hop is this device (value ignored on config add)
+    pub next_hop_is_this_device: bool,
+    /// Whether or not BR is advertising a ULA prefix in PIO (AP flag)
+    pub adv_pio: bool,
+    /// The border router's RLOC16 (value ignored on config add)
+    pub rloc16: u16,
+}
+
+impl OtExternalRouteConfig {
+    pub(crate) fn from_ot(config: otExternalRouteConfig) -> Self {
+        Self {
+            prefix: (
+                unsafe { config.mPrefix.mPrefix.mFields.m8 }.into(),
+                config.mPrefix.mLength,
+            ),
+            preference: OtRoutePreference::from_ot_int(config.mPreference()),
+            nat64: config.mNat64(),
+            stable: config.mStable(),
+            next_hop_is_this_device: config.mNextHopIsThisDevice(),
+            adv_pio: config.mAdvPio(),
+            rloc16: config.mRloc16,
+        }
+    }
+
+    #[cfg(feature = "border-router")]
+    pub(crate) fn to_ot(&self) -> otExternalRouteConfig {
+        let mut config = otExternalRouteConfig {
+            mPrefix: prefix_to_ot(self.prefix),
+            mRloc16: self.rloc16,
+            ..Default::default()
+        };
+
+        config.set_mPreference(self.preference.to_ot_int() as _);
+        config.set_mNat64(self.nat64);
+        config.set_mStable(self.stable);
+        config.set_mAdvPio(self.adv_pio);
+
+        config
+    }
+}
+
+impl fmt::Display for OtExternalRouteConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OtExternalRouteConfig {{
+        prefix: ({}, {}),
+        preference: {},
+        nat64: {},
+        stable: {},
+        next_hop_is_this_device: {},
+        adv_pio: {},
+        rloc16: {}
+    }}",
+            self.prefix.0,
+            self.prefix.1,
+            self.preference,
+            self.nat64,
+            self.stable,
+            self.next_hop_is_this_device,
+            self.adv_pio,
+            self.rloc16
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for OtExternalRouteConfig {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "OtExternalRouteConfig {{
+        prefix: ({}, {}),
+        preference: {},
+        nat64: {},
+        stable: {},
+        next_hop_is_this_device: {},
+        adv_pio: {},
+        rloc16: {}
+    }}",
+            self.prefix.0,
+            self.prefix.1,
+            self.preference,
+            self.nat64,
+            self.stable,
+            self.next_hop_is_this_device,
+            self.adv_pio,
+            self.rloc16
+        )
+    }
+}
+
+/// Represents a Network Data service configuration, with the configuration of one of its servers
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OtServiceConfig {
+    /// Service ID (value ignored on config add)
+    pub service_id: u8,
+    /// IANA Enterprise Number
+    pub enterprise_number: u32,
+    /// The service data
+    pub service_data: heapless::Vec<u8, { OT_SERVICE_DATA_MAX_SIZE as usize }>,
+    /// The server data
+    pub server_data: heapless::Vec<u8, { OT_SERVER_DATA_MAX_SIZE as usize }>,
+    /// Whether this configuration is considered Stable Network Data
+    pub stable: bool,
+    /// The server's RLOC16 (value ignored on config add)
+    pub rloc16: u16,
+}
+
+impl OtServiceConfig {
+    fn from_ot(config: &otServiceConfig) -> Self {
+        let server = &config.mServerConfig;
+
+        let service_data_len = (config.mServiceDataLength as usize).min(config.mServiceData.len());
+        let server_data_len = (server.mServerDataLength as usize).min(server.mServerData.len());
+
+        Self {
+            service_id: config.mServiceId,
+            enterprise_number: config.mEnterpriseNumber,
+            service_data: unwrap!(heapless::Vec::from_slice(
+                &config.mServiceData[..service_data_len]
+            )),
+            server_data: unwrap!(heapless::Vec::from_slice(
+                &server.mServerData[..server_data_len]
+            )),
+            stable: server.mStable(),
+            rloc16: server.mRloc16,
+        }
+    }
+
+    #[cfg(feature = "service")]
+    fn to_ot(&self) -> otServiceConfig {
+        let mut config = otServiceConfig {
+            mEnterpriseNumber: self.enterprise_number,
+            mServiceDataLength: self.service_data.len() as _,
+            ..Default::default()
+        };
+
+        config.mServiceData[..self.service_data.len()].copy_from_slice(&self.service_data);
+
+        let server = &mut config.mServerConfig;
+
+        server.mServerDataLength = self.server_data.len() as _;
+        server.mServerData[..self.server_data.len()].copy_from_slice(&self.server_data);
+        server.set_mStable(self.stable);
+
+        config
+    }
+}
+
+impl fmt::Display for OtServiceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OtServiceConfig {{
+        service_id: {},
+        enterprise_number: {},
+        service_data: {},
+        server_data: {},
+        stable: {},
+        rloc16: {}
+    }}",
+            self.service_id,
+            self.enterprise_number,
+            Bytes(&self.service_data),
+            Bytes(&self.server_data),
+            self.stable,
+            self.rloc16
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for OtServiceConfig {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "OtServiceConfig {{
+        service_id: {},
+        enterprise_number: {},
+        service_data: {},
+        server_data: {},
+        stable: {},
+        rloc16: {}
+    }}",
+            self.service_id,
+            self.enterprise_number,
+            Bytes(&self.service_data),
+            Bytes(&self.server_data),
+            self.stable,
+            self.rloc16
+        )
+    }
+}
+
+#[cfg(feature = "border-router")]
+pub(crate) fn prefix_to_ot(prefix: (Ipv6Addr, u8)) -> otIp6Prefix {
+    otIp6Prefix {
+        mPrefix: otIp6Address {
+            mFields: otIp6Address__bindgen_ty_1 {
+                m8: prefix.0.octets(),
+            },
+        },
+        mLength: prefix.1,
+    }
+}
+
 impl OpenThread<'_> {
     /// Gets the list of all on mesh prefixes
     ///
@@ -216,4 +487,280 @@ impl OpenThread<'_> {
 
         f(None)
     }
+
+    /// Gets the list of all external routes
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each external route with the corresponding
+    ///   `OtExternalRouteConfig`. Once called for all routes,
+    ///   the closure will be called with `None`.
+    pub fn netdata_get_external_routes<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<OtExternalRouteConfig>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut network_data_iterator = OT_NETWORK_DATA_ITERATOR_INIT;
+        let mut a_config = otExternalRouteConfig::default();
+
+        while unsafe {
+            otNetDataGetNextRoute(state.ot.instance, &mut network_data_iterator, &mut a_config)
+        } == otError_OT_ERROR_NONE
+        {
+            f(Some(OtExternalRouteConfig::from_ot(a_config)))?;
+        }
+
+        f(None)
+    }
+
+    /// Add an on-mesh prefix to the local network data
+    ///
+    /// The change is only published to the leader with `border_router_register`.
+    ///
+    /// Arguments:
+    /// - `config`: The on-mesh prefix configuration; `rloc16` is ignored
+    #[cfg(feature = "border-router")]
+    pub fn border_router_add_on_mesh_prefix(
+        &self,
+        config: &OtBorderRouterConfig,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRouterAddOnMeshPrefix(state.ot.instance, &config.to_ot()) })
+    }
+
+    /// Remove an on-mesh prefix from the local network data
+    ///
+    /// The change is only published to the leader with `border_router_register`.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix and its length
+    #[cfg(feature = "border-router")]
+    pub fn border_router_remove_on_mesh_prefix(
+        &self,
+        prefix: (Ipv6Addr, u8),
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRouterRemoveOnMeshPrefix(state.ot.instance, &prefix_to_ot(prefix)) })
+    }
+
+    /// Add an external route to the local network data
+    ///
+    /// The change is only published to the leader with `border_router_register`.
+    ///
+    /// Arguments:
+    /// - `config`: The external route configuration; `next_hop_is_this_device` and `rloc16` are ignored
+    #[cfg(feature = "border-router")]
+    pub fn border_router_add_route(&self, config: &OtExternalRouteConfig) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRouterAddRoute(state.ot.instance, &config.to_ot()) })
+    }
+
+    /// Remove an external route from the local network data
+    ///
+    /// The change is only published to the leader with `border_router_register`.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix and its length
+    #[cfg(feature = "border-router")]
+    pub fn border_router_remove_route(&self, prefix: (Ipv6Addr, u8)) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRouterRemoveRoute(state.ot.instance, &prefix_to_ot(prefix)) })
+    }
+
+    /// Immediately register the local network data with the leader
+    #[cfg(feature = "border-router")]
+    pub fn border_router_register(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otBorderRouterRegister(state.ot.instance) })
+    }
+
+    /// Gets the list of the on mesh prefixes in the local network data
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each mesh prefix with the corresponding
+    ///   `OtBorderRouterConfig`. Once called for all prefixes,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "border-router")]
+    pub fn border_router_get_on_mesh_prefixes<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<OtBorderRouterConfig>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut network_data_iterator = OT_NETWORK_DATA_ITERATOR_INIT;
+        let mut a_config = otBorderRouterConfig::default();
+
+        while unsafe {
+            otBorderRouterGetNextOnMeshPrefix(
+                state.ot.instance,
+                &mut network_data_iterator,
+                &mut a_config,
+            )
+        } == otError_OT_ERROR_NONE
+        {
+            f(Some(OtBorderRouterConfig::from_ot(a_config)))?;
+        }
+
+        f(None)
+    }
+
+    /// Gets the list of the external routes in the local network data
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each external route with the corresponding
+    ///   `OtExternalRouteConfig`. Once called for all routes,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "border-router")]
+    pub fn border_router_get_external_routes<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<OtExternalRouteConfig>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut network_data_iterator = OT_NETWORK_DATA_ITERATOR_INIT;
+        let mut a_config = otExternalRouteConfig::default();
+
+        while unsafe {
+            otBorderRouterGetNextRoute(state.ot.instance, &mut network_data_iterator, &mut a_config)
+        } == otError_OT_ERROR_NONE
+        {
+            f(Some(OtExternalRouteConfig::from_ot(a_config)))?;
+        }
+
+        f(None)
+    }
+
+    /// Gets the list of all services
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each server of each service with the corresponding
+    ///   `OtServiceConfig`. Once called for all services,
+    ///   the closure will be called with `None`.
+    pub fn netdata_get_services<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<OtServiceConfig>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut network_data_iterator = OT_NETWORK_DATA_ITERATOR_INIT;
+        let mut a_config = otServiceConfig::default();
+
+        while unsafe {
+            otNetDataGetNextService(state.ot.instance, &mut network_data_iterator, &mut a_config)
+        } == otError_OT_ERROR_NONE
+        {
+            f(Some(OtServiceConfig::from_ot(&a_config)))?;
+        }
+
+        f(None)
+    }
+
+    /// Gets the Service ALOC (Anycast Locator) of a service
+    ///
+    /// Arguments:
+    /// - `service_id`: The service ID, as reported in `OtServiceConfig::service_id`
+    pub fn netdata_service_aloc(&self, service_id: u8) -> Result<Ipv6Addr, OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut aloc = otIp6Address::default();
+
+        ot!(unsafe { otThreadGetServiceAloc(state.ot.instance, service_id, &mut aloc) })?;
+
+        Ok(unsafe { aloc.mFields.m8 }.into())
+    }
+
+    /// Add a service to the local network data
+    ///
+    /// The change is only published to the leader with `server_register`.
+    ///
+    /// Arguments:
+    /// - `config`: The service configuration; `service_id` and `rloc16` are ignored
+    #[cfg(feature = "service")]
+    pub fn server_add_service(&self, config: &OtServiceConfig) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otServerAddService(state.ot.instance, &config.to_ot()) })
+    }
+
+    /// Remove a service from the local network data
+    ///
+    /// The change is only published to the leader with `server_register`.
+    ///
+    /// Arguments:
+    /// - `enterprise_number`: The IANA Enterprise Number of the service
+    /// - `service_data`: The service data of the service
+    #[cfg(feature = "service")]
+    pub fn server_remove_service(
+        &self,
+        enterprise_number: u32,
+        service_data: &[u8],
+    ) -> Result<(), OtError> {
+        if service_data.len() > OT_SERVICE_DATA_MAX_SIZE as usize {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe {
+            otServerRemoveService(
+                state.ot.instance,
+                enterprise_number,
+                service_data.as_ptr(),
+                service_data.len() as _,
+            )
+        })
+    }
+
+    /// Immediately register the local network data (services) with the leader
+    #[cfg(feature = "service")]
+    pub fn server_register(&self) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otServerRegister(state.ot.instance) })
+    }
+
+    /// Gets the list of the services in the local network data
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each service with the corresponding
+    ///   `OtServiceConfig`. Once called for all services,
+    ///   the closure will be called with `None`.
+    #[cfg(feature = "service")]
+    pub fn server_get_services<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<OtServiceConfig>) -> Result<(), OtError>,
+    {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut network_data_iterator = OT_NETWORK_DATA_ITERATOR_INIT;
+        let mut a_config = otServiceConfig::default();
+
+        while unsafe {
+            otServerGetNextService(state.ot.instance, &mut network_data_iterator, &mut a_config)
+        } == otError_OT_ERROR_NONE
+        {
+            f(Some(OtServiceConfig::from_ot(&a_config)))?;
+        }
+
+        f(None)
+    }
 }
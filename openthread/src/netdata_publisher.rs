@@ -0,0 +1,388 @@
+//! Network Data Publisher API (`OPENTHREAD_CONFIG_NETDATA_PUBLISHER_ENABLE` / the `netdata-publisher` feature).
+//!
+//! Rather than unconditionally adding entries to the Network Data, the Publisher monitors the
+//! Network Data and decides whether (and when) an entry published by this device should actually
+//! be added, based on the number of similar entries already present (so as to limit the entries
+//! in the Network Data when many devices are able to provide the same service).
+//!
+//! Entries are published with `netdata_publish_*` and withdrawn with `netdata_unpublish_*`.
+//! Whether a published entry is currently added to the Network Data can be checked with
+//! `netdata_is_*_added`, awaited with `netdata_wait_*_added`, or observed with
+//! [`OpenThread::netdata_wait_publisher_event`].
+//!
+//! Publishing on-mesh prefixes and external routes additionally requires the `border-router` feature.
+
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+use core::task::Poll;
+
+use crate::sys::{
+    otInstance, otNetDataIsDnsSrpServiceAdded, otNetDataPublishDnsSrpServiceAnycast,
+    otNetDataPublishDnsSrpServiceUnicast, otNetDataPublishDnsSrpServiceUnicastMeshLocalEid,
+    otNetDataPublisherEvent, otNetDataPublisherEvent_OT_NETDATA_PUBLISHER_EVENT_ENTRY_ADDED,
+    otNetDataSetDnsSrpServicePublisherCallback, otNetDataUnpublishDnsSrpService,
+};
+use crate::{to_ot_ip6_addr, OpenThread, OtContext};
+
+#[cfg(feature = "border-router")]
+use crate::netdata::prefix_to_ot;
+#[cfg(feature = "border-router")]
+use crate::sys::{
+    otIp6Prefix, otNetDataIsPrefixAdded, otNetDataPublishExternalRoute,
+    otNetDataPublishOnMeshPrefix, otNetDataReplacePublishedExternalRoute,
+    otNetDataSetPrefixPublisherCallback, otNetDataUnpublishPrefix,
+};
+#[cfg(feature = "border-router")]
+use crate::{ot, OtBorderRouterConfig, OtError, OtExternalRouteConfig};
+
+/// The maximum number of publisher events (see [`OpenThread::netdata_wait_publisher_event`])
+/// which can be queued, waiting to be picked up by the application.
+///
+/// Events arriving while the queue is full are dropped.
+pub const NETDATA_PUBLISHER_MAX_PENDING_EVENTS: usize = 4;
+
+/// A Network Data entry published with the Publisher.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NetDataPublisherEntry {
+    /// The DNS/SRP service entry.
+    DnsSrpService,
+    /// An on-mesh prefix or external route entry, with its IPv6 prefix.
+    Prefix((Ipv6Addr, u8)),
+}
+
+/// A publisher event, as returned by [`OpenThread::netdata_wait_publisher_event`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDataPublisherEvent {
+    /// The published entry the event relates to.
+    pub entry: NetDataPublisherEntry,
+    /// `true` if the entry was added to the Network Data, `false` if it was removed from it.
+    pub added: bool,
+}
+
+impl OpenThread<'_> {
+    /// Publish the "DNS/SRP Service Anycast Address" entry in the Network Data.
+    ///
+    /// Replaces any previously published DNS/SRP service entry.
+    ///
+    /// Arguments:
+    /// - `sequence_number`: The sequence number of the DNS/SRP Anycast Service.
+    /// - `version`: The version of the service.
+    pub fn netdata_publish_dns_srp_service_anycast(&self, sequence_number: u8, version: u8) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            Self::netdata_set_dns_srp_service_publisher_callback(instance);
+            otNetDataPublishDnsSrpServiceAnycast(instance, sequence_number, version);
+        }
+    }
+
+    /// Publish the "DNS/SRP Service Unicast Address" entry in the Network Data,
+    /// with the address and port included in the service data.
+    ///
+    /// Replaces any previously published DNS/SRP service entry.
+    ///
+    /// Arguments:
+    /// - `address`: The address of the DNS/SRP server.
+    /// - `port`: The UDP port of the DNS/SRP server.
+    /// - `version`: The version of the service.
+    pub fn netdata_publish_dns_srp_service_unicast(
+        &self,
+        address: Ipv6Addr,
+        port: u16,
+        version: u8,
+    ) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let address = to_ot_ip6_addr(address);
+
+        unsafe {
+            Self::netdata_set_dns_srp_service_publisher_callback(instance);
+            otNetDataPublishDnsSrpServiceUnicast(instance, &address, port, version);
+        }
+    }
+
+    /// Publish the "DNS/SRP Service Unicast Address" entry in the Network Data,
+    /// with the mesh-local EID of this device and the port included in the server data.
+    ///
+    /// Replaces any previously published DNS/SRP service entry.
+    ///
+    /// Arguments:
+    /// - `port`: The UDP port of the DNS/SRP server.
+    /// - `version`: The version of the service.
+    pub fn netdata_publish_dns_srp_service_unicast_ml_eid(&self, port: u16, version: u8) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            Self::netdata_set_dns_srp_service_publisher_callback(instance);
+            otNetDataPublishDnsSrpServiceUnicastMeshLocalEid(instance, port, version);
+        }
+    }
+
+    /// Unpublish the previously published DNS/SRP service entry, removing it from the Network Data
+    /// if it was added.
+    pub fn netdata_unpublish_dns_srp_service(&self) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNetDataUnpublishDnsSrpService(state.ot.instance) }
+    }
+
+    /// Return `true` if the published DNS/SRP service entry is currently added to the Network Data.
+    pub fn netdata_is_dns_srp_service_added(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNetDataIsDnsSrpServiceAdded(state.ot.instance) }
+    }
+
+    /// Wait until the published DNS/SRP service entry is added to (or removed from) the Network Data.
+    ///
+    /// Completes immediately if the entry is already in the requested state.
+    ///
+    /// Arguments:
+    /// - `added`: `true` to wait until the entry is added, `false` to wait until it is removed.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// (nor together with the other `netdata_wait_*_added` method), because they share a single
+    /// waker registration. Thus, while the method will not panic, the tasks will fight with each
+    /// other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn netdata_wait_dns_srp_service_added(&self, added: bool) {
+        self.netdata_wait_publisher_changed(|| self.netdata_is_dns_srp_service_added() == added)
+            .await
+    }
+
+    /// Publish an on-mesh prefix entry in the Network Data.
+    ///
+    /// Replaces any previously published entry with the same prefix.
+    ///
+    /// Arguments:
+    /// - `config`: The on-mesh prefix configuration; `rloc16` is ignored
+    #[cfg(feature = "border-router")]
+    pub fn netdata_publish_on_mesh_prefix(
+        &self,
+        config: &OtBorderRouterConfig,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            Self::netdata_set_prefix_publisher_callback(instance);
+        }
+
+        ot!(unsafe { otNetDataPublishOnMeshPrefix(instance, &config.to_ot()) })
+    }
+
+    /// Publish an external route entry in the Network Data.
+    ///
+    /// Replaces any previously published entry with the same prefix.
+    ///
+    /// Arguments:
+    /// - `config`: The external route configuration; `next_hop_is_this_device` and `rloc16` are ignored
+    #[cfg(feature = "border-router")]
+    pub fn netdata_publish_external_route(
+        &self,
+        config: &OtExternalRouteConfig,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            Self::netdata_set_prefix_publisher_callback(instance);
+        }
+
+        ot!(unsafe { otNetDataPublishExternalRoute(instance, &config.to_ot()) })
+    }
+
+    /// Replace a previously published external route entry with a new one.
+    ///
+    /// Unlike unpublishing the old entry and publishing the new one, the replacement is done
+    /// so that the new prefix is published before the old one is removed (if both are added),
+    /// avoiding a period without a route.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix of the previously published entry
+    /// - `config`: The new external route configuration
+    #[cfg(feature = "border-router")]
+    pub fn netdata_replace_published_external_route(
+        &self,
+        prefix: (Ipv6Addr, u8),
+        config: &OtExternalRouteConfig,
+    ) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            Self::netdata_set_prefix_publisher_callback(instance);
+        }
+
+        ot!(unsafe {
+            otNetDataReplacePublishedExternalRoute(instance, &prefix_to_ot(prefix), &config.to_ot())
+        })
+    }
+
+    /// Unpublish a previously published on-mesh prefix or external route entry,
+    /// removing it from the Network Data if it was added.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix of the entry
+    #[cfg(feature = "border-router")]
+    pub fn netdata_unpublish_prefix(&self, prefix: (Ipv6Addr, u8)) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        ot!(unsafe { otNetDataUnpublishPrefix(state.ot.instance, &prefix_to_ot(prefix)) })
+    }
+
+    /// Return `true` if the published on-mesh prefix or external route entry
+    /// is currently added to the Network Data.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix of the entry
+    #[cfg(feature = "border-router")]
+    pub fn netdata_is_prefix_added(&self, prefix: (Ipv6Addr, u8)) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otNetDataIsPrefixAdded(state.ot.instance, &prefix_to_ot(prefix)) }
+    }
+
+    /// Wait until the published on-mesh prefix or external route entry is added to (or removed from)
+    /// the Network Data.
+    ///
+    /// Completes immediately if the entry is already in the requested state.
+    ///
+    /// Arguments:
+    /// - `prefix`: The IPv6 prefix of the entry
+    /// - `added`: `true` to wait until the entry is added, `false` to wait until it is removed.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// (nor together with the other `netdata_wait_*_added` method), because they share a single
+    /// waker registration. Thus, while the method will not panic, the tasks will fight with each
+    /// other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    #[cfg(feature = "border-router")]
+    pub async fn netdata_wait_prefix_added(&self, prefix: (Ipv6Addr, u8), added: bool) {
+        self.netdata_wait_publisher_changed(|| self.netdata_is_prefix_added(prefix) == added)
+            .await
+    }
+
+    /// Wait for the next publisher event, i.e. for a published entry to be added to
+    /// or removed from the Network Data.
+    ///
+    /// Events are only queued once an entry was published;
+    /// see [`NETDATA_PUBLISHER_MAX_PENDING_EVENTS`].
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn netdata_wait_publisher_event(&self) -> NetDataPublisherEvent {
+        poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if let Some(event) = state.ot.netdata_publisher_events.pop_front() {
+                return Poll::Ready(event);
+            }
+
+            state.ot.netdata_publisher_event.reset();
+            let _ = state.ot.netdata_publisher_event.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    async fn netdata_wait_publisher_changed<F>(&self, mut condition: F)
+    where
+        F: FnMut() -> bool,
+    {
+        poll_fn(|cx| {
+            if condition() {
+                return Poll::Ready(());
+            }
+
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            state.ot.netdata_publisher_changed.reset();
+            let _ = state.ot.netdata_publisher_changed.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    unsafe fn netdata_set_dns_srp_service_publisher_callback(instance: *mut otInstance) {
+        otNetDataSetDnsSrpServicePublisherCallback(
+            instance,
+            Some(OtContext::plat_c_netdata_dns_srp_service_publisher_callback),
+            instance as _,
+        );
+    }
+
+    #[cfg(feature = "border-router")]
+    unsafe fn netdata_set_prefix_publisher_callback(instance: *mut otInstance) {
+        otNetDataSetPrefixPublisherCallback(
+            instance,
+            Some(OtContext::plat_c_netdata_prefix_publisher_callback),
+            instance as _,
+        );
+    }
+}
+
+impl OtContext<'_> {
+    fn plat_netdata_publisher_event(
+        &mut self,
+        entry: NetDataPublisherEntry,
+        event: otNetDataPublisherEvent,
+    ) {
+        let state = self.state();
+
+        let event = NetDataPublisherEvent {
+            entry,
+            added: event == otNetDataPublisherEvent_OT_NETDATA_PUBLISHER_EVENT_ENTRY_ADDED,
+        };
+
+        debug!("Network Data publisher event: {:?}", event);
+
+        if state.ot.netdata_publisher_events.push_back(event).is_err() {
+            warn!("Too many pending publisher events, dropping event");
+        } else {
+            state.ot.netdata_publisher_event.signal(());
+        }
+
+        state.ot.netdata_publisher_changed.signal(());
+    }
+
+    unsafe extern "C" fn plat_c_netdata_dns_srp_service_publisher_callback(
+        event: otNetDataPublisherEvent,
+        context: *mut core::ffi::c_void,
+    ) {
+        Self::callback(context as *mut otInstance)
+            .plat_netdata_publisher_event(NetDataPublisherEntry::DnsSrpService, event);
+    }
+
+    #[cfg(feature = "border-router")]
+    unsafe extern "C" fn plat_c_netdata_prefix_publisher_callback(
+        event: otNetDataPublisherEvent,
+        prefix: *const otIp6Prefix,
+        context: *mut core::ffi::c_void,
+    ) {
+        let prefix = unsafe { &*prefix };
+
+        Self::callback(context as *mut otInstance).plat_netdata_publisher_event(
+            NetDataPublisherEntry::Prefix((
+                unsafe { prefix.mPrefix.mFields.m8 }.into(),
+                prefix.mLength,
+            )),
+            event,
+        );
+    }
+}
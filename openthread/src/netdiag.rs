@@ -0,0 +1,723 @@
+//! Network Diagnostic client API (`OPENTHREAD_CONFIG_TMF_NETDIAG_CLIENT_ENABLE` / the `netdiag-client` feature).
+//!
+//! Allows querying the diagnostic TLVs of other nodes of the Thread network (`DIAG_GET`),
+//! either of a single node (unicast destination) or of many nodes at once (multicast destination,
+//! e.g. the Realm-Local All-Thread-Nodes address `ff03::2`), as well as resetting their
+//! counters (`DIAG_RST`).
+//!
+//! Each received response is handed to a user closure as a borrowed [`NetDiagResponse`]
+//! accessor, whose TLVs are decoded on the fly into [`NetDiagTlv`] values - because the
+//! underlying message is only valid for the duration of the callback and must not be retained.
+//!
+//! Only one `DIAG_GET` may be in flight at a time per `OpenThread` instance;
+//! starting another while one is pending returns [`otError`] `BUSY`.
+//!
+//! NOTE: Like [`OpenThread::scan`], the future returned by [`OpenThread::netdiag_get`]
+//! is currently NOT `core::mem::forget`-safe. Do not `mem::forget` it.
+//!
+//! [`otError`]: crate::sys::otError
+
+use core::ffi::{c_void, CStr};
+use core::future::poll_fn;
+use core::net::Ipv6Addr;
+
+use crate::fmt::bitflags;
+use crate::sys::{
+    otError, otError_OT_ERROR_BUSY, otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND, otInstance,
+    otIp6Address, otLeaderData, otLinkModeConfig, otMessage, otMessageInfo,
+    otNetworkDiagChildEntry, otNetworkDiagConnectivity, otNetworkDiagIterator,
+    otNetworkDiagMacCounters, otNetworkDiagMleCounters, otNetworkDiagRoute, otNetworkDiagRouteData,
+    otNetworkDiagTlv, otThreadGetNextDiagnosticTlv, otThreadSendDiagnosticGet,
+    otThreadSendDiagnosticReset, OT_NETWORK_DIAGNOSTIC_ITERATOR_INIT,
+    OT_NETWORK_DIAGNOSTIC_TLV_BATTERY_LEVEL, OT_NETWORK_DIAGNOSTIC_TLV_CHANNEL_PAGES,
+    OT_NETWORK_DIAGNOSTIC_TLV_CHILD_TABLE, OT_NETWORK_DIAGNOSTIC_TLV_CONNECTIVITY,
+    OT_NETWORK_DIAGNOSTIC_TLV_EUI64, OT_NETWORK_DIAGNOSTIC_TLV_EXT_ADDRESS,
+    OT_NETWORK_DIAGNOSTIC_TLV_IP6_ADDR_LIST, OT_NETWORK_DIAGNOSTIC_TLV_LEADER_DATA,
+    OT_NETWORK_DIAGNOSTIC_TLV_MAC_COUNTERS, OT_NETWORK_DIAGNOSTIC_TLV_MAX_CHILD_TIMEOUT,
+    OT_NETWORK_DIAGNOSTIC_TLV_MLE_COUNTERS, OT_NETWORK_DIAGNOSTIC_TLV_MODE,
+    OT_NETWORK_DIAGNOSTIC_TLV_NETWORK_DATA, OT_NETWORK_DIAGNOSTIC_TLV_ROUTE,
+    OT_NETWORK_DIAGNOSTIC_TLV_SHORT_ADDRESS, OT_NETWORK_DIAGNOSTIC_TLV_SUPPLY_VOLTAGE,
+    OT_NETWORK_DIAGNOSTIC_TLV_THREAD_STACK_VERSION, OT_NETWORK_DIAGNOSTIC_TLV_TIMEOUT,
+    OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_APP_URL, OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_MODEL,
+    OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_NAME, OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_SW_VERSION,
+    OT_NETWORK_DIAGNOSTIC_TLV_VERSION,
+};
+use crate::{ot, to_ot_ip6_addr, OpenThread, OtContext, OtError};
+
+bitflags! {
+    /// A set of Network Diagnostic TLV types, to be requested with [`OpenThread::netdiag_get`]
+    /// or reset with [`OpenThread::netdiag_reset`].
+    #[repr(transparent)]
+    #[derive(Default)]
+    #[cfg_attr(not(feature = "defmt"), derive(Debug, Copy, Clone, Eq, PartialEq, Hash))]
+    pub struct NetDiagTlvs: u64 {
+        /// The extended MAC address
+        const EXT_ADDRESS = 1 << OT_NETWORK_DIAGNOSTIC_TLV_EXT_ADDRESS;
+        /// The RLOC16
+        const RLOC16 = 1 << OT_NETWORK_DIAGNOSTIC_TLV_SHORT_ADDRESS;
+        /// The link mode
+        const MODE = 1 << OT_NETWORK_DIAGNOSTIC_TLV_MODE;
+        /// The child timeout (SEDs only)
+        const TIMEOUT = 1 << OT_NETWORK_DIAGNOSTIC_TLV_TIMEOUT;
+        /// The connectivity
+        const CONNECTIVITY = 1 << OT_NETWORK_DIAGNOSTIC_TLV_CONNECTIVITY;
+        /// The Route64 (routers only)
+        const ROUTE64 = 1 << OT_NETWORK_DIAGNOSTIC_TLV_ROUTE;
+        /// The leader data
+        const LEADER_DATA = 1 << OT_NETWORK_DIAGNOSTIC_TLV_LEADER_DATA;
+        /// The network data
+        const NETWORK_DATA = 1 << OT_NETWORK_DIAGNOSTIC_TLV_NETWORK_DATA;
+        /// The list of IPv6 addresses
+        const IP6_ADDR_LIST = 1 << OT_NETWORK_DIAGNOSTIC_TLV_IP6_ADDR_LIST;
+        /// The MAC counters
+        const MAC_COUNTERS = 1 << OT_NETWORK_DIAGNOSTIC_TLV_MAC_COUNTERS;
+        /// The battery level
+        const BATTERY_LEVEL = 1 << OT_NETWORK_DIAGNOSTIC_TLV_BATTERY_LEVEL;
+        /// The supply voltage
+        const SUPPLY_VOLTAGE = 1 << OT_NETWORK_DIAGNOSTIC_TLV_SUPPLY_VOLTAGE;
+        /// The child table (routers only)
+        const CHILD_TABLE = 1 << OT_NETWORK_DIAGNOSTIC_TLV_CHILD_TABLE;
+        /// The supported channel pages
+        const CHANNEL_PAGES = 1 << OT_NETWORK_DIAGNOSTIC_TLV_CHANNEL_PAGES;
+        /// The maximum child timeout (routers only)
+        const MAX_CHILD_TIMEOUT = 1 << OT_NETWORK_DIAGNOSTIC_TLV_MAX_CHILD_TIMEOUT;
+        /// The EUI-64
+        const EUI64 = 1 << OT_NETWORK_DIAGNOSTIC_TLV_EUI64;
+        /// The Thread version
+        const VERSION = 1 << OT_NETWORK_DIAGNOSTIC_TLV_VERSION;
+        /// The vendor name
+        const VENDOR_NAME = 1 << OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_NAME;
+        /// The vendor model
+        const VENDOR_MODEL = 1 << OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_MODEL;
+        /// The vendor software version
+        const VENDOR_SW_VERSION = 1 << OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_SW_VERSION;
+        /// The Thread stack version
+        const THREAD_STACK_VERSION = 1 << OT_NETWORK_DIAGNOSTIC_TLV_THREAD_STACK_VERSION;
+        /// The vendor application URL
+        const VENDOR_APP_URL = 1 << OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_APP_URL;
+        /// The MLE counters
+        const MLE_COUNTERS = 1 << OT_NETWORK_DIAGNOSTIC_TLV_MLE_COUNTERS;
+    }
+}
+
+impl NetDiagTlvs {
+    /// Write the TLV types of the set into `buf` and return the number of types written.
+    fn to_types(self, buf: &mut [u8; 64]) -> u8 {
+        let mut count = 0;
+
+        for tlv_type in 0..64 {
+            if self.bits() & (1 << tlv_type) != 0 {
+                buf[count] = tlv_type as u8;
+                count += 1;
+            }
+        }
+
+        count as u8
+    }
+}
+
+/// The link mode of a node, as reported in a [`NetDiagTlv::Mode`] TLV.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagLinkMode {
+    /// Whether the node keeps its receiver on when idle.
+    pub rx_on_when_idle: bool,
+    /// Whether the node is a Full Thread Device.
+    pub full_thread_device: bool,
+    /// Whether the node requests the full Network Data.
+    pub full_network_data: bool,
+}
+
+impl From<&otLinkModeConfig> for NetDiagLinkMode {
+    fn from(mode: &otLinkModeConfig) -> Self {
+        Self {
+            rx_on_when_idle: mode.mRxOnWhenIdle(),
+            full_thread_device: mode.mDeviceType(),
+            full_network_data: mode.mNetworkData(),
+        }
+    }
+}
+
+/// The connectivity of a node, as reported in a [`NetDiagTlv::Connectivity`] TLV.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagConnectivity {
+    /// The priority of the node as a parent.
+    pub parent_priority: i8,
+    /// Number of neighbors with link quality 3.
+    pub link_quality_3: u8,
+    /// Number of neighbors with link quality 2.
+    pub link_quality_2: u8,
+    /// Number of neighbors with link quality 1.
+    pub link_quality_1: u8,
+    /// Cost to the leader.
+    pub leader_cost: u8,
+    /// Most recent received Router ID sequence number.
+    pub id_sequence: u8,
+    /// Number of active routers.
+    pub active_routers: u8,
+    /// Buffer capacity in bytes for SEDs (optional; zero if not present).
+    pub sed_buffer_size: u16,
+    /// Queue capacity (number of IPv6 datagrams) per SED (optional; zero if not present).
+    pub sed_datagram_count: u8,
+}
+
+impl From<&otNetworkDiagConnectivity> for NetDiagConnectivity {
+    fn from(c: &otNetworkDiagConnectivity) -> Self {
+        Self {
+            parent_priority: c.mParentPriority,
+            link_quality_3: c.mLinkQuality3,
+            link_quality_2: c.mLinkQuality2,
+            link_quality_1: c.mLinkQuality1,
+            leader_cost: c.mLeaderCost,
+            id_sequence: c.mIdSequence,
+            active_routers: c.mActiveRouters,
+            sed_buffer_size: c.mSedBufferSize,
+            sed_datagram_count: c.mSedDatagramCount,
+        }
+    }
+}
+
+/// A single route of a [`NetDiagRoute64`] TLV.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagRouteData {
+    /// The assigned Router ID.
+    pub router_id: u8,
+    /// Link Quality Out (0..=3).
+    pub link_quality_out: u8,
+    /// Link Quality In (0..=3).
+    pub link_quality_in: u8,
+    /// The route cost to the router (zero if unreachable).
+    pub route_cost: u8,
+}
+
+impl From<&otNetworkDiagRouteData> for NetDiagRouteData {
+    fn from(route: &otNetworkDiagRouteData) -> Self {
+        Self {
+            router_id: route.mRouterId,
+            link_quality_out: route.mLinkQualityOut(),
+            link_quality_in: route.mLinkQualityIn(),
+            route_cost: route.mRouteCost(),
+        }
+    }
+}
+
+/// A borrowed accessor over a Route64 TLV, as reported in a [`NetDiagTlv::Route64`] TLV.
+#[derive(Copy, Clone)]
+pub struct NetDiagRoute64<'a>(&'a otNetworkDiagRoute);
+
+impl<'a> NetDiagRoute64<'a> {
+    /// The Router ID sequence number.
+    pub fn id_sequence(&self) -> u8 {
+        self.0.mIdSequence
+    }
+
+    /// The routes to the assigned Router IDs.
+    pub fn routes(&self) -> impl Iterator<Item = NetDiagRouteData> + 'a {
+        let route = self.0;
+
+        route.mRouteData[..(route.mRouteCount as usize).min(route.mRouteData.len())]
+            .iter()
+            .map(NetDiagRouteData::from)
+    }
+}
+
+/// The leader data of a node, as reported in a [`NetDiagTlv::LeaderData`] TLV.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagLeaderData {
+    /// The partition ID.
+    pub partition_id: u32,
+    /// The leader weight.
+    pub weighting: u8,
+    /// The full Network Data version.
+    pub data_version: u8,
+    /// The stable Network Data version.
+    pub stable_data_version: u8,
+    /// The Router ID of the leader.
+    pub leader_router_id: u8,
+}
+
+impl From<&otLeaderData> for NetDiagLeaderData {
+    fn from(data: &otLeaderData) -> Self {
+        Self {
+            partition_id: data.mPartitionId,
+            weighting: data.mWeighting,
+            data_version: data.mDataVersion,
+            stable_data_version: data.mStableDataVersion,
+            leader_router_id: data.mLeaderRouterId,
+        }
+    }
+}
+
+/// The MAC counters of a node, as reported in a [`NetDiagTlv::MacCounters`] TLV.
+///
+/// See RFC 2863 for the definitions of the fields.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagMacCounters {
+    /// Number of received packets discarded because of an unknown or unsupported protocol.
+    pub if_in_unknown_protos: u32,
+    /// Number of received packets discarded because of errors.
+    pub if_in_errors: u32,
+    /// Number of packets which could not be transmitted because of errors.
+    pub if_out_errors: u32,
+    /// Number of received unicast packets.
+    pub if_in_ucast_pkts: u32,
+    /// Number of received broadcast packets.
+    pub if_in_broadcast_pkts: u32,
+    /// Number of received packets discarded although free of errors.
+    pub if_in_discards: u32,
+    /// Number of transmitted unicast packets.
+    pub if_out_ucast_pkts: u32,
+    /// Number of transmitted broadcast packets.
+    pub if_out_broadcast_pkts: u32,
+    /// Number of outbound packets discarded although free of errors.
+    pub if_out_discards: u32,
+}
+
+impl From<&otNetworkDiagMacCounters> for NetDiagMacCounters {
+    fn from(c: &otNetworkDiagMacCounters) -> Self {
+        Self {
+            if_in_unknown_protos: c.mIfInUnknownProtos,
+            if_in_errors: c.mIfInErrors,
+            if_out_errors: c.mIfOutErrors,
+            if_in_ucast_pkts: c.mIfInUcastPkts,
+            if_in_broadcast_pkts: c.mIfInBroadcastPkts,
+            if_in_discards: c.mIfInDiscards,
+            if_out_ucast_pkts: c.mIfOutUcastPkts,
+            if_out_broadcast_pkts: c.mIfOutBroadcastPkts,
+            if_out_discards: c.mIfOutDiscards,
+        }
+    }
+}
+
+/// The MLE counters of a node, as reported in a [`NetDiagTlv::MleCounters`] TLV.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagMleCounters {
+    /// Number of times the node entered the disabled role.
+    pub disabled_role: u16,
+    /// Number of times the node entered the detached role.
+    pub detached_role: u16,
+    /// Number of times the node entered the child role.
+    pub child_role: u16,
+    /// Number of times the node entered the router role.
+    pub router_role: u16,
+    /// Number of times the node entered the leader role.
+    pub leader_role: u16,
+    /// Number of attach attempts while the node was detached.
+    pub attach_attempts: u16,
+    /// Number of changes to the partition ID.
+    pub partition_id_changes: u16,
+    /// Number of attempts to attach to a better partition.
+    pub better_partition_attach_attempts: u16,
+    /// Number of times the node changed its parent.
+    pub parent_changes: u16,
+    /// Total time tracked by the `*_time_millis` counters, in milliseconds (zero if not supported).
+    pub tracked_time_millis: u64,
+    /// Cumulative time spent in the disabled role, in milliseconds.
+    pub disabled_time_millis: u64,
+    /// Cumulative time spent in the detached role, in milliseconds.
+    pub detached_time_millis: u64,
+    /// Cumulative time spent in the child role, in milliseconds.
+    pub child_time_millis: u64,
+    /// Cumulative time spent in the router role, in milliseconds.
+    pub router_time_millis: u64,
+    /// Cumulative time spent in the leader role, in milliseconds.
+    pub leader_time_millis: u64,
+}
+
+impl From<&otNetworkDiagMleCounters> for NetDiagMleCounters {
+    fn from(c: &otNetworkDiagMleCounters) -> Self {
+        Self {
+            disabled_role: c.mDisabledRole,
+            detached_role: c.mDetachedRole,
+            child_role: c.mChildRole,
+            router_role: c.mRouterRole,
+            leader_role: c.mLeaderRole,
+            attach_attempts: c.mAttachAttempts,
+            partition_id_changes: c.mPartitionIdChanges,
+            better_partition_attach_attempts: c.mBetterPartitionAttachAttempts,
+            parent_changes: c.mParentChanges,
+            tracked_time_millis: c.mTrackedTime,
+            disabled_time_millis: c.mDisabledTime,
+            detached_time_millis: c.mDetachedTime,
+            child_time_millis: c.mChildTime,
+            router_time_millis: c.mRouterTime,
+            leader_time_millis: c.mLeaderTime,
+        }
+    }
+}
+
+/// A single entry of a [`NetDiagChildTable`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetDiagChildEntry {
+    /// The child ID (the lower bits of the RLOC16 of the child).
+    pub child_id: u16,
+    /// The encoded child timeout: the timeout in seconds is `2^(timeout - 4)`.
+    pub timeout: u8,
+    /// The link quality (0..=3).
+    pub link_quality: u8,
+    /// The link mode of the child.
+    pub mode: NetDiagLinkMode,
+}
+
+impl From<&otNetworkDiagChildEntry> for NetDiagChildEntry {
+    fn from(entry: &otNetworkDiagChildEntry) -> Self {
+        Self {
+            child_id: entry.mChildId(),
+            timeout: entry.mTimeout() as _,
+            link_quality: entry.mLinkQuality(),
+            mode: (&entry.mMode).into(),
+        }
+    }
+}
+
+/// A borrowed accessor over the IPv6 addresses of a [`NetDiagTlv::Ip6AddrList`] TLV.
+#[derive(Copy, Clone)]
+pub struct NetDiagIp6AddrList<'a>(&'a [otIp6Address]);
+
+impl<'a> NetDiagIp6AddrList<'a> {
+    /// The IPv6 addresses of the node.
+    pub fn addrs(&self) -> impl Iterator<Item = Ipv6Addr> + 'a {
+        self.0
+            .iter()
+            .map(|addr| Ipv6Addr::from(unsafe { addr.mFields.m8 }))
+    }
+}
+
+/// A borrowed accessor over the entries of a [`NetDiagTlv::ChildTable`] TLV.
+#[derive(Copy, Clone)]
+pub struct NetDiagChildTable<'a>(&'a [otNetworkDiagChildEntry]);
+
+impl<'a> NetDiagChildTable<'a> {
+    /// The children of the node.
+    pub fn entries(&self) -> impl Iterator<Item = NetDiagChildEntry> + 'a {
+        self.0.iter().map(NetDiagChildEntry::from)
+    }
+}
+
+/// A decoded Network Diagnostic TLV, as reported by [`NetDiagResponse::tlvs`].
+///
+/// The variants borrowing data are valid only within the `DIAG_GET` callback closure.
+#[derive(Copy, Clone)]
+pub enum NetDiagTlv<'a> {
+    /// The extended MAC address
+    ExtAddress(u64),
+    /// The RLOC16
+    Rloc16(u16),
+    /// The link mode
+    Mode(NetDiagLinkMode),
+    /// The child timeout, in seconds
+    Timeout(u32),
+    /// The connectivity
+    Connectivity(NetDiagConnectivity),
+    /// The Route64
+    Route64(NetDiagRoute64<'a>),
+    /// The leader data
+    LeaderData(NetDiagLeaderData),
+    /// The raw network data
+    NetworkData(&'a [u8]),
+    /// The list of IPv6 addresses
+    Ip6AddrList(NetDiagIp6AddrList<'a>),
+    /// The MAC counters
+    MacCounters(NetDiagMacCounters),
+    /// The battery level, in percent
+    BatteryLevel(u8),
+    /// The supply voltage, in millivolts
+    SupplyVoltage(u16),
+    /// The child table
+    ChildTable(NetDiagChildTable<'a>),
+    /// The supported channel pages
+    ChannelPages(&'a [u8]),
+    /// The maximum child timeout, in seconds
+    MaxChildTimeout(u32),
+    /// The EUI-64
+    Eui64(u64),
+    /// The Thread version
+    Version(u16),
+    /// The vendor name
+    VendorName(&'a str),
+    /// The vendor model
+    VendorModel(&'a str),
+    /// The vendor software version
+    VendorSwVersion(&'a str),
+    /// The Thread stack version
+    ThreadStackVersion(&'a str),
+    /// The vendor application URL
+    VendorAppUrl(&'a str),
+    /// The MLE counters
+    MleCounters(NetDiagMleCounters),
+    /// A TLV with a type not decoded by this API
+    Other(u8),
+}
+
+impl<'a> NetDiagTlv<'a> {
+    #[allow(non_upper_case_globals)]
+    fn from_ot(tlv: &'a otNetworkDiagTlv) -> Self {
+        let data = &tlv.mData;
+
+        unsafe {
+            match tlv.mType as u32 {
+                OT_NETWORK_DIAGNOSTIC_TLV_EXT_ADDRESS => {
+                    Self::ExtAddress(u64::from_be_bytes(data.mExtAddress.m8))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_SHORT_ADDRESS => Self::Rloc16(data.mAddr16),
+                OT_NETWORK_DIAGNOSTIC_TLV_MODE => Self::Mode((&data.mMode).into()),
+                OT_NETWORK_DIAGNOSTIC_TLV_TIMEOUT => Self::Timeout(data.mTimeout),
+                OT_NETWORK_DIAGNOSTIC_TLV_CONNECTIVITY => {
+                    Self::Connectivity((&data.mConnectivity).into())
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_ROUTE => Self::Route64(NetDiagRoute64(&data.mRoute)),
+                OT_NETWORK_DIAGNOSTIC_TLV_LEADER_DATA => {
+                    Self::LeaderData((&data.mLeaderData).into())
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_NETWORK_DATA => {
+                    let nd = &data.mNetworkData;
+                    Self::NetworkData(&nd.m8[..(nd.mCount as usize).min(nd.m8.len())])
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_IP6_ADDR_LIST => {
+                    let list = &data.mIp6AddrList;
+                    Self::Ip6AddrList(NetDiagIp6AddrList(
+                        &list.mList[..(list.mCount as usize).min(list.mList.len())],
+                    ))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_MAC_COUNTERS => {
+                    Self::MacCounters((&data.mMacCounters).into())
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_BATTERY_LEVEL => Self::BatteryLevel(data.mBatteryLevel),
+                OT_NETWORK_DIAGNOSTIC_TLV_SUPPLY_VOLTAGE => {
+                    Self::SupplyVoltage(data.mSupplyVoltage)
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_CHILD_TABLE => {
+                    let table = &data.mChildTable;
+                    Self::ChildTable(NetDiagChildTable(
+                        &table.mTable[..(table.mCount as usize).min(table.mTable.len())],
+                    ))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_CHANNEL_PAGES => {
+                    let pages = &data.mChannelPages;
+                    Self::ChannelPages(&pages.m8[..(pages.mCount as usize).min(pages.m8.len())])
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_MAX_CHILD_TIMEOUT => {
+                    Self::MaxChildTimeout(data.mMaxChildTimeout)
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_EUI64 => Self::Eui64(u64::from_be_bytes(data.mEui64.m8)),
+                OT_NETWORK_DIAGNOSTIC_TLV_VERSION => Self::Version(data.mVersion),
+                OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_NAME => {
+                    Self::VendorName(to_str(&data.mVendorName))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_MODEL => {
+                    Self::VendorModel(to_str(&data.mVendorModel))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_SW_VERSION => {
+                    Self::VendorSwVersion(to_str(&data.mVendorSwVersion))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_THREAD_STACK_VERSION => {
+                    Self::ThreadStackVersion(to_str(&data.mThreadStackVersion))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_VENDOR_APP_URL => {
+                    Self::VendorAppUrl(to_str(&data.mVendorAppUrl))
+                }
+                OT_NETWORK_DIAGNOSTIC_TLV_MLE_COUNTERS => {
+                    Self::MleCounters((&data.mMleCounters).into())
+                }
+                other => Self::Other(other as _),
+            }
+        }
+    }
+}
+
+/// A borrowed accessor over a Network Diagnostic `DIAG_GET` response.
+///
+/// Valid only within the [`OpenThread::netdiag_get`] callback closure; do not retain it.
+pub struct NetDiagResponse {
+    message: *const otMessage,
+    message_info: *const otMessageInfo,
+}
+
+impl NetDiagResponse {
+    /// The IPv6 address of the node which sent the response.
+    pub fn source(&self) -> Ipv6Addr {
+        Ipv6Addr::from(unsafe { (*self.message_info).mPeerAddr.mFields.m8 })
+    }
+
+    /// Decode the TLVs of the response
+    ///
+    /// Arguments:
+    /// - `f`: A closure that will be called for each decoded TLV. Once called for all TLVs,
+    ///   the closure will be called with `None`.
+    #[allow(non_upper_case_globals)]
+    pub fn tlvs<F>(&self, mut f: F) -> Result<(), OtError>
+    where
+        F: FnMut(Option<&NetDiagTlv>) -> Result<(), OtError>,
+    {
+        let mut iter: otNetworkDiagIterator = OT_NETWORK_DIAGNOSTIC_ITERATOR_INIT as _;
+        let mut tlv = otNetworkDiagTlv::default();
+
+        loop {
+            match unsafe { otThreadGetNextDiagnosticTlv(self.message, &mut iter, &mut tlv) } {
+                otError_OT_ERROR_NONE => f(Some(&NetDiagTlv::from_ot(&tlv)))?,
+                otError_OT_ERROR_NOT_FOUND => break,
+                err => ot!(err)?,
+            }
+        }
+
+        f(None)
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Send a Network Diagnostic Get request (`DIAG_GET`) for the `tlvs` TLV types to `dest`.
+    ///
+    /// `f` is invoked with each received response:
+    /// - For a unicast `dest` (`DIAG_GET.req`), the future completes once the single response
+    ///   is received, or with an error if the request timed out.
+    /// - For a multicast `dest` (`DIAG_GET.qry`), `f` is invoked with the answer of every node,
+    ///   and the future never completes on its own - drop it (e.g. after a timeout) once done
+    ///   collecting the answers.
+    ///
+    /// Only one request can be in flight at a time; a concurrent call is reported as a `BUSY` error.
+    /// For a unicast `dest`, responses coming from other nodes (i.e. late answers to a previous
+    /// multicast request) are ignored.
+    ///
+    /// NOTE: The future returned by this method is currently NOT `core::mem::forget` safe.
+    /// Its constructor MUST run, so don't call `core::mem::forget` on it.
+    pub async fn netdiag_get<F>(
+        &self,
+        dest: Ipv6Addr,
+        tlvs: NetDiagTlvs,
+        mut f: F,
+    ) -> Result<(), OtError>
+    where
+        F: FnMut(&NetDiagResponse),
+    {
+        {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            if state.ot.netdiag_callback.is_some() {
+                warn!("Another DIAG_GET in progress");
+                return Err(OtError::new(otError_OT_ERROR_BUSY));
+            }
+
+            // Clear any stale completion left over from a prior request whose future
+            // was dropped after the callback signalled but before `poll_wait` consumed it.
+            state.ot.netdiag_done.reset();
+
+            // Answers to a previous multicast request, whose future was dropped while the answers
+            // were still arriving, are delivered by OpenThread to this request's callback too;
+            // for a unicast request, only the answer of `dest` is expected
+            state.ot.netdiag_dest = (!dest.is_multicast()).then_some(dest);
+
+            {
+                let f: &mut dyn FnMut(&NetDiagResponse) = &mut f;
+
+                state.ot.netdiag_callback = Some(unsafe {
+                    core::mem::transmute::<
+                        &mut dyn FnMut(&NetDiagResponse),
+                        &'a mut dyn FnMut(&NetDiagResponse),
+                    >(f)
+                });
+
+                let mut types = [0; 64];
+                let count = tlvs.to_types(&mut types);
+
+                let res = ot!(unsafe {
+                    otThreadSendDiagnosticGet(
+                        state.ot.instance,
+                        &to_ot_ip6_addr(dest),
+                        types.as_ptr(),
+                        count,
+                        Some(OtContext::plat_c_netdiag_get_callback),
+                        state.ot.instance as *mut _,
+                    )
+                });
+
+                if res.is_err() {
+                    // Request never sent; release the slot.
+                    state.ot.netdiag_callback = None;
+                    res?;
+                }
+            }
+        }
+
+        // See the forget-safety note in `OpenThread::scan` - the same caveat
+        // applies to the closure reference stashed here.
+        let _guard = scopeguard::guard((), |_| {
+            let mut ot = self.activate();
+            ot.state().ot.netdiag_callback = None;
+        });
+
+        loop {
+            let error =
+                poll_fn(move |cx| self.activate().state().ot.netdiag_done.poll_wait(cx)).await;
+
+            if !dest.is_multicast() || error != otError_OT_ERROR_NONE {
+                break ot!(error);
+            }
+        }
+    }
+
+    /// Send a Network Diagnostic Reset request (`DIAG_RST`) for the `tlvs` TLV types to `dest`.
+    ///
+    /// Used to reset the counters of the destination node(s); OpenThread currently only
+    /// supports resetting the [`NetDiagTlvs::MAC_COUNTERS`].
+    pub fn netdiag_reset(&self, dest: Ipv6Addr, tlvs: NetDiagTlvs) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        let mut types = [0; 64];
+        let count = tlvs.to_types(&mut types);
+
+        ot!(unsafe {
+            otThreadSendDiagnosticReset(
+                state.ot.instance,
+                &to_ot_ip6_addr(dest),
+                types.as_ptr(),
+                count,
+            )
+        })
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_netdiag_get_callback(
+        error: otError,
+        message: *mut otMessage,
+        message_info: *const otMessageInfo,
+        context: *mut c_void,
+    ) {
+        let mut ot = Self::callback(context as *mut otInstance);
+        let state = ot.state();
+
+        if error == otError_OT_ERROR_NONE && !message.is_null() && !message_info.is_null() {
+            let source = Ipv6Addr::from(unsafe { (*message_info).mPeerAddr.mFields.m8 });
+
+            if state.ot.netdiag_dest.is_some_and(|dest| dest != source) {
+                debug!("Ignoring DIAG_GET response from {}", source);
+                return;
+            }
+
+            if let Some(f) = state.ot.netdiag_callback.as_mut() {
+                f(&NetDiagResponse {
+                    message,
+                    message_info,
+                });
+            }
+        }
+
+        state.ot.netdiag_done.signal(error);
+    }
+}
+fn to_str(buf: &[core::ffi::c_char]) -> &str {
+    let buf = unsafe { core::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), buf.len()) };
+
+    CStr::from_bytes_until_nul(buf)
+        .ok()
+        .and_then(|s| s.to_str().ok())
+        .unwrap_or("")
+}
@@ -7,10 +7,10 @@ use core::future::poll_fn;
 use core::net::Ipv6Addr;
 
 use crate::sys::{
-    otError_OT_ERROR_BUSY, otInstance, otIp6Address, otIp6Address__bindgen_ty_1, otPingSenderPing,
-    otPingSenderReply, otPingSenderStatistics, otPingSenderStop,
+    otError_OT_ERROR_BUSY, otInstance, otPingSenderPing, otPingSenderReply, otPingSenderStatistics,
+    otPingSenderStop,
 };
-use crate::{ot, OpenThread, OtContext, OtError};
+use crate::{ot, to_ot_ip6_addr, OpenThread, OtContext, OtError};
 
 /// The configuration of a ping run (`otPingSenderConfig`).
 ///
@@ -122,13 +122,6 @@ impl From<&otPingSenderStatistics> for PingStatistics {
         }
     }
 }
-
-fn to_ot_ip6_addr(addr: Ipv6Addr) -> otIp6Address {
-    otIp6Address {
-        mFields: otIp6Address__bindgen_ty_1 { m8: addr.octets() },
-    }
-}
-
 impl<'a> OpenThread<'a> {
     /// Ping the destination described by `config`, invoking `f` for each
     /// received reply, and return the run's statistics once it completes
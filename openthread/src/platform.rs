@@ -418,6 +418,103 @@ extern "C" fn otPlatSettingsWipe(instance: *mut otInstance) {
     OtContext::callback(instance).plat_settings_wipe()
 }
 
+// --- mDNS socket (`mdns` feature) ---
+//
+// Bridged to the `MdnsSocket` driven by `OpenThread::run_mdns`; see `mdns.rs`.
+
+#[cfg(feature = "mdns")]
+#[no_mangle]
+extern "C" fn otPlatMdnsSetListeningEnabled(
+    instance: *mut otInstance,
+    enable: bool,
+    infra_if_index: u32,
+) -> otError {
+    OtContext::callback(instance).plat_mdns_set_listening(enable, infra_if_index);
+
+    otError_OT_ERROR_NONE
+}
+
+#[cfg(feature = "mdns")]
+#[no_mangle]
+extern "C" fn otPlatMdnsSendMulticast(
+    instance: *mut otInstance,
+    message: *mut crate::sys::otMessage,
+    _infra_if_index: u32,
+) {
+    OtContext::callback(instance).plat_mdns_send(message, None);
+}
+
+#[cfg(feature = "mdns")]
+#[no_mangle]
+extern "C" fn otPlatMdnsSendUnicast(
+    instance: *mut otInstance,
+    message: *mut crate::sys::otMessage,
+    address: *const crate::sys::otPlatMdnsAddressInfo,
+) {
+    OtContext::callback(instance).plat_mdns_send(message, Some(unsafe { &*address }));
+}
+
+// --- Infrastructure interface (`border-routing` feature) ---
+//
+// Bridged to the `InfraIf` driven by `OpenThread::run_infra_if`; see `border_routing.rs`.
+
+#[cfg(feature = "border-routing")]
+#[no_mangle]
+extern "C" fn otPlatInfraIfHasAddress(
+    _infra_if_index: u32,
+    address: *const crate::sys::otIp6Address,
+) -> bool {
+    OtContext::callback(core::ptr::null_mut()).plat_infra_if_has_address(unsafe { &*address })
+}
+
+#[cfg(feature = "border-routing")]
+#[no_mangle]
+extern "C" fn otPlatInfraIfSendIcmp6Nd(
+    _infra_if_index: u32,
+    dest_address: *const crate::sys::otIp6Address,
+    buffer: *const u8,
+    buffer_length: u16,
+) -> otError {
+    OtContext::callback(core::ptr::null_mut())
+        .plat_infra_if_send(unsafe { &*dest_address }, unsafe {
+            core::slice::from_raw_parts(buffer, buffer_length as _)
+        })
+}
+
+#[cfg(feature = "border-routing")]
+#[no_mangle]
+extern "C" fn otPlatInfraIfDiscoverNat64Prefix(_infra_if_index: u32) -> otError {
+    // Discovering the NAT64 prefix of the infrastructure network (RFC 7050) is not supported
+    crate::sys::otError_OT_ERROR_NOT_IMPLEMENTED
+}
+
+// --- Enhanced-ACK Based Probing (`link-metrics-subject` feature) ---
+//
+// Tracks the Initiators which have configured the probing and hands them to the radio;
+// see `link_metrics.rs`.
+
+#[cfg(feature = "link-metrics-subject")]
+#[no_mangle]
+extern "C" fn otPlatRadioConfigureEnhAckProbing(
+    instance: *const otInstance,
+    link_metrics: crate::sys::otLinkMetrics,
+    short_address: u16,
+    ext_address: *const crate::sys::otExtAddress,
+) -> otError {
+    if ext_address.is_null() {
+        return crate::sys::otError_OT_ERROR_INVALID_ARGS;
+    }
+
+    // Unlike `otPlatRadioSetExtendedAddress`, the address is in big-endian byte order.
+    let ext_address = u64::from_be_bytes(unsafe { (*ext_address).m8 });
+
+    OtContext::callback(instance).plat_radio_configure_enh_ack_probing(
+        &link_metrics,
+        short_address,
+        ext_address,
+    )
+}
+
 /// NOTE:
 /// While the correct signature should be something like:
 /// ```ignore
@@ -144,13 +144,20 @@ bitflags! {
         ///
         /// A radio doing its own RX ACKs *without* this capability should answer every data poll FP = 1.
         const SRC_MATCH = 0x40;
+        /// The radio's ACK engine answers IEEE 802.15.4-2015 frames with Enhanced ACKs, and adds the Link Metrics IE
+        /// configured with [`Radio::set_enh_ack_probing_config`] to those sent to the Enhanced-ACK Based Probing
+        /// Initiators (Thread 1.2 Link Metrics Subject).
+        ///
+        /// Without this capability, the Initiators trying to configure Enhanced-ACK Based Probing
+        /// with this device are answered with an error.
+        const ENH_ACK_PROBING = 0x80;
     }
 }
 
 impl MacCapabilities {
     /// The MAC-offload set a radio must provide to be driven by the OpenThread stack.
     ///
-    /// This is everything except the two capabilities a software layer above
+    /// This is everything except the capabilities a software layer above
     /// the radio cannot supply, and whose absence costs a diagnostic or an
     /// optimization rather than correct Thread operation:
     ///
@@ -163,13 +170,17 @@ impl MacCapabilities {
     ///   already dropped. A radio that filters in hardware but cannot be told
     ///   to stop simply cannot sniff - and sniffing is not part of operating a
     ///   Thread network.
+    /// - [`ENH_ACK_PROBING`](Self::ENH_ACK_PROBING): like the source-match
+    ///   table, the Link Metrics IE only matters to whoever sends the ACKs;
+    ///   without it, the device is merely not an Enhanced-ACK Based Probing Subject.
     ///
     /// A radio reporting less than this - a bare PHY, typically - must be
     /// wrapped by the user in a [`MacRadio`], which emulates the missing pieces
     /// in software.
     pub const REQUIRED: Self = Self::all()
         .difference(Self::SRC_MATCH)
-        .difference(Self::PROMISCUOUS);
+        .difference(Self::PROMISCUOUS)
+        .difference(Self::ENH_ACK_PROBING);
 
     /// Panic unless these capabilities cover [`Self::REQUIRED`].
     pub(crate) fn assert_required(&self) {
@@ -339,6 +350,106 @@ impl Default for SrcMatchConfig {
     }
 }
 
+/// Capacity of the [`EnhAckProbingConfig`] table.
+///
+/// On overflow the glue answers `OT_ERROR_NO_BUFS`, which OpenThread reports to the
+/// Initiator as a failure to configure the probing.
+pub const ENH_ACK_PROBING_CAPACITY: usize = 4;
+
+/// An Initiator which has configured Enhanced-ACK Based Probing with this device
+/// (Thread 1.2 Link Metrics Subject), see [`EnhAckProbingConfig`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EnhAckProbingInitiator {
+    /// The short address of the Initiator
+    pub short_addr: u16,
+    /// The extended address of the Initiator
+    pub ext_addr: u64,
+    /// Whether to report the LQI of the frame being ACKed
+    pub lqi: bool,
+    /// Whether to report the link margin of the frame being ACKed
+    pub link_margin: bool,
+    /// Whether to report the RSSI of the frame being ACKed
+    pub rssi: bool,
+}
+
+impl EnhAckProbingInitiator {
+    /// The maximum length of the metric values returned by [`Self::link_metrics`].
+    pub const MAX_LINK_METRICS_LEN: usize = 3;
+
+    /// Compute the metric values to be reported to the Initiator in the Link Metrics IE of an
+    /// Enhanced ACK, in the order and with the scaling of the Thread specification
+    /// (LQI, then link margin, then RSSI).
+    ///
+    /// Arguments:
+    /// - `lqi`: The LQI of the frame being ACKed
+    /// - `rssi`: The RSSI of the frame being ACKed, in dBm
+    /// - `noise_floor`: The noise floor the link margin is computed against, in dBm
+    ///   (typically the receive sensitivity of the radio)
+    /// - `buf`: The buffer to store the values in; at least [`Self::MAX_LINK_METRICS_LEN`] bytes long
+    ///
+    /// Returns:
+    /// - The length of the values stored in `buf`
+    pub fn link_metrics(&self, lqi: u8, rssi: i8, noise_floor: i8, buf: &mut [u8]) -> usize {
+        let mut len = 0;
+
+        if self.lqi {
+            buf[len] = lqi;
+            len += 1;
+        }
+
+        if self.link_margin {
+            let link_margin = (rssi as i16 - noise_floor as i16).clamp(0, 130);
+
+            buf[len] = (link_margin * 255 / 130) as u8;
+            len += 1;
+        }
+
+        if self.rssi {
+            let rssi = (rssi as i16).clamp(-130, 0) + 130;
+
+            buf[len] = (rssi * 255 / 130) as u8;
+            len += 1;
+        }
+
+        len
+    }
+}
+
+/// The Enhanced-ACK Based Probing table (Thread 1.2 Link Metrics Subject), i.e. the Initiators whose
+/// Enhanced ACKs have to carry a Link Metrics IE, and the metrics to report in it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EnhAckProbingConfig {
+    /// The Initiators.
+    pub initiators: heapless::Vec<EnhAckProbingInitiator, ENH_ACK_PROBING_CAPACITY>,
+}
+
+impl EnhAckProbingConfig {
+    pub const fn new() -> Self {
+        Self {
+            initiators: heapless::Vec::new(),
+        }
+    }
+
+    /// The Initiator - if any - an ack-requesting frame arriving from `src_short` / `src_ext` is from.
+    ///
+    /// An address the frame does not carry is to be passed as the broadcast one
+    /// (`0xffff` / `0xffffffffffffffff`), which matches no Initiator.
+    pub fn initiator(&self, src_short: u16, src_ext: u64) -> Option<&EnhAckProbingInitiator> {
+        self.initiators.iter().find(|initiator| {
+            (src_short != u16::MAX && initiator.short_addr == src_short)
+                || (src_ext != u64::MAX && initiator.ext_addr == src_ext)
+        })
+    }
+}
+
+impl Default for EnhAckProbingConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Meta-data associated with the received IEEE 802.15.4 frame
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -450,6 +561,20 @@ pub trait Radio {
     /// but such radios must be wrapped by the user in a [`MacRadio`] then.
     async fn set_src_match_config(&mut self, config: &SrcMatchConfig) -> Result<(), Self::Error>;
 
+    /// Set the radio Enhanced-ACK Based Probing configuration.
+    ///
+    /// NOTE:
+    /// Only called for radios reporting `MacCapabilities::ENH_ACK_PROBING`, hence the
+    /// default no-op implementation for the radios which do not.
+    async fn set_enh_ack_probing_config(
+        &mut self,
+        config: &EnhAckProbingConfig,
+    ) -> Result<(), Self::Error> {
+        let _ = config;
+
+        Ok(())
+    }
+
     /// Set the radio to receive mode on `channel`.
     ///
     /// Arguments
@@ -497,6 +622,24 @@ pub trait Radio {
         Ok(crate::sys::OT_RADIO_RSSI_INVALID as i8)
     }
 
+    /// Read the instantaneous RSSI (the channel energy) on `channel`, in dBm.
+    ///
+    /// Sampled while the radio is receiving on `channel`; after the reading, the radio is
+    /// put back into receive mode with [`Radio::set_receive`].
+    ///
+    /// The default implementation takes a short (1 ms) energy scan sample, so radios which
+    /// can read the RSSI register directly should override it.
+    ///
+    /// Arguments
+    /// - `channel`: The channel to read the RSSI on.
+    ///
+    /// NOTE:
+    /// Only called for radios reporting [`Capabilities::ENERGY_SCAN`]; OpenThread gets an
+    /// invalid RSSI (+127 dBm) for the radios which do not.
+    async fn rssi(&mut self, channel: u8) -> Result<i8, Self::Error> {
+        self.energy_scan(channel, 1).await
+    }
+
     /// Transmit a radio frame.
     ///
     /// If the radio _does_ support `MacCapabilities::TX_ACK`:
@@ -560,10 +703,21 @@ where
         T::set_src_match_config(self, entries).await
     }
 
+    async fn set_enh_ack_probing_config(
+        &mut self,
+        config: &EnhAckProbingConfig,
+    ) -> Result<(), Self::Error> {
+        T::set_enh_ack_probing_config(self, config).await
+    }
+
     async fn energy_scan(&mut self, channel: u8, duration_millis: u16) -> Result<i8, Self::Error> {
         T::energy_scan(self, channel, duration_millis).await
     }
 
+    async fn rssi(&mut self, channel: u8) -> Result<i8, Self::Error> {
+        T::rssi(self, channel).await
+    }
+
     async fn set_receive(&mut self, channel: u8) -> Result<(), Self::Error> {
         T::set_receive(self, channel).await
     }
@@ -587,3 +741,19 @@ where
         T::receive(self, psdu_buf).await
     }
 }
+
+/// Convert from RSSI (Received Signal Strength Indicator) to LQI (Link Quality
+/// Indication), for radios which do not report the LQI of the received frames.
+///
+/// RSSI is a measure of incoherent (raw) RF power in a channel. LQI is a
+/// cumulative value used in multi-hop networks to assess the cost of a link.
+pub(crate) fn rssi_to_lqi(rssi: i8) -> u8 {
+    if rssi < -80 {
+        0
+    } else if rssi > -30 {
+        0xff
+    } else {
+        let lqi_convert = ((rssi as u32).wrapping_add(80)) * 255;
+        (lqi_convert / 50) as u8
+    }
+}
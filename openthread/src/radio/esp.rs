@@ -166,8 +166,10 @@ impl Radio for EspRadio<'_> {
             phy: Capabilities::ACK_TIMEOUT.union(Capabilities::CSMA_BACKOFF),
             // .union(Capabilities::AUTO_SLEEP) TODO: Depends on coex being off in ESP-IDF
             //
-            // TODO: Upstream `SRC_MATCH` support to `esp-radio`.
-            mac: MacCapabilities::all().difference(MacCapabilities::SRC_MATCH),
+            // TODO: Upstream `SRC_MATCH` and Enhanced-ACK IE support to `esp-radio`.
+            mac: MacCapabilities::all()
+                .difference(MacCapabilities::SRC_MATCH)
+                .difference(MacCapabilities::ENH_ACK_PROBING),
             // TODO: Report the ESP 802.15.4 hardware's real figure.
             receive_sensitivity: RadioCaps::DEFAULT_RECEIVE_SENSITIVITY,
             default_tx_power: Self::DEFAULT_TX_POWER,
@@ -7,9 +7,11 @@ use embassy_futures::select::{select, Either};
 use embassy_time::Instant;
 
 use crate::fmt::Bytes;
+use crate::radio::rssi_to_lqi;
 use crate::sys::OT_RADIO_FRAME_MAX_SIZE;
 use crate::{
-    Config, MacCapabilities, PsduMeta, Radio, RadioCaps, RadioError, RadioErrorKind, SrcMatchConfig,
+    Config, EnhAckProbingConfig, EnhAckProbingInitiator, MacCapabilities, PsduMeta, Radio,
+    RadioCaps, RadioError, RadioErrorKind, SrcMatchConfig,
 };
 
 pub(crate) use mac_utils::MacHeader;
@@ -79,6 +81,9 @@ pub struct MacRadioResources<const RX_QUEUE_SIZE: usize = DEFAULT_RX_QUEUE_SIZE>
     /// The source-address-match table, consulted for the Frame Pending bit
     /// of the software ACKs answering data polls (see [`SrcMatchConfig`]).
     src_match: SrcMatchConfig,
+    /// The Enhanced-ACK Based Probing table, consulted for the Link Metrics IE
+    /// of the software Enhanced ACKs (see [`EnhAckProbingConfig`]).
+    enh_ack_probing: EnhAckProbingConfig,
 }
 
 impl<const RX_QUEUE_SIZE: usize> MacRadioResources<RX_QUEUE_SIZE> {
@@ -91,6 +96,7 @@ impl<const RX_QUEUE_SIZE: usize> MacRadioResources<RX_QUEUE_SIZE> {
             ack_psdu_buf: MaybeUninit::uninit(),
             pending_rx: MaybeUninit::uninit(),
             src_match: SrcMatchConfig::new(),
+            enh_ack_probing: EnhAckProbingConfig::new(),
         }
     }
 
@@ -98,11 +104,24 @@ impl<const RX_QUEUE_SIZE: usize> MacRadioResources<RX_QUEUE_SIZE> {
     ///
     /// Returns the borrowed pieces, with `RX_QUEUE_SIZE` erased into the queue's
     /// slice length - which is what keeps [`MacRadio`] free of a const parameter.
-    fn init(&mut self) -> (&mut [u8], &mut [PendingRxFrame], &mut SrcMatchConfig) {
+    #[allow(clippy::type_complexity)]
+    fn init(
+        &mut self,
+    ) -> (
+        &mut [u8],
+        &mut [PendingRxFrame],
+        &mut SrcMatchConfig,
+        &mut EnhAckProbingConfig,
+    ) {
         let ack_psdu_buf = self.ack_psdu_buf.write([0; OT_RADIO_FRAME_MAX_SIZE as _]);
         let pending_rx = self.pending_rx.write([Self::INIT_FRAME; RX_QUEUE_SIZE]);
 
-        (ack_psdu_buf, pending_rx, &mut self.src_match)
+        (
+            ack_psdu_buf,
+            pending_rx,
+            &mut self.src_match,
+            &mut self.enh_ack_probing,
+        )
     }
 }
 
@@ -137,6 +156,11 @@ impl<const RX_QUEUE_SIZE: usize> Default for MacRadioResources<RX_QUEUE_SIZE> {
 /// higher-priority executor via [`crate::ProxyRadio`] / [`crate::PhyRadioRunner`]
 /// is strongly advisable - the wrapping then happens around the PHY radio on
 /// the runner's side.
+///
+/// IEEE 802.15.4-2015 frames are answered with Enhanced ACKs, carrying the Link
+/// Metrics IE for the Enhanced-ACK Based Probing Initiators (see
+/// [`EnhAckProbingConfig`]). As the wrapper has no access to the MAC keys, the
+/// Enhanced ACKs are sent unsecured, which OpenThread accepts.
 pub struct MacRadio<'a, R, T> {
     /// The wrapped radio.
     radio: R,
@@ -169,6 +193,12 @@ pub struct MacRadio<'a, R, T> {
     /// The source-address-match table, consulted for the Frame Pending bit
     /// of the software ACKs answering data polls (see [`SrcMatchConfig`]).
     src_match: &'a mut SrcMatchConfig,
+    /// The Enhanced-ACK Based Probing table, consulted for the Link Metrics IE
+    /// of the software Enhanced ACKs (see [`EnhAckProbingConfig`]).
+    enh_ack_probing: &'a mut EnhAckProbingConfig,
+    /// The receive sensitivity of the wrapped radio: the noise floor the link
+    /// margin reported in the Link Metrics IE is computed against.
+    receive_sensitivity: i8,
     /// The channel the radio was last commanded onto (by `set_receive` or by
     /// a `transmit`) - the software ACKs are sent on it, since a radio is
     /// only ever on one channel at a time.
@@ -220,7 +250,7 @@ where
         timer: T,
         resources: &'a mut MacRadioResources<RX_QUEUE_SIZE>,
     ) -> Self {
-        let (ack_psdu_buf, pending_rx, src_match) = resources.init();
+        let (ack_psdu_buf, pending_rx, src_match, enh_ack_probing) = resources.init();
 
         Self {
             radio,
@@ -232,6 +262,8 @@ where
             ack_psdu_buf,
             pending_rx: PendingRx::new(pending_rx),
             src_match,
+            enh_ack_probing,
+            receive_sensitivity: RadioCaps::DEFAULT_RECEIVE_SENSITIVITY,
             channel: 11,
             power: RadioCaps::DEFAULT_TX_POWER,
             promiscuous: false,
@@ -252,7 +284,11 @@ where
     ///
     /// `psdu` must not alias `self`'s buffers (callers pass caller-owned or
     /// stack copies).
-    async fn screen_incoming(&mut self, psdu: &[u8]) -> Result<bool, MacRadioError<R::Error>> {
+    async fn screen_incoming(
+        &mut self,
+        psdu: &[u8],
+        meta: &PsduMeta,
+    ) -> Result<bool, MacRadioError<R::Error>> {
         if self.mac_caps == MacCapabilities::all() {
             return Ok(true);
         }
@@ -316,7 +352,35 @@ where
                         self.mac_header.src_ext_addr,
                     );
 
-                let ack_len = self.mac_header.prep_ack(self.ack_psdu_buf, frame_pending);
+                // An Enhanced-ACK Based Probing Initiator gets the metrics of
+                // the frame in the Enhanced ACK.
+                let mut link_metrics = [0; EnhAckProbingInitiator::MAX_LINK_METRICS_LEN];
+                let link_metrics_len = self
+                    .mac_header
+                    .is_version_2015()
+                    .then(|| {
+                        self.enh_ack_probing
+                            .initiator(self.mac_header.src_short_addr, self.mac_header.src_ext_addr)
+                    })
+                    .flatten()
+                    .map(|initiator| {
+                        let rssi = meta.rssi.unwrap_or(0);
+                        let lqi = meta.lqi.unwrap_or_else(|| rssi_to_lqi(rssi));
+
+                        initiator.link_metrics(
+                            lqi,
+                            rssi,
+                            self.receive_sensitivity,
+                            &mut link_metrics,
+                        )
+                    })
+                    .unwrap_or(0);
+
+                let ack_len = self.mac_header.prep_ack(
+                    self.ack_psdu_buf,
+                    frame_pending,
+                    &link_metrics[..link_metrics_len],
+                );
 
                 trace!(
                     "MacRadio, about to transmit ACK: {}",
@@ -374,7 +438,15 @@ where
             mac.remove(MacCapabilities::SRC_MATCH);
         }
 
+        // Same for the Link Metrics IE of the Enhanced ACKs.
+        if caps.mac.contains(MacCapabilities::RX_ACK)
+            && !caps.mac.contains(MacCapabilities::ENH_ACK_PROBING)
+        {
+            mac.remove(MacCapabilities::ENH_ACK_PROBING);
+        }
+
         self.power = caps.default_tx_power;
+        self.receive_sensitivity = caps.receive_sensitivity;
 
         Ok(RadioCaps { mac, ..caps })
     }
@@ -430,6 +502,24 @@ where
         }
     }
 
+    async fn set_enh_ack_probing_config(
+        &mut self,
+        config: &EnhAckProbingConfig,
+    ) -> Result<(), Self::Error> {
+        if self.mac_caps.contains(MacCapabilities::ENH_ACK_PROBING) {
+            // The inner radio's own acking adds the IE - hand it down.
+            self.radio
+                .set_enh_ack_probing_config(config)
+                .await
+                .map_err(Self::Error::Io)
+        } else {
+            // This wrapper's software ACKs consult the copy.
+            *self.enh_ack_probing = config.clone();
+
+            Ok(())
+        }
+    }
+
     async fn energy_scan(&mut self, channel: u8, duration_millis: u16) -> Result<i8, Self::Error> {
         // Energy scan involves no MAC-layer processing - pass through.
         self.radio
@@ -438,6 +528,11 @@ where
             .map_err(Self::Error::Io)
     }
 
+    async fn rssi(&mut self, channel: u8) -> Result<i8, Self::Error> {
+        // Same as the energy scan - pass through.
+        self.radio.rssi(channel).await.map_err(Self::Error::Io)
+    }
+
     async fn transmit(
         &mut self,
         psdu: &[u8],
@@ -520,7 +615,7 @@ where
                     let mut crossing = [0; OT_RADIO_FRAME_MAX_SIZE as _];
                     crossing[..meta.len].copy_from_slice(&self.ack_psdu_buf[..meta.len]);
 
-                    if self.screen_incoming(&crossing[..meta.len]).await?
+                    if self.screen_incoming(&crossing[..meta.len], &meta).await?
                         && !self.pending_rx.push_back(meta, &crossing[..meta.len])
                     {
                         trace!(
@@ -578,7 +673,10 @@ where
                 psdu_meta
             );
 
-            if self.screen_incoming(&psdu_buf[..psdu_meta.len]).await? {
+            if self
+                .screen_incoming(&psdu_buf[..psdu_meta.len], &psdu_meta)
+                .await?
+            {
                 trace!(
                     "MacRadio, received frame: {}",
                     Bytes(&psdu_buf[..psdu_meta.len])
@@ -738,6 +836,9 @@ mod mac_utils {
         /// 0xffffffffffffffff if the Frame does not contain an extended address
         /// or if the extended address is the broadcast extended address
         pub dst_ext_addr: u64,
+        /// Source PAN ID. The destination PAN ID if the Frame elides it
+        /// (PAN ID compression)
+        pub src_pan_id: u16,
         /// Source short address
         /// 0xffff if the Frame does not carry a short source address
         pub src_short_addr: u16,
@@ -750,6 +851,14 @@ mod mac_utils {
     impl MacHeader {
         /// The length of an Imm-ACK PSDU.
         pub const ACK_PSDU_LEN: usize = Self::FCF_LEN + Self::SEQ_LEN + Self::CRC_LEN;
+        /// The maximum length of an Enh-ACK PSDU: an Imm-ACK plus the destination
+        /// PAN ID and extended address, plus the Link Metrics IE.
+        pub const ENH_ACK_PSDU_MAX_LEN: usize = Self::ACK_PSDU_LEN
+            + 2
+            + 8
+            + Self::IE_HEADER_LEN
+            + Self::THREAD_IE_VENDOR_HEADER_LEN
+            + Self::LINK_METRICS_MAX_LEN;
 
         /// The broadcast PAN ID.
         pub const BROADCAST_PAN_ID: u16 = u16::MAX;
@@ -773,14 +882,28 @@ mod mac_utils {
         #[allow(unused)]
         const FCF_PENDING_BIT: u16 = 1 << 4;
         const FCF_ACK_REQ_BIT: u16 = 1 << 5;
-        #[allow(unused)]
         const FCF_PAN_ID_COMPRESSION_MASK: u16 = 1 << 6;
+        const FCF_SEQ_SUPPRESSION_BIT: u16 = 1 << 8;
+        const FCF_IE_PRESENT_BIT: u16 = 1 << 9;
         const FCF_FRAME_DST_ADDR_MODE_SHIFT: u16 = 10;
         const FCF_FRAME_DST_ADDR_MODE_MASK: u16 = 0x03 << Self::FCF_FRAME_DST_ADDR_MODE_SHIFT;
         const FCF_FRAME_VERSION_SHIFT: u16 = 12;
         const FCF_FRAME_VERSION_MASK: u16 = 0x03 << Self::FCF_FRAME_VERSION_SHIFT;
         const FCF_FRAME_SRC_ADDR_MODE_SHIFT: u16 = 14;
         const FCF_FRAME_SRC_ADDR_MODE_MASK: u16 = 0x03 << Self::FCF_FRAME_SRC_ADDR_MODE_SHIFT;
+        const FCF_FRAME_VERSION_2015: u16 = 2 << Self::FCF_FRAME_VERSION_SHIFT;
+
+        const IE_HEADER_LEN: usize = 2;
+        /// The Element ID of a vendor-specific Header IE
+        const IE_VENDOR_ELEMENT_ID: u16 = 0x00;
+        const IE_ELEMENT_ID_SHIFT: u16 = 7;
+        const THREAD_IE_VENDOR_HEADER_LEN: usize = 4;
+        /// The Thread Group OUI (0xEAB89B), little-endian
+        const THREAD_IE_OUI: [u8; 3] = [0x9b, 0xb8, 0xea];
+        /// The sub-type of the Thread Enhanced-ACK Probing (Link Metrics) IE
+        const THREAD_IE_ENH_ACK_PROBING: u8 = 0x00;
+        /// The maximum number of values in the Enhanced-ACK Probing IE
+        const LINK_METRICS_MAX_LEN: usize = 3;
 
         /// Create a new empty MAC header.
         pub const fn new() -> Self {
@@ -790,6 +913,7 @@ mod mac_utils {
                 pan_id: 0,
                 dst_short_addr: 0,
                 dst_ext_addr: 0,
+                src_pan_id: 0,
                 src_short_addr: 0,
                 src_ext_addr: 0,
             }
@@ -809,53 +933,63 @@ mod mac_utils {
             self.seq = psdu[Self::SEQ_OFFSET];
 
             let _frame_type = FrameType::get(self.fcf)?;
-            let _frame_version = FrameVersion::get(self.fcf)?;
+            let frame_version = FrameVersion::get(self.fcf)?;
             let dst_addr_mode = FrameAddrMode::get_dst(self.fcf)?;
+            let src_addr_mode = FrameAddrMode::get_src(self.fcf)?;
+
+            if matches!(frame_version, FrameVersion::IEEE802154_2015)
+                && (self.fcf & Self::FCF_SEQ_SUPPRESSION_BIT) != 0
+            {
+                // Not used by Thread, and the ACKs could not be matched anyway
+                return None;
+            }
+
+            let (dst_pan_id_present, src_pan_id_present) =
+                Self::pan_ids_present(self.fcf, &frame_version, &dst_addr_mode, &src_addr_mode);
+
+            let mut offs = Self::ADDRS_OFFSET;
+
+            self.pan_id = if dst_pan_id_present {
+                Self::ensure_len(psdu, offs + 2 + Self::CRC_LEN)?;
+                offs += 2;
+
+                u16::from_le_bytes(unwrap!(psdu[offs - 2..offs].try_into()))
+            } else {
+                Self::BROADCAST_PAN_ID
+            };
 
             match dst_addr_mode {
                 FrameAddrMode::NotPresent => {
-                    self.pan_id = Self::BROADCAST_PAN_ID;
                     self.dst_short_addr = Self::BROADCAST_SHORT_ADDR;
                     self.dst_ext_addr = Self::BROADCAST_EXT_ADDR;
                 }
                 FrameAddrMode::Short => {
-                    Self::ensure_len(psdu, Self::ADDRS_OFFSET + 2 + 2 + Self::CRC_LEN)?;
+                    Self::ensure_len(psdu, offs + 2 + Self::CRC_LEN)?;
 
-                    self.pan_id = u16::from_le_bytes(unwrap!(psdu[3..5].try_into()));
-                    self.dst_short_addr = u16::from_le_bytes(unwrap!(psdu[5..7].try_into()));
+                    self.dst_short_addr =
+                        u16::from_le_bytes(unwrap!(psdu[offs..offs + 2].try_into()));
                     self.dst_ext_addr = Self::BROADCAST_EXT_ADDR;
+                    offs += 2;
                 }
                 FrameAddrMode::Extended => {
-                    Self::ensure_len(psdu, Self::ADDRS_OFFSET + 2 + 8 + Self::CRC_LEN)?;
+                    Self::ensure_len(psdu, offs + 8 + Self::CRC_LEN)?;
 
-                    self.pan_id = u16::from_le_bytes(unwrap!(psdu[3..5].try_into()));
                     // See platform.rs, `otPlatRadioSetExtendedAddress` impl
-                    self.dst_ext_addr = u64::from_le_bytes(unwrap!(psdu[5..13].try_into()));
+                    self.dst_ext_addr =
+                        u64::from_le_bytes(unwrap!(psdu[offs..offs + 8].try_into()));
                     self.dst_short_addr = Self::BROADCAST_SHORT_ADDR;
+                    offs += 8;
                 }
             }
 
-            let src_addr_mode = FrameAddrMode::get_src(self.fcf)?;
-
-            // Offset just past the destination addressing fields.
-            let mut offs = Self::ADDRS_OFFSET
-                + match dst_addr_mode {
-                    FrameAddrMode::NotPresent => 0,
-                    FrameAddrMode::Short => 2 + 2,
-                    FrameAddrMode::Extended => 2 + 8,
-                };
-
-            // The source PAN ID is elided when the PAN ID Compression bit is
-            // set - the common case for intra-PAN traffic, data polls
-            // included. (The elision rule modeled here is the 2003/2006 one;
-            // the 2015 frame version's table-based rules are not - the
-            // consumers of the source fields only look at 2006-era command
-            // frames.)
-            if !matches!(src_addr_mode, FrameAddrMode::NotPresent)
-                && (self.fcf & Self::FCF_PAN_ID_COMPRESSION_MASK) == 0
-            {
+            self.src_pan_id = if src_pan_id_present {
+                Self::ensure_len(psdu, offs + 2 + Self::CRC_LEN)?;
                 offs += 2;
-            }
+
+                u16::from_le_bytes(unwrap!(psdu[offs - 2..offs].try_into()))
+            } else {
+                self.pan_id
+            };
 
             match src_addr_mode {
                 FrameAddrMode::NotPresent => {
@@ -881,6 +1015,36 @@ mod mac_utils {
             Some(())
         }
 
+        /// Return whether the destination and the source PAN IDs are present in a frame.
+        ///
+        /// The 2003/2006 frame versions elide the source PAN ID when the PAN ID
+        /// Compression bit is set; the 2015 frame version follows the table of
+        /// IEEE 802.15.4-2015, 7.2.1.5 instead.
+        fn pan_ids_present(
+            fcf: u16,
+            frame_version: &FrameVersion,
+            dst_addr_mode: &FrameAddrMode,
+            src_addr_mode: &FrameAddrMode,
+        ) -> (bool, bool) {
+            let compressed = (fcf & Self::FCF_PAN_ID_COMPRESSION_MASK) != 0;
+
+            let dst_present = !matches!(dst_addr_mode, FrameAddrMode::NotPresent);
+            let src_present = !matches!(src_addr_mode, FrameAddrMode::NotPresent);
+
+            match frame_version {
+                FrameVersion::IEEE802154_2003 | FrameVersion::IEEE802154_2006 => {
+                    (dst_present, src_present && !compressed)
+                }
+                FrameVersion::IEEE802154_2015 => match (dst_addr_mode, src_addr_mode) {
+                    (FrameAddrMode::NotPresent, FrameAddrMode::NotPresent) => (compressed, false),
+                    (_, FrameAddrMode::NotPresent) => (!compressed, false),
+                    (FrameAddrMode::NotPresent, _) => (false, !compressed),
+                    (FrameAddrMode::Extended, FrameAddrMode::Extended) => (!compressed, false),
+                    _ => (true, !compressed),
+                },
+            }
+        }
+
         /// Return `true` if the frame needs an ACK.
         #[inline(always)]
         pub fn needs_ack(&self) -> bool {
@@ -897,13 +1061,37 @@ mod mac_utils {
             matches!(FrameType::get(self.fcf), Some(FrameType::Command))
         }
 
+        /// Return `true` if the frame is an IEEE 802.15.4-2015 frame,
+        /// to be acknowledged with an Enh-ACK.
+        #[inline(always)]
+        pub fn is_version_2015(&self) -> bool {
+            matches!(
+                FrameVersion::get(self.fcf),
+                Some(FrameVersion::IEEE802154_2015)
+            )
+        }
+
         /// Prepare an ACK PSDU.
         /// Assumes that the parsed frame header indicates that ACK is necessary (`self.needs_ack` returns `true`)
         ///
         /// `frame_pending` sets the ACK's Frame Pending bit - the "stay awake,
         /// data follows" hint a parent gives a polling sleepy child.
+        ///
+        /// IEEE 802.15.4-2015 frames are acknowledged with an Enh-ACK addressed
+        /// to the frame's source, which carries the Thread Link Metrics IE with
+        /// the `link_metrics` values if these are not empty; the other frames
+        /// with an Imm-ACK (`link_metrics` is then ignored).
         #[inline(always)]
-        pub fn prep_ack(&self, ack_buf: &mut [u8], frame_pending: bool) -> usize {
+        pub fn prep_ack(
+            &self,
+            ack_buf: &mut [u8],
+            frame_pending: bool,
+            link_metrics: &[u8],
+        ) -> usize {
+            if self.is_version_2015() {
+                return self.prep_enh_ack(ack_buf, frame_pending, link_metrics);
+            }
+
             assert!(ack_buf.len() >= Self::ACK_PSDU_LEN);
 
             let ack_fcf = Self::FCF_FRAME_TYPE_ACK
@@ -923,6 +1111,87 @@ mod mac_utils {
             Self::ACK_PSDU_LEN
         }
 
+        /// Prepare an Enh-ACK PSDU, see `prep_ack`.
+        fn prep_enh_ack(
+            &self,
+            ack_buf: &mut [u8],
+            frame_pending: bool,
+            link_metrics: &[u8],
+        ) -> usize {
+            assert!(ack_buf.len() >= Self::ENH_ACK_PSDU_MAX_LEN);
+            assert!(link_metrics.len() <= Self::LINK_METRICS_MAX_LEN);
+
+            // The Enh-ACK is addressed to the source of the frame, and - like
+            // OpenThread's own - keeps its PAN ID Compression bit
+            let dst_addr_mode = unwrap!(FrameAddrMode::get_src(self.fcf));
+
+            let mut ack_fcf = Self::FCF_FRAME_TYPE_ACK
+                | Self::FCF_FRAME_VERSION_2015
+                | (self.fcf & Self::FCF_PAN_ID_COMPRESSION_MASK)
+                | ((self.fcf & Self::FCF_FRAME_SRC_ADDR_MODE_MASK)
+                    >> (Self::FCF_FRAME_SRC_ADDR_MODE_SHIFT - Self::FCF_FRAME_DST_ADDR_MODE_SHIFT));
+
+            if frame_pending {
+                ack_fcf |= Self::FCF_PENDING_BIT;
+            }
+
+            if !link_metrics.is_empty() {
+                ack_fcf |= Self::FCF_IE_PRESENT_BIT;
+            }
+
+            ack_buf[0] = ack_fcf.to_le_bytes()[0];
+            ack_buf[1] = ack_fcf.to_le_bytes()[1];
+            ack_buf[2] = self.seq;
+
+            let mut offs = Self::ADDRS_OFFSET;
+
+            let (dst_pan_id_present, _) = Self::pan_ids_present(
+                ack_fcf,
+                &FrameVersion::IEEE802154_2015,
+                &dst_addr_mode,
+                &FrameAddrMode::NotPresent,
+            );
+
+            if dst_pan_id_present {
+                ack_buf[offs..offs + 2].copy_from_slice(&self.src_pan_id.to_le_bytes());
+                offs += 2;
+            }
+
+            match dst_addr_mode {
+                FrameAddrMode::NotPresent => (),
+                FrameAddrMode::Short => {
+                    ack_buf[offs..offs + 2].copy_from_slice(&self.src_short_addr.to_le_bytes());
+                    offs += 2;
+                }
+                FrameAddrMode::Extended => {
+                    ack_buf[offs..offs + 8].copy_from_slice(&self.src_ext_addr.to_le_bytes());
+                    offs += 8;
+                }
+            }
+
+            if !link_metrics.is_empty() {
+                // A vendor-specific Header IE: the Thread OUI and IE sub-type, then the values
+                let ie_len = Self::THREAD_IE_VENDOR_HEADER_LEN + link_metrics.len();
+                let ie_header =
+                    ie_len as u16 | (Self::IE_VENDOR_ELEMENT_ID << Self::IE_ELEMENT_ID_SHIFT);
+
+                ack_buf[offs..offs + 2].copy_from_slice(&ie_header.to_le_bytes());
+                offs += 2;
+
+                ack_buf[offs..offs + 3].copy_from_slice(&Self::THREAD_IE_OUI);
+                ack_buf[offs + 3] = Self::THREAD_IE_ENH_ACK_PROBING;
+                offs += Self::THREAD_IE_VENDOR_HEADER_LEN;
+
+                ack_buf[offs..offs + link_metrics.len()].copy_from_slice(link_metrics);
+                offs += link_metrics.len();
+            }
+
+            ack_buf[offs] = 0; // CRC, will be filled-in by the PHY driver
+            ack_buf[offs + 1] = 0; // CRC, will be filled-in by the PHY driver
+
+            offs + Self::CRC_LEN
+        }
+
         /// Return `true` if the frame is an ACK frame and is an ACK for the given source sequence number.
         #[inline(always)]
         pub fn ack_for(&self, src_seq: u8) -> bool {
@@ -941,6 +1210,7 @@ mod mac_utils {
     enum FrameVersion {
         IEEE802154_2003,
         IEEE802154_2006,
+        IEEE802154_2015,
     }
 
     impl FrameVersion {
@@ -952,6 +1222,7 @@ mod mac_utils {
             match (fcf & MacHeader::FCF_FRAME_VERSION_MASK) >> MacHeader::FCF_FRAME_VERSION_SHIFT {
                 0 => Some(Self::IEEE802154_2003),
                 1 => Some(Self::IEEE802154_2006),
+                2 => Some(Self::IEEE802154_2015),
                 _ => None,
             }
         }
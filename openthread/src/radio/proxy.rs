@@ -12,7 +12,10 @@ use embassy_sync::zerocopy_channel::{Channel, Receiver, Sender};
 
 use crate::fmt::Bytes;
 use crate::sys::{OT_RADIO_FRAME_MAX_SIZE, OT_RADIO_RSSI_INVALID};
-use crate::{Config, PsduMeta, Radio, RadioCaps, RadioError as _, RadioErrorKind, SrcMatchConfig};
+use crate::{
+    Config, EnhAckProbingConfig, PsduMeta, Radio, RadioCaps, RadioError as _, RadioErrorKind,
+    SrcMatchConfig,
+};
 
 /// The resources for the radio proxy.
 pub struct ProxyRadioResources {
@@ -201,6 +204,15 @@ impl Radio for ProxyRadio<'_> {
             .result
     }
 
+    async fn set_enh_ack_probing_config(
+        &mut self,
+        config: &EnhAckProbingConfig,
+    ) -> Result<(), Self::Error> {
+        self.exec(ProxyRadioRequest::EnhAckProbing(config.clone()))
+            .await
+            .result
+    }
+
     async fn set_receive(&mut self, channel: u8) -> Result<(), Self::Error> {
         self.exec(ProxyRadioRequest::Receive { channel })
             .await
@@ -222,6 +234,12 @@ impl Radio for ProxyRadio<'_> {
         response.result.map(|_| response.energy)
     }
 
+    async fn rssi(&mut self, channel: u8) -> Result<i8, Self::Error> {
+        let response = self.exec(ProxyRadioRequest::Rssi { channel }).await;
+
+        response.result.map(|_| response.energy)
+    }
+
     async fn transmit(
         &mut self,
         psdu: &[u8],
@@ -465,6 +483,12 @@ impl PhyRadioRunner<'_> {
                     .await
                     .map_err(|e| e.kind());
             }
+            ProxyRadioRequest::EnhAckProbing(config) => {
+                response.result = radio
+                    .set_enh_ack_probing_config(config)
+                    .await
+                    .map_err(|e| e.kind());
+            }
             ProxyRadioRequest::Receive { channel } => {
                 response.result = radio.set_receive(*channel).await.map_err(|e| e.kind());
             }
@@ -481,6 +505,13 @@ impl PhyRadioRunner<'_> {
                     .map_err(|e| e.kind())
                     .map(|energy| response.energy = energy);
             }
+            ProxyRadioRequest::Rssi { channel } => {
+                response.result = radio
+                    .rssi(*channel)
+                    .await
+                    .map_err(|e| e.kind())
+                    .map(|energy| response.energy = energy);
+            }
             ProxyRadioRequest::Transmit {
                 psdu,
                 channel,
@@ -642,12 +673,16 @@ enum ProxyRadioRequest {
     Config(Config),
     /// [`Radio::set_src_match_config`]
     SrcMatch(SrcMatchConfig),
+    /// [`Radio::set_enh_ack_probing_config`]
+    EnhAckProbing(EnhAckProbingConfig),
     /// [`Radio::set_receive`]
     Receive { channel: u8 },
     /// [`Radio::set_sleep`]
     Sleep,
     /// [`Radio::energy_scan`]
     EnergyScan { channel: u8, duration_millis: u16 },
+    /// [`Radio::rssi`]
+    Rssi { channel: u8 },
     /// [`Radio::transmit`]
     Transmit {
         psdu: heapless::Vec<u8, PSDU_LEN>,
@@ -663,7 +698,8 @@ enum ProxyRadioRequest {
 struct ProxyRadioResponse {
     /// The result of the operation
     result: Result<(), RadioErrorKind>,
-    /// The maximum energy observed, for a successful energy scan
+    /// The maximum energy observed, for a successful energy scan, or the RSSI,
+    /// for a successful RSSI reading
     energy: i8,
     /// The received ACK PSDU, for a successful transmit (might be empty)
     psdu: heapless::Vec<u8, PSDU_LEN>,
@@ -0,0 +1,69 @@
+//! SLAAC control API (`OPENTHREAD_CONFIG_IP6_SLAAC_ENABLE` / the `slaac` feature).
+//!
+//! When enabled, OpenThread's SLAAC module assigns an address to the Thread interface for each
+//! on-mesh prefix of the Network Data which has the `slaac` flag set. The assigned addresses are
+//! reported by [`OpenThread::ipv6_addrs_info`] with the [`Ipv6AddrOrigin::Slaac`](crate::Ipv6AddrOrigin::Slaac) origin.
+
+use core::net::Ipv6Addr;
+
+use crate::sys::{
+    otInstance, otIp6IsSlaacEnabled, otIp6Prefix, otIp6SetSlaacEnabled, otIp6SetSlaacPrefixFilter,
+};
+use crate::{OpenThread, OtContext};
+
+impl OpenThread<'_> {
+    /// Enable or disable the SLAAC module.
+    ///
+    /// Disabling the module removes all previously added SLAAC addresses.
+    pub fn slaac_set_enabled(&self, enable: bool) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otIp6SetSlaacEnabled(state.ot.instance, enable) }
+    }
+
+    /// Return `true` if the SLAAC module is enabled.
+    pub fn slaac_is_enabled(&self) -> bool {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        unsafe { otIp6IsSlaacEnabled(state.ot.instance) }
+    }
+
+    /// Set or clear the SLAAC prefix filter.
+    ///
+    /// The filter is called by the SLAAC module when it is about to add a SLAAC address based on
+    /// an on-mesh prefix. Returning `true` rejects the prefix, i.e. no SLAAC address is added for it.
+    ///
+    /// Arguments:
+    /// - `filter`: The filter, called with the prefix and its length, or `None` to disable filtering.
+    pub fn slaac_set_prefix_filter(&self, filter: Option<fn(Ipv6Addr, u8) -> bool>) {
+        let mut ot = self.activate();
+        let state = ot.state();
+
+        state.ot.slaac_prefix_filter = filter;
+
+        unsafe {
+            otIp6SetSlaacPrefixFilter(
+                state.ot.instance,
+                filter
+                    .is_some()
+                    .then_some(OtContext::plat_c_slaac_prefix_filter as _),
+            )
+        }
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_slaac_prefix_filter(
+        instance: *mut otInstance,
+        prefix: *const otIp6Prefix,
+    ) -> bool {
+        let mut ot = Self::callback(instance);
+        let prefix = unsafe { &*prefix };
+
+        ot.state().ot.slaac_prefix_filter.is_some_and(|filter| {
+            filter(unsafe { prefix.mPrefix.mFields.m8 }.into(), prefix.mLength)
+        })
+    }
+}
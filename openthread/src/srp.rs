@@ -898,6 +898,8 @@ impl OpenThread<'_> {
     /// It is not advised to call this method concurrently from multiple async tasks
     /// because it uses a single waker registration. Thus, while the method will not panic,
     /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    /// Only [`ChangeSubscriber::wait_srp_changed`](crate::ChangeSubscriber::wait_srp_changed)
+    /// supports multiple concurrent consumers, with a [`ChangeSubscriber`](crate::ChangeSubscriber) per task.
     pub async fn srp_wait_changed(&self) {
         if self.activate().state().srp().is_ok() {
             poll_fn(move |cx| {
@@ -943,6 +945,8 @@ impl OtContext<'_> {
         if let Ok(srp) = state.srp() {
             srp.changes.signal(());
             state.ot.changes.signal(());
+
+            self.plat_srp_changed_subscribers();
         }
     }
 
@@ -962,6 +966,8 @@ impl OtContext<'_> {
         if let Ok(srp) = state.srp() {
             srp.changes.signal(());
             state.ot.changes.signal(());
+
+            self.plat_srp_changed_subscribers();
         }
     }
 }
@@ -0,0 +1,775 @@
+//! SRP server API (`OPENTHREAD_CONFIG_SRP_SERVER_ENABLE` / the `srp-server` feature).
+//!
+//! Lets a (typically Border Router) device act as the SRP registrar of the Thread network:
+//! enabling/disabling the server, configuring its domain and the lease ranges it grants,
+//! inspecting the registered hosts and their services, and - optionally - vetting each
+//! incoming SRP update before it is committed.
+//!
+//! Unlike the SRP client, the SRP server does not need any user-supplied resources;
+//! OpenThread keeps the registered hosts and services on its own heap.
+
+use core::ffi::c_void;
+use core::fmt::{self, Display};
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::net::Ipv6Addr;
+use core::task::Poll;
+
+use embassy_time::{Duration, Instant};
+
+use crate::sys::{
+    otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE, otError_OT_ERROR_NO_BUFS, otInstance,
+    otSrpServerGetDomain, otSrpServerGetLeaseConfig, otSrpServerGetNextHost, otSrpServerGetPort,
+    otSrpServerGetState, otSrpServerHandleServiceUpdateResult, otSrpServerHost,
+    otSrpServerHostGetAddresses, otSrpServerHostGetFullName, otSrpServerHostGetLeaseInfo,
+    otSrpServerHostGetNextService, otSrpServerHostIsDeleted, otSrpServerLeaseConfig,
+    otSrpServerLeaseInfo, otSrpServerService, otSrpServerServiceGetInstanceLabel,
+    otSrpServerServiceGetInstanceName, otSrpServerServiceGetLeaseInfo,
+    otSrpServerServiceGetNumberOfSubTypes, otSrpServerServiceGetPort,
+    otSrpServerServiceGetPriority, otSrpServerServiceGetServiceName,
+    otSrpServerServiceGetSubTypeServiceNameAt, otSrpServerServiceGetTtl,
+    otSrpServerServiceGetTxtData, otSrpServerServiceGetWeight, otSrpServerServiceIsDeleted,
+    otSrpServerServiceUpdateId, otSrpServerSetDomain, otSrpServerSetEnabled,
+    otSrpServerSetLeaseConfig, otSrpServerSetServiceUpdateHandler, otSrpServerState,
+    otSrpServerState_OT_SRP_SERVER_STATE_DISABLED, otSrpServerState_OT_SRP_SERVER_STATE_RUNNING,
+    otSrpServerState_OT_SRP_SERVER_STATE_STOPPED, OT_DNS_MAX_NAME_SIZE,
+};
+use crate::{c_str, ot, OpenThread, OtContext, OtError};
+
+/// The maximum number of SRP updates which can be pending a decision from the application
+/// (see [`OpenThread::srp_server_wait_update`]) at any point in time.
+///
+/// Updates arriving while the queue is full are rejected with `NO_BUFS` straight away.
+pub const SRP_SERVER_MAX_PENDING_UPDATES: usize = 4;
+
+/// The ID of an SRP update transaction, as reported by [`OpenThread::srp_server_wait_update`].
+pub type SrpServerUpdateId = otSrpServerServiceUpdateId;
+
+/// The state of the SRP server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SrpServerState {
+    /// The SRP server is disabled.
+    Disabled,
+    /// The SRP server is enabled and running.
+    Running,
+    /// The SRP server is enabled but stopped (e.g. because the device is not attached yet).
+    Stopped,
+    /// Any other state.
+    Other(otSrpServerState),
+}
+
+impl Display for SrpServerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "Disabled"),
+            Self::Running => write!(f, "Running"),
+            Self::Stopped => write!(f, "Stopped"),
+            Self::Other(state) => write!(f, "Other ({})", state),
+        }
+    }
+}
+
+#[allow(non_upper_case_globals)]
+impl From<otSrpServerState> for SrpServerState {
+    fn from(value: otSrpServerState) -> Self {
+        match value {
+            otSrpServerState_OT_SRP_SERVER_STATE_DISABLED => Self::Disabled,
+            otSrpServerState_OT_SRP_SERVER_STATE_RUNNING => Self::Running,
+            otSrpServerState_OT_SRP_SERVER_STATE_STOPPED => Self::Stopped,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// The lease ranges granted by the SRP server.
+///
+/// The lease (or key lease) requested by an SRP client is clamped into the corresponding range.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SrpServerLeaseConf {
+    /// Minimum lease time in seconds.
+    pub min_lease_secs: u32,
+    /// Maximum lease time in seconds.
+    pub max_lease_secs: u32,
+    /// Minimum key lease time in seconds.
+    pub min_key_lease_secs: u32,
+    /// Maximum key lease time in seconds.
+    pub max_key_lease_secs: u32,
+}
+
+impl SrpServerLeaseConf {
+    /// Create a new `SrpServerLeaseConf` instance with the OpenThread default ranges:
+    /// 30 seconds to 27 hours for the lease, and 30 seconds to 189 hours for the key lease.
+    pub const fn new() -> Self {
+        Self {
+            min_lease_secs: 30,
+            max_lease_secs: 27 * 60 * 60,
+            min_key_lease_secs: 30,
+            max_key_lease_secs: 189 * 60 * 60,
+        }
+    }
+}
+
+impl Default for SrpServerLeaseConf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The lease information of a host or a service registered with the SRP server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SrpServerLeaseInfo {
+    /// The granted lease time in milliseconds.
+    pub lease_ms: u32,
+    /// The granted key lease time in milliseconds.
+    pub key_lease_ms: u32,
+    /// The remaining lease time in milliseconds.
+    pub remaining_lease_ms: u32,
+    /// The remaining key lease time in milliseconds.
+    pub remaining_key_lease_ms: u32,
+}
+
+impl From<otSrpServerLeaseInfo> for SrpServerLeaseInfo {
+    fn from(value: otSrpServerLeaseInfo) -> Self {
+        Self {
+            lease_ms: value.mLease,
+            key_lease_ms: value.mKeyLease,
+            remaining_lease_ms: value.mRemainingLease,
+            remaining_key_lease_ms: value.mRemainingKeyLease,
+        }
+    }
+}
+
+/// A host registered with the SRP server, as returned by `OpenThread::srp_server_hosts`
+/// and `OpenThread::srp_server_wait_update`.
+pub struct SrpServerHost<'a> {
+    ptr: *const otSrpServerHost,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> SrpServerHost<'a> {
+    /// The full name of the host (e.g. `ot-device.default.service.arpa.`),
+    /// or `None` if the name registered by the SRP client is not valid UTF-8.
+    pub fn full_name(&self) -> Option<&'a str> {
+        c_str(unsafe { otSrpServerHostGetFullName(self.ptr) }).ok()
+    }
+
+    /// Return `true` if the host had been deleted.
+    ///
+    /// A deleted host keeps its name reserved until its key lease expires.
+    pub fn is_deleted(&self) -> bool {
+        unsafe { otSrpServerHostIsDeleted(self.ptr) }
+    }
+
+    /// The IPv6 addresses of the host.
+    pub fn addrs(&self) -> &'a [Ipv6Addr] {
+        let mut len = 0;
+        let addrs = unsafe { otSrpServerHostGetAddresses(self.ptr, &mut len) };
+
+        if addrs.is_null() || len == 0 {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(addrs as *const _, len as _) }
+        }
+    }
+
+    /// The lease information of the host.
+    pub fn lease_info(&self) -> SrpServerLeaseInfo {
+        let mut info = otSrpServerLeaseInfo::default();
+
+        unsafe {
+            otSrpServerHostGetLeaseInfo(self.ptr, &mut info);
+        }
+
+        info.into()
+    }
+
+    /// An iterator over the services of the host.
+    pub fn services(&self) -> SrpServerServicesIter<'a> {
+        SrpServerServicesIter {
+            host: self.ptr,
+            service: core::ptr::null(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl Display for SrpServerHost<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SRP Host {{name: {:?}, deleted: {}, addrs: {:?}, lease: {:?}}}",
+            self.full_name(),
+            self.is_deleted(),
+            self.addrs(),
+            self.lease_info()
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SrpServerHost<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "SRP Host {{name: {:?}, deleted: {}, addrs: {:?}, lease: {:?}}}",
+            self.full_name(),
+            self.is_deleted(),
+            self.addrs(),
+            self.lease_info()
+        )
+    }
+}
+
+/// An iterator over the services of an SRP server host.
+#[derive(Clone)]
+pub struct SrpServerServicesIter<'a> {
+    host: *const otSrpServerHost,
+    service: *const otSrpServerService,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> Iterator for SrpServerServicesIter<'a> {
+    type Item = SrpServerService<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.service = unsafe { otSrpServerHostGetNextService(self.host, self.service) };
+
+        (!self.service.is_null()).then_some(SrpServerService {
+            ptr: self.service,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// A service registered with the SRP server.
+pub struct SrpServerService<'a> {
+    ptr: *const otSrpServerService,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> SrpServerService<'a> {
+    /// The full service instance name (e.g. `my-light._matter._tcp.default.service.arpa.`),
+    /// or `None` if the name registered by the SRP client is not valid UTF-8.
+    pub fn instance_name(&self) -> Option<&'a str> {
+        c_str(unsafe { otSrpServerServiceGetInstanceName(self.ptr) }).ok()
+    }
+
+    /// The service instance label, i.e. the first label of the instance name (e.g. `my-light`),
+    /// or `None` if the label registered by the SRP client is not valid UTF-8.
+    pub fn instance_label(&self) -> Option<&'a str> {
+        c_str(unsafe { otSrpServerServiceGetInstanceLabel(self.ptr) }).ok()
+    }
+
+    /// The full service name (e.g. `_matter._tcp.default.service.arpa.`),
+    /// or `None` if the name registered by the SRP client is not valid UTF-8.
+    pub fn service_name(&self) -> Option<&'a str> {
+        c_str(unsafe { otSrpServerServiceGetServiceName(self.ptr) }).ok()
+    }
+
+    /// An iterator over the full sub-type service names of the service
+    /// (e.g. `_L3840._sub._matterc._udp.default.service.arpa.`).
+    ///
+    /// Sub-type names which are not valid UTF-8 are skipped.
+    pub fn subtype_names(&self) -> SrpServerSubtypeNamesIter<'a> {
+        SrpServerSubtypeNamesIter {
+            ptr: self.ptr,
+            size: unsafe { otSrpServerServiceGetNumberOfSubTypes(self.ptr) },
+            index: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Return `true` if the service had been deleted.
+    ///
+    /// A deleted service keeps its name reserved until its key lease expires.
+    pub fn is_deleted(&self) -> bool {
+        unsafe { otSrpServerServiceIsDeleted(self.ptr) }
+    }
+
+    /// The service port.
+    pub fn port(&self) -> u16 {
+        unsafe { otSrpServerServiceGetPort(self.ptr) }
+    }
+
+    /// The service priority.
+    pub fn priority(&self) -> u16 {
+        unsafe { otSrpServerServiceGetPriority(self.ptr) }
+    }
+
+    /// The service weight.
+    pub fn weight(&self) -> u16 {
+        unsafe { otSrpServerServiceGetWeight(self.ptr) }
+    }
+
+    /// The service TTL in seconds.
+    pub fn ttl(&self) -> u32 {
+        unsafe { otSrpServerServiceGetTtl(self.ptr) }
+    }
+
+    /// The raw TXT data of the service, as encoded on the wire.
+    pub fn txt_data(&self) -> &'a [u8] {
+        let mut len = 0;
+        let data = unsafe { otSrpServerServiceGetTxtData(self.ptr, &mut len) };
+
+        if data.is_null() || len == 0 {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(data, len as _) }
+        }
+    }
+
+    /// An iterator over the TXT entries of the service.
+    pub fn txt_entries(&self) -> SrpServerTxtEntriesIter<'a> {
+        SrpServerTxtEntriesIter {
+            data: self.txt_data(),
+        }
+    }
+
+    /// The lease information of the service.
+    pub fn lease_info(&self) -> SrpServerLeaseInfo {
+        let mut info = otSrpServerLeaseInfo::default();
+
+        unsafe {
+            otSrpServerServiceGetLeaseInfo(self.ptr, &mut info);
+        }
+
+        info.into()
+    }
+}
+
+impl Display for SrpServerService<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SRP Service {{instance: {:?}, port: {}, priority: {}, weight: {}, ttl: {}, deleted: {}, lease: {:?}, subtypes: [",
+            self.instance_name(),
+            self.port(),
+            self.priority(),
+            self.weight(),
+            self.ttl(),
+            self.is_deleted(),
+            self.lease_info()
+        )?;
+
+        for (index, name) in self.subtype_names().enumerate() {
+            if index > 0 {
+                write!(f, ", {}", name)?;
+            } else {
+                write!(f, "{}", name)?;
+            }
+        }
+
+        write!(f, "], txt: [")?;
+
+        for (index, value) in self.txt_entries().enumerate() {
+            if index > 0 {
+                write!(f, ", {}: {:?}", value.0, value.1)?;
+            } else {
+                write!(f, "{}: {:?}", value.0, value.1)?;
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SrpServerService<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "SRP Service {{instance: {:?}, port: {}, priority: {}, weight: {}, ttl: {}, deleted: {}, lease: {:?}, subtypes: [",
+            self.instance_name(),
+            self.port(),
+            self.priority(),
+            self.weight(),
+            self.ttl(),
+            self.is_deleted(),
+            self.lease_info()
+        );
+
+        for (index, name) in self.subtype_names().enumerate() {
+            #[allow(clippy::if_same_then_else)]
+            if index > 0 {
+                defmt::write!(f, ", {}", name);
+            } else {
+                defmt::write!(f, "{}", name);
+            }
+        }
+
+        defmt::write!(f, "], txt: [");
+
+        for (index, value) in self.txt_entries().enumerate() {
+            #[allow(clippy::if_same_then_else)]
+            if index > 0 {
+                defmt::write!(f, ", {}: {:?}", value.0, value.1);
+            } else {
+                defmt::write!(f, "{}: {:?}", value.0, value.1);
+            }
+        }
+
+        defmt::write!(f, "]}}")
+    }
+}
+
+/// An iterator over the sub-type service names of an SRP server service.
+#[derive(Clone)]
+pub struct SrpServerSubtypeNamesIter<'a> {
+    ptr: *const otSrpServerService,
+    size: u16,
+    index: u16,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> Iterator for SrpServerSubtypeNamesIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.size {
+            let name = unsafe { otSrpServerServiceGetSubTypeServiceNameAt(self.ptr, self.index) };
+
+            self.index += 1;
+
+            // Skip the sub-type names which are not valid UTF-8
+            if let Ok(name) = c_str(name) {
+                return Some(name);
+            }
+        }
+
+        None
+    }
+}
+
+/// An iterator over the TXT entries of an SRP server service.
+///
+/// Entries are decoded from the raw TXT data as `key=value` strings; entries without a `=`
+/// are returned with an empty value, and entries with a non-UTF-8 key are skipped.
+#[derive(Clone)]
+pub struct SrpServerTxtEntriesIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for SrpServerTxtEntriesIter<'a> {
+    type Item = (&'a str, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((&len, rest)) = self.data.split_first() {
+            let len = (len as usize).min(rest.len());
+            let (entry, rest) = rest.split_at(len);
+
+            self.data = rest;
+
+            let (key, value) = match entry.iter().position(|&b| b == b'=') {
+                Some(pos) => (&entry[..pos], &entry[pos + 1..]),
+                None => (entry, &[][..]),
+            };
+
+            if key.is_empty() {
+                continue;
+            }
+
+            if let Ok(key) = core::str::from_utf8(key) {
+                return Some((key, value));
+            }
+        }
+
+        None
+    }
+}
+
+/// An SRP update pending a decision from the application,
+/// as reported by `OpenThread::srp_server_wait_update`.
+pub struct SrpServerUpdate<'a> {
+    /// The ID of the update transaction, to be passed to `OpenThread::srp_server_update_result`.
+    pub id: SrpServerUpdateId,
+    /// The host as it would look like once the update is accepted, with all its services.
+    /// A host or services marked as deleted are being removed by the update.
+    pub host: SrpServerHost<'a>,
+}
+
+/// An SRP update queued by the service update handler, until the application picks it up.
+#[derive(Copy, Clone)]
+pub(crate) struct SrpServerPendingUpdate {
+    id: SrpServerUpdateId,
+    host: *const otSrpServerHost,
+    /// When OpenThread gives up waiting on the result (and frees the host)
+    deadline: Instant,
+}
+
+impl OpenThread<'_> {
+    /// Return the current state of the SRP server.
+    pub fn srp_server_state(&self) -> SrpServerState {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe { otSrpServerGetState(instance) }.into()
+    }
+
+    /// Enable or disable the SRP server.
+    ///
+    /// Once enabled, the server starts (and publishes itself in the Network Data)
+    /// as soon as the device is attached to a Thread network.
+    pub fn srp_server_set_enabled(&self, enabled: bool) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        unsafe {
+            otSrpServerSetEnabled(instance, enabled);
+        }
+    }
+
+    /// Return the port the SRP server is listening on, or `None` if the server is not running.
+    pub fn srp_server_port(&self) -> Option<u16> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let port = unsafe { otSrpServerGetPort(instance) };
+
+        (port != 0).then_some(port)
+    }
+
+    /// Return the domain of the SRP server to the provided closure.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that takes the domain (e.g. `default.service.arpa.`) as an argument.
+    pub fn srp_server_domain<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&str) -> R,
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        // The domain is always set locally from a `&str`, hence it is valid UTF-8
+        f(c_str(unsafe { otSrpServerGetDomain(instance) }).unwrap_or(""))
+    }
+
+    /// Set the domain of the SRP server.
+    ///
+    /// A trailing dot is appended to the domain if missing.
+    ///
+    /// Returns:
+    /// - `Ok(())` if the domain was set successfully.
+    /// - `Err(OtError)` if the domain is invalid, or if the SRP server is not disabled.
+    pub fn srp_server_set_domain(&self, domain: &str) -> Result<(), OtError> {
+        let mut buf = [0u8; OT_DNS_MAX_NAME_SIZE as usize];
+
+        if domain.len() >= buf.len() {
+            Err(OtError::new(otError_OT_ERROR_INVALID_ARGS))?;
+        }
+
+        buf[..domain.len()].copy_from_slice(domain.as_bytes());
+
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        ot!(unsafe { otSrpServerSetDomain(instance, buf.as_ptr() as _) })
+    }
+
+    /// Return the lease ranges granted by the SRP server.
+    pub fn srp_server_lease_conf(&self) -> SrpServerLeaseConf {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut conf = otSrpServerLeaseConfig::default();
+
+        unsafe {
+            otSrpServerGetLeaseConfig(instance, &mut conf);
+        }
+
+        SrpServerLeaseConf {
+            min_lease_secs: conf.mMinLease,
+            max_lease_secs: conf.mMaxLease,
+            min_key_lease_secs: conf.mMinKeyLease,
+            max_key_lease_secs: conf.mMaxKeyLease,
+        }
+    }
+
+    /// Set the lease ranges granted by the SRP server.
+    ///
+    /// Returns:
+    /// - `Ok(())` if the configuration was set successfully.
+    /// - `Err(OtError)` if the ranges are invalid (e.g. a minimum larger than the maximum,
+    ///   or a key lease range below the lease range).
+    pub fn srp_server_set_lease_conf(&self, conf: &SrpServerLeaseConf) -> Result<(), OtError> {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let conf = otSrpServerLeaseConfig {
+            mMinLease: conf.min_lease_secs,
+            mMaxLease: conf.max_lease_secs,
+            mMinKeyLease: conf.min_key_lease_secs,
+            mMaxKeyLease: conf.max_key_lease_secs,
+        };
+
+        ot!(unsafe { otSrpServerSetLeaseConfig(instance, &conf) })
+    }
+
+    /// Iterate over the hosts registered with the SRP server.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that receives the next SRP host; the services of the host are available
+    ///   via `SrpServerHost::services`. If there are no more SRP hosts, the closure will receive `None`.
+    pub fn srp_server_hosts<F>(&self, mut f: F)
+    where
+        F: FnMut(Option<&SrpServerHost<'_>>),
+    {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let mut host_ptr = unsafe { otSrpServerGetNextHost(instance, core::ptr::null()) };
+
+        while !host_ptr.is_null() {
+            f(Some(&SrpServerHost {
+                ptr: host_ptr,
+                _phantom: PhantomData,
+            }));
+
+            host_ptr = unsafe { otSrpServerGetNextHost(instance, host_ptr) };
+        }
+
+        f(None);
+    }
+
+    /// Enable or disable the vetting of SRP updates by the application.
+    ///
+    /// When enabled, each SRP update received by the server is held back until the application
+    /// picks it up with [`OpenThread::srp_server_wait_update`] and reports the outcome with
+    /// [`OpenThread::srp_server_update_result`]. Updates for which no outcome is reported within
+    /// the OpenThread service update timeout are rejected by the server.
+    ///
+    /// When disabled (the default), all SRP updates are accepted right away. Disabling accepts
+    /// the updates which are still waiting to be picked up.
+    pub fn srp_server_set_update_vetting(&self, enabled: bool) {
+        let mut ot = self.activate();
+        let state = ot.state();
+        let instance = state.ot.instance;
+
+        if enabled {
+            unsafe {
+                otSrpServerSetServiceUpdateHandler(
+                    instance,
+                    Some(OtContext::plat_c_srp_server_update_handler),
+                    instance as _,
+                );
+            }
+        } else {
+            unsafe {
+                otSrpServerSetServiceUpdateHandler(instance, None, core::ptr::null_mut());
+            }
+
+            while let Some(update) = state.ot.srp_server_updates.pop_front() {
+                unsafe {
+                    otSrpServerHandleServiceUpdateResult(
+                        instance,
+                        update.id,
+                        otError_OT_ERROR_NONE,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Wait for an SRP update pending a decision from the application.
+    ///
+    /// Only reports updates if vetting was enabled with [`OpenThread::srp_server_set_update_vetting`],
+    /// otherwise waits forever.
+    ///
+    /// Arguments:
+    /// - `f`: A closure that receives the pending update, so that the application can inspect the host
+    ///   and its services. Once the closure returns, the update must be accepted or rejected with
+    ///   [`OpenThread::srp_server_update_result`].
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn srp_server_wait_update<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&SrpServerUpdate<'_>) -> R,
+    {
+        let update = poll_fn(|cx| {
+            let mut ot = self.activate();
+            let state = ot.state();
+
+            let now = Instant::now();
+
+            while let Some(update) = state.ot.srp_server_updates.pop_front() {
+                // The host of an expired update is already freed by OpenThread
+                if update.deadline > now {
+                    return Poll::Ready(update);
+                }
+
+                debug!("SRP update {} expired before being picked up", update.id);
+            }
+
+            state.ot.srp_server_update.reset();
+            let _ = state.ot.srp_server_update.poll_wait(cx);
+
+            Poll::Pending
+        })
+        .await;
+
+        let _ot = self.activate();
+
+        f(&SrpServerUpdate {
+            id: update.id,
+            host: SrpServerHost {
+                ptr: update.host,
+                _phantom: PhantomData,
+            },
+        })
+    }
+
+    /// Report the outcome of an SRP update returned by [`OpenThread::srp_server_wait_update`].
+    ///
+    /// Arguments:
+    /// - `id`: The ID of the update.
+    /// - `result`: `Ok(())` to accept and commit the update, or `Err(OtError)` to reject it.
+    ///   Use `OT_ERROR_DUPLICATED` to report a name conflict to the SRP client.
+    pub fn srp_server_update_result(&self, id: SrpServerUpdateId, result: Result<(), OtError>) {
+        let mut ot = self.activate();
+        let instance = ot.state().ot.instance;
+
+        let error = match result {
+            Ok(()) => otError_OT_ERROR_NONE,
+            Err(err) => err.into_inner(),
+        };
+
+        unsafe {
+            otSrpServerHandleServiceUpdateResult(instance, id, error);
+        }
+    }
+}
+
+impl OtContext<'_> {
+    unsafe extern "C" fn plat_c_srp_server_update_handler(
+        id: otSrpServerServiceUpdateId,
+        host: *const otSrpServerHost,
+        timeout: u32,
+        context: *mut c_void,
+    ) {
+        let instance = context as *mut otInstance;
+
+        let mut ot = OtContext::callback(instance);
+        let state = ot.state();
+
+        let update = SrpServerPendingUpdate {
+            id,
+            host,
+            deadline: Instant::now() + Duration::from_millis(timeout as _),
+        };
+
+        if state.ot.srp_server_updates.push_back(update).is_err() {
+            warn!("Too many pending SRP updates, rejecting update {}", id);
+
+            unsafe {
+                otSrpServerHandleServiceUpdateResult(instance, id, otError_OT_ERROR_NO_BUFS);
+            }
+        } else {
+            state.ot.srp_server_update.signal(());
+        }
+    }
+}
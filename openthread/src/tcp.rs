@@ -0,0 +1,940 @@
+//! TCP API: OpenThread's native TCP implementation (TCPlp), bridged into async Rust.
+//!
+//! [`TcpSocket`] connects to a peer and [`TcpListener`] accepts connections from peers;
+//! both run directly on the OpenThread instance, without an external IP stack.
+//!
+//! The receive and send buffers of the sockets are statically allocated in `OtTcpResources`,
+//! and the `OpenThread` instance must have been created with those
+//! (see [`OpenThreadBuilder::tcp`](crate::OpenThreadBuilder::tcp)).
+//!
+//! `TcpSocket` implements the `embedded_io_async::Read` and `embedded_io_async::Write` traits,
+//! so that protocol crates built on top of these can run over it.
+
+use core::cell::RefCell;
+use core::ffi::c_void;
+use core::fmt::{self, Display};
+use core::future::poll_fn;
+use core::mem::MaybeUninit;
+use core::net::SocketAddrV6;
+use core::task::{Context, Poll};
+
+use crate::signal::Signal;
+use crate::sys::{
+    otError_OT_ERROR_NO_BUFS, otLinkedBuffer, otSockAddr, otTcpAbort, otTcpCircularSendBuffer,
+    otTcpCircularSendBufferDeinitialize, otTcpCircularSendBufferForceDiscardAll,
+    otTcpCircularSendBufferGetFreeSpace, otTcpCircularSendBufferHandleForwardProgress,
+    otTcpCircularSendBufferInitialize, otTcpCircularSendBufferWrite, otTcpCommitReceive,
+    otTcpConnect, otTcpDisconnectedReason,
+    otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_REFUSED,
+    otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_RESET,
+    otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_TIMED_OUT, otTcpEndpoint,
+    otTcpEndpointDeinitialize, otTcpEndpointGetContext, otTcpEndpointInitialize,
+    otTcpEndpointInitializeArgs, otTcpGetLocalAddress, otTcpGetPeerAddress,
+    otTcpIncomingConnectionAction,
+    otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT,
+    otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_DEFER, otTcpListen,
+    otTcpListener, otTcpListenerDeinitialize, otTcpListenerGetContext, otTcpListenerInitialize,
+    otTcpListenerInitializeArgs, otTcpReceiveByReference, otTcpSendEndOfStream, otTcpStopListening,
+    OT_TCP_CONNECT_NO_FAST_OPEN,
+};
+use crate::{ot, to_ot_addr, to_sock_addr, Bytes, OpenThread, OtContext, OtError};
+
+/// A TCP error
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TcpError {
+    /// The peer refused the connection
+    Refused,
+    /// The peer reset the connection
+    Reset,
+    /// The connection timed out
+    TimedOut,
+    /// The connection is closed (or is being closed) and cannot be used for the operation
+    Closed,
+    /// Any other OpenThread error
+    Ot(OtError),
+}
+
+impl TcpError {
+    /// Map the reason of a disconnection to the error reported to the operations
+    /// on the socket afterwards.
+    #[allow(non_upper_case_globals)]
+    fn from_reason(reason: otTcpDisconnectedReason) -> Self {
+        match reason {
+            otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_REFUSED => Self::Refused,
+            otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_RESET => Self::Reset,
+            otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_TIMED_OUT => Self::TimedOut,
+            _ => Self::Closed,
+        }
+    }
+}
+
+impl From<OtError> for TcpError {
+    fn from(error: OtError) -> Self {
+        Self::Ot(error)
+    }
+}
+
+impl Display for TcpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Refused => write!(f, "Connection refused"),
+            Self::Reset => write!(f, "Connection reset"),
+            Self::TimedOut => write!(f, "Connection timed out"),
+            Self::Closed => write!(f, "Connection closed"),
+            Self::Ot(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for TcpError {}
+
+impl embedded_io_async::Error for TcpError {
+    fn kind(&self) -> embedded_io_async::ErrorKind {
+        match self {
+            Self::Refused => embedded_io_async::ErrorKind::ConnectionRefused,
+            Self::Reset => embedded_io_async::ErrorKind::ConnectionReset,
+            Self::TimedOut => embedded_io_async::ErrorKind::TimedOut,
+            Self::Closed => embedded_io_async::ErrorKind::NotConnected,
+            Self::Ot(_) => embedded_io_async::ErrorKind::Other,
+        }
+    }
+}
+
+/// An OpenThread native TCP socket
+pub struct TcpSocket<'a> {
+    /// The OpenThread stack that the socket is associated with.
+    ot: OpenThread<'a>,
+    /// The slot in the OpenThread stack's TCP socket array that this socket occupies.
+    slot: usize,
+}
+
+impl<'a> TcpSocket<'a> {
+    /// Create a new TCP socket and connect it to the specified remote address.
+    ///
+    /// Waits until the connection is established. Dropping the returned future
+    /// before that aborts the connection.
+    pub async fn connect(ot: OpenThread<'a>, remote: &SocketAddrV6) -> Result<Self, TcpError> {
+        let this = Self::new(ot)?;
+
+        {
+            let mut ot = this.ot.activate();
+            let socket = &mut ot.state().tcp()?.sockets[this.slot];
+
+            ot!(unsafe {
+                otTcpConnect(
+                    &mut socket.endpoint,
+                    &to_ot_addr(remote),
+                    OT_TCP_CONNECT_NO_FAST_OPEN,
+                )
+            })?;
+        }
+
+        poll_fn(|cx| {
+            this.with_socket(cx, |socket| {
+                if socket.established {
+                    Some(Ok(()))
+                } else {
+                    socket.disconnected.map(Err)
+                }
+            })
+        })
+        .await?;
+
+        debug!("TCP connection to {} established", remote);
+
+        Ok(this)
+    }
+
+    /// Create a new TCP socket, not connected yet.
+    fn new(ot: OpenThread<'a>) -> Result<Self, OtError> {
+        let slot = {
+            let mut active_ot = ot.activate();
+            let state = active_ot.state();
+            let instance = state.ot.instance;
+            let tcp = state.tcp()?;
+
+            let slot = tcp
+                .sockets
+                .iter()
+                .position(|socket| !socket.taken)
+                .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+            let rx_offset = slot * tcp.rx_buf_len;
+            let rx_buf = &mut tcp.rx_buffers[rx_offset..rx_offset + tcp.rx_buf_len];
+
+            let tx_offset = slot * tcp.tx_buf_len;
+            let tx_buf = &mut tcp.tx_buffers[tx_offset..tx_offset + tcp.tx_buf_len];
+
+            let socket = &mut tcp.sockets[slot];
+
+            let args = otTcpEndpointInitializeArgs {
+                mContext: slot as *mut c_void,
+                mEstablishedCallback: Some(plat_c_tcp_established),
+                mSendDoneCallback: None,
+                mForwardProgressCallback: Some(plat_c_tcp_forward_progress),
+                mReceiveAvailableCallback: Some(plat_c_tcp_receive_available),
+                mDisconnectedCallback: Some(plat_c_tcp_disconnected),
+                mReceiveBuffer: rx_buf.as_mut_ptr() as *mut c_void,
+                mReceiveBufferSize: rx_buf.len(),
+            };
+
+            ot!(unsafe { otTcpEndpointInitialize(instance, &mut socket.endpoint, &args) })?;
+
+            unsafe {
+                otTcpCircularSendBufferInitialize(
+                    &mut socket.send_buffer,
+                    tx_buf.as_mut_ptr() as *mut c_void,
+                    tx_buf.len(),
+                )
+            };
+
+            socket.established = false;
+            socket.eof = false;
+            socket.closing = false;
+            socket.disconnected = None;
+            socket.rx.reset();
+            socket.tx.reset();
+            socket.taken = true;
+
+            slot
+        };
+
+        Ok(Self { ot, slot })
+    }
+
+    /// Return the local address of the socket.
+    pub fn local_addr(&self) -> Result<SocketAddrV6, OtError> {
+        let mut ot = self.ot.activate();
+        let socket = &mut ot.state().tcp()?.sockets[self.slot];
+
+        let addr = unsafe { &*otTcpGetLocalAddress(&socket.endpoint) };
+
+        Ok(to_sock_addr(&addr.mAddress, addr.mPort, 0))
+    }
+
+    /// Return the address of the peer the socket is connected to.
+    pub fn peer_addr(&self) -> Result<SocketAddrV6, OtError> {
+        let mut ot = self.ot.activate();
+        let socket = &mut ot.state().tcp()?.sockets[self.slot];
+
+        let addr = unsafe { &*otTcpGetPeerAddress(&socket.endpoint) };
+
+        Ok(to_sock_addr(&addr.mAddress, addr.mPort, 0))
+    }
+
+    /// Receive data from the socket.
+    /// If there is no data available, this function will async-wait until data is available.
+    ///
+    /// Arguments:
+    /// - `buf`: The buffer to store the received data.
+    ///
+    /// Returns:
+    /// - The number of bytes received; 0 if the peer closed its side of the connection.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn read(&self, buf: &mut [u8]) -> Result<usize, TcpError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let len = poll_fn(|cx| {
+            self.with_socket(cx, |socket| match socket.recv(buf) {
+                Ok(0) if socket.eof => Some(Ok(0)),
+                Ok(0) => match socket.disconnected {
+                    Some(TcpError::Closed) => Some(Ok(0)),
+                    Some(err) => Some(Err(err)),
+                    None => None,
+                },
+                Ok(len) => Some(Ok(len)),
+                Err(err) => Some(Err(err.into())),
+            })
+        })
+        .await?;
+
+        trace!("Received TCP data: {}", Bytes(&buf[..len]));
+
+        Ok(len)
+    }
+
+    /// Send data over the socket.
+    /// If there is no room in the send buffer, this function will async-wait until
+    /// the peer acknowledges some of the data sent so far.
+    ///
+    /// Arguments:
+    /// - `data`: The data to send.
+    ///
+    /// Returns:
+    /// - The number of bytes queued for sending; might be less than the length of `data`.
+    pub async fn write(&self, data: &[u8]) -> Result<usize, TcpError> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        let len = poll_fn(|cx| {
+            self.with_socket_tx(cx, |socket| {
+                if let Some(err) = socket.disconnected {
+                    return Some(Err(err));
+                }
+
+                if socket.closing {
+                    return Some(Err(TcpError::Closed));
+                }
+
+                let mut written = 0;
+
+                let res = ot!(unsafe {
+                    otTcpCircularSendBufferWrite(
+                        &mut socket.endpoint,
+                        &mut socket.send_buffer,
+                        data.as_ptr() as *const c_void,
+                        data.len(),
+                        &mut written,
+                        0,
+                    )
+                });
+
+                match res {
+                    Err(err) => Some(Err(err.into())),
+                    Ok(()) if written > 0 => Some(Ok(written)),
+                    Ok(()) => None,
+                }
+            })
+        })
+        .await?;
+
+        trace!("Transmitted TCP data: {}", Bytes(&data[..len]));
+
+        Ok(len)
+    }
+
+    /// Wait until all data written so far is acknowledged by the peer.
+    pub async fn flush(&self) -> Result<(), TcpError> {
+        poll_fn(|cx| {
+            self.with_socket_tx(cx, |socket| {
+                let free = unsafe { otTcpCircularSendBufferGetFreeSpace(&socket.send_buffer) };
+
+                if free == socket.send_buffer.mCapacity {
+                    Some(Ok(()))
+                } else {
+                    socket.disconnected.map(Err)
+                }
+            })
+        })
+        .await
+    }
+
+    /// Gracefully close the sending side of the connection (`otTcpSendEndOfStream`)
+    /// and wait until all data written so far is acknowledged by the peer.
+    ///
+    /// Data can still be received afterwards, until the peer closes its side too
+    /// (i.e. until `read` returns 0).
+    pub async fn close(&self) -> Result<(), TcpError> {
+        {
+            let mut ot = self.ot.activate();
+            let socket = &mut ot.state().tcp()?.sockets[self.slot];
+
+            if let Some(err) = socket.disconnected {
+                return if err == TcpError::Closed {
+                    Ok(())
+                } else {
+                    Err(err)
+                };
+            }
+
+            if !socket.closing {
+                ot!(unsafe { otTcpSendEndOfStream(&mut socket.endpoint) })?;
+                socket.closing = true;
+            }
+        }
+
+        match self.flush().await {
+            Err(TcpError::Closed) => Ok(()),
+            other => other,
+        }
+    }
+
+    /// Forcibly close the connection (`otTcpAbort`), sending a reset to the peer.
+    ///
+    /// All further operations on the socket fail with `TcpError::Closed`.
+    pub fn abort(&self) -> Result<(), TcpError> {
+        let mut ot = self.ot.activate();
+        let tcp = ot.state().tcp()?;
+
+        ot!(unsafe { otTcpAbort(&mut tcp.sockets[self.slot].endpoint) })?;
+
+        let socket = &mut tcp.sockets[self.slot];
+        unsafe { otTcpCircularSendBufferForceDiscardAll(&mut socket.send_buffer) };
+        socket.disconnected = Some(TcpError::Closed);
+        socket.rx.signal(());
+        socket.tx.signal(());
+
+        Ok(())
+    }
+
+    /// Poll the socket with `f`; if `f` returns `None`, register the waker for
+    /// receive-side events and return `Poll::Pending`.
+    fn with_socket<R>(
+        &self,
+        cx: &mut Context<'_>,
+        f: impl FnOnce(&mut TcpSocketCtx) -> Option<Result<R, TcpError>>,
+    ) -> Poll<Result<R, TcpError>> {
+        let mut ot = self.ot.activate();
+        let socket = &mut ot.state().tcp()?.sockets[self.slot];
+
+        if let Some(res) = f(socket) {
+            return Poll::Ready(res);
+        }
+
+        // No callback can fire until we release the activation, so registering after
+        // the check does not lose any wakeups
+        socket.rx.reset();
+        let _ = socket.rx.poll_wait(cx);
+
+        Poll::Pending
+    }
+
+    /// Same as `with_socket`, but registering the waker for send-side events.
+    fn with_socket_tx<R>(
+        &self,
+        cx: &mut Context<'_>,
+        f: impl FnOnce(&mut TcpSocketCtx) -> Option<Result<R, TcpError>>,
+    ) -> Poll<Result<R, TcpError>> {
+        let mut ot = self.ot.activate();
+        let socket = &mut ot.state().tcp()?.sockets[self.slot];
+
+        if let Some(res) = f(socket) {
+            return Poll::Ready(res);
+        }
+
+        socket.tx.reset();
+        let _ = socket.tx.poll_wait(cx);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for TcpSocket<'_> {
+    fn drop(&mut self) {
+        let mut ot = self.ot.activate();
+        let tcp = unwrap!(ot.state().tcp());
+
+        // Aborts the connection, if it is still alive
+        unwrap!(ot!(unsafe {
+            otTcpEndpointDeinitialize(&mut tcp.sockets[self.slot].endpoint)
+        }));
+
+        let socket = &mut tcp.sockets[self.slot];
+
+        unsafe {
+            otTcpCircularSendBufferForceDiscardAll(&mut socket.send_buffer);
+            otTcpCircularSendBufferDeinitialize(&mut socket.send_buffer);
+        }
+
+        socket.taken = false;
+    }
+}
+
+impl embedded_io_async::ErrorType for TcpSocket<'_> {
+    type Error = TcpError;
+}
+
+impl embedded_io_async::Read for TcpSocket<'_> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        TcpSocket::read(self, buf).await
+    }
+}
+
+impl embedded_io_async::Write for TcpSocket<'_> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        TcpSocket::write(self, buf).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        TcpSocket::flush(self).await
+    }
+}
+
+/// An OpenThread native TCP listener
+pub struct TcpListener<'a> {
+    /// The OpenThread stack that the listener is associated with.
+    ot: OpenThread<'a>,
+    /// The slot in the OpenThread stack's TCP listener array that this listener occupies.
+    slot: usize,
+}
+
+impl<'a> TcpListener<'a> {
+    /// Create a new TCP listener and bind it to the specified local address.
+    pub fn bind(ot: OpenThread<'a>, local: &SocketAddrV6) -> Result<Self, OtError> {
+        let slot = {
+            let mut active_ot = ot.activate();
+            let state = active_ot.state();
+            let instance = state.ot.instance;
+            let tcp = state.tcp()?;
+
+            let slot = tcp
+                .listeners
+                .iter()
+                .position(|listener| !listener.taken)
+                .ok_or(OtError::new(otError_OT_ERROR_NO_BUFS))?;
+
+            let listener = &mut tcp.listeners[slot];
+
+            let args = otTcpListenerInitializeArgs {
+                mContext: slot as *mut c_void,
+                mAcceptReadyCallback: Some(plat_c_tcp_accept_ready),
+                mAcceptDoneCallback: Some(plat_c_tcp_accept_done),
+            };
+
+            ot!(unsafe { otTcpListenerInitialize(instance, &mut listener.ot_listener, &args) })?;
+
+            let res = ot!(unsafe { otTcpListen(&mut listener.ot_listener, &to_ot_addr(local)) });
+            if res.is_err() {
+                unsafe { otTcpListenerDeinitialize(&mut listener.ot_listener) };
+            }
+
+            res?;
+
+            listener.accept_into = None;
+            listener.accepted.reset();
+            listener.taken = true;
+
+            slot
+        };
+
+        Ok(Self { ot, slot })
+    }
+
+    /// Accept an incoming connection.
+    /// If there is no incoming connection, this function will async-wait until there is one.
+    ///
+    /// Connection requests arriving while no `accept` call is pending are deferred
+    /// (i.e. the peer retries later).
+    ///
+    /// Returns:
+    /// - The connected socket.
+    /// - The address of the peer.
+    ///
+    /// NOTE:
+    /// It is not advised to call this method concurrently from multiple async tasks
+    /// because it uses a single waker registration. Thus, while the method will not panic,
+    /// the tasks will fight with each other by each re-registering its own waker, thus keeping the CPU constantly busy.
+    pub async fn accept(&self) -> Result<(TcpSocket<'a>, SocketAddrV6), TcpError> {
+        let socket = TcpSocket::new(self.ot.clone())?;
+
+        {
+            let mut ot = self.ot.activate();
+            let listener = &mut ot.state().tcp()?.listeners[self.slot];
+
+            listener.accept_into = Some(socket.slot);
+            listener.accepted.reset();
+        }
+
+        // Cancel-safety: stop accepting into the socket, which is about to be dropped.
+        // Defused on normal completion below.
+        let guard = scopeguard::guard((), |_| {
+            let mut ot = self.ot.activate();
+            let Ok(tcp) = ot.state().tcp() else {
+                return;
+            };
+
+            tcp.listeners[self.slot].accept_into = None;
+        });
+
+        let peer = poll_fn(|cx| {
+            self.ot.activate().state().tcp()?.listeners[self.slot]
+                .accepted
+                .poll_wait(cx)
+                .map(Ok::<_, OtError>)
+        })
+        .await?;
+
+        scopeguard::ScopeGuard::into_inner(guard);
+
+        debug!("TCP connection from {} accepted", peer);
+
+        Ok((socket, peer))
+    }
+}
+
+impl Drop for TcpListener<'_> {
+    fn drop(&mut self) {
+        let mut ot = self.ot.activate();
+        let tcp = unwrap!(ot.state().tcp());
+
+        let listener = &mut tcp.listeners[self.slot];
+
+        unsafe {
+            otTcpStopListening(&mut listener.ot_listener);
+            otTcpListenerDeinitialize(&mut listener.ot_listener);
+        }
+
+        listener.taken = false;
+    }
+}
+
+/// The resources (data) that is necessary for the OpenThread stack to operate with TCP sockets.
+///
+/// A separate type so that it can be allocated outside of the OpenThread futures,
+/// thus avoiding expensive mem-moves.
+///
+/// Can also be statically-allocated.
+///
+/// Parameters:
+/// - `TCP_SOCKETS`: The maximum number of simultaneously open `TcpSocket`s
+///   (including the ones being accepted).
+/// - `TCP_LISTENERS`: The maximum number of simultaneously open `TcpListener`s.
+/// - `TCP_RX_SZ`: The size of the receive buffer of each socket; determines the receive window.
+/// - `TCP_TX_SZ`: The size of the send buffer of each socket.
+pub struct OtTcpResources<
+    const TCP_SOCKETS: usize = 2,
+    const TCP_LISTENERS: usize = 1,
+    const TCP_RX_SZ: usize = 1280,
+    const TCP_TX_SZ: usize = 1280,
+> {
+    /// The TCP sockets that are available for use.
+    sockets: MaybeUninit<[TcpSocketCtx; TCP_SOCKETS]>,
+    /// The TCP listeners that are available for use.
+    listeners: MaybeUninit<[TcpListenerCtx; TCP_LISTENERS]>,
+    /// The receive buffers of the sockets.
+    rx_buffers: MaybeUninit<[[u8; TCP_RX_SZ]; TCP_SOCKETS]>,
+    /// The send buffers of the sockets.
+    tx_buffers: MaybeUninit<[[u8; TCP_TX_SZ]; TCP_SOCKETS]>,
+    /// The state of the OpenThread stack, from Rust POV.
+    state: MaybeUninit<RefCell<OtTcpState<'static>>>,
+}
+
+impl<
+        const TCP_SOCKETS: usize,
+        const TCP_LISTENERS: usize,
+        const TCP_RX_SZ: usize,
+        const TCP_TX_SZ: usize,
+    > OtTcpResources<TCP_SOCKETS, TCP_LISTENERS, TCP_RX_SZ, TCP_TX_SZ>
+{
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_SOCKET: TcpSocketCtx = TcpSocketCtx::new();
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_LISTENER: TcpListenerCtx = TcpListenerCtx::new();
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_RX_BUFFERS: [u8; TCP_RX_SZ] = [0; TCP_RX_SZ];
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_TX_BUFFERS: [u8; TCP_TX_SZ] = [0; TCP_TX_SZ];
+
+    /// Create a new `OtTcpResources` instance.
+    pub const fn new() -> Self {
+        Self {
+            sockets: MaybeUninit::uninit(),
+            listeners: MaybeUninit::uninit(),
+            rx_buffers: MaybeUninit::uninit(),
+            tx_buffers: MaybeUninit::uninit(),
+            state: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Initialize the resources, as they start their life as `MaybeUninit` so as to avoid mem-moves.
+    ///
+    /// Returns:
+    /// - A reference to a `RefCell<OtTcpState>` value that represents the initialized OpenThread TCP state.
+    pub(crate) fn init(&mut self) -> &RefCell<OtTcpState<'static>> {
+        self.sockets.write([Self::INIT_SOCKET; TCP_SOCKETS]);
+        self.listeners.write([Self::INIT_LISTENER; TCP_LISTENERS]);
+        self.rx_buffers.write([Self::INIT_RX_BUFFERS; TCP_SOCKETS]);
+        self.tx_buffers.write([Self::INIT_TX_BUFFERS; TCP_SOCKETS]);
+
+        let sockets = unsafe { self.sockets.assume_init_mut() };
+        let sockets = unsafe {
+            core::mem::transmute::<
+                &mut [TcpSocketCtx; TCP_SOCKETS],
+                &'static mut [TcpSocketCtx; TCP_SOCKETS],
+            >(sockets)
+        };
+
+        let listeners = unsafe { self.listeners.assume_init_mut() };
+        let listeners = unsafe {
+            core::mem::transmute::<
+                &mut [TcpListenerCtx; TCP_LISTENERS],
+                &'static mut [TcpListenerCtx; TCP_LISTENERS],
+            >(listeners)
+        };
+
+        let rx_buffers: &mut [[u8; TCP_RX_SZ]; TCP_SOCKETS] =
+            unsafe { self.rx_buffers.assume_init_mut() };
+        let rx_buffers: &'static mut [u8] = unsafe {
+            core::slice::from_raw_parts_mut(
+                rx_buffers.as_mut_ptr() as *mut _,
+                TCP_RX_SZ * TCP_SOCKETS,
+            )
+        };
+
+        let tx_buffers: &mut [[u8; TCP_TX_SZ]; TCP_SOCKETS] =
+            unsafe { self.tx_buffers.assume_init_mut() };
+        let tx_buffers: &'static mut [u8] = unsafe {
+            core::slice::from_raw_parts_mut(
+                tx_buffers.as_mut_ptr() as *mut _,
+                TCP_TX_SZ * TCP_SOCKETS,
+            )
+        };
+
+        self.state.write(RefCell::new(OtTcpState {
+            sockets,
+            listeners,
+            rx_buffers,
+            tx_buffers,
+            rx_buf_len: TCP_RX_SZ,
+            tx_buf_len: TCP_TX_SZ,
+        }));
+
+        info!("OpenThread TCP resources initialized");
+
+        unsafe { self.state.assume_init_mut() }
+    }
+}
+
+impl<
+        const TCP_SOCKETS: usize,
+        const TCP_LISTENERS: usize,
+        const TCP_RX_SZ: usize,
+        const TCP_TX_SZ: usize,
+    > Default for OtTcpResources<TCP_SOCKETS, TCP_LISTENERS, TCP_RX_SZ, TCP_TX_SZ>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The TCP state of the OpenThread stack, from Rust POV.
+///
+/// This data lives behind a `RefCell` and is mutably borrowed each time
+/// the OpenThread stack is activated, by creating an `OtContext` instance.
+pub(crate) struct OtTcpState<'a> {
+    /// The TCP sockets that are available for use.
+    sockets: &'a mut [TcpSocketCtx],
+    /// The TCP listeners that are available for use.
+    listeners: &'a mut [TcpListenerCtx],
+    /// The receive buffers of the sockets.
+    rx_buffers: &'a mut [u8],
+    /// The send buffers of the sockets.
+    tx_buffers: &'a mut [u8],
+    /// The length of each receive buffer.
+    rx_buf_len: usize,
+    /// The length of each send buffer.
+    tx_buf_len: usize,
+}
+
+/// The internal data associated with each `TcpSocket` instance.
+pub(crate) struct TcpSocketCtx {
+    /// Whether the data (slot) is taken by a `TcpSocket` instance or not.
+    taken: bool,
+    /// The OpenThread native TCP endpoint.
+    endpoint: otTcpEndpoint,
+    /// The send buffer of the endpoint, backed by the socket's slice of `OtTcpState::tx_buffers`.
+    send_buffer: otTcpCircularSendBuffer,
+    /// Whether the connection is established.
+    established: bool,
+    /// Whether the peer closed its side of the connection.
+    eof: bool,
+    /// Whether we closed our side of the connection.
+    closing: bool,
+    /// The error to report, once the connection is gone.
+    disconnected: Option<TcpError>,
+    /// The signal that is triggered when data is received or the connection state changes.
+    rx: Signal<()>,
+    /// The signal that is triggered when sent data is acknowledged or the connection state changes.
+    tx: Signal<()>,
+}
+
+impl TcpSocketCtx {
+    /// Create a new `TcpSocketCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            taken: false,
+            // Plain C structs, which are all-zeroes when not initialized
+            endpoint: unsafe { core::mem::zeroed() },
+            send_buffer: unsafe { core::mem::zeroed() },
+            established: false,
+            eof: false,
+            closing: false,
+            disconnected: None,
+            rx: Signal::new(),
+            tx: Signal::new(),
+        }
+    }
+
+    /// Copy the received data into `buf` and mark it as consumed.
+    ///
+    /// Returns the number of bytes copied.
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, OtError> {
+        let mut data: *const otLinkedBuffer = core::ptr::null();
+        ot!(unsafe { otTcpReceiveByReference(&mut self.endpoint, &mut data) })?;
+
+        let mut len = 0;
+
+        while !data.is_null() && len < buf.len() {
+            let link = unsafe { &*data };
+            let chunk = link.mLength.min(buf.len() - len);
+
+            if chunk > 0 {
+                buf[len..len + chunk]
+                    .copy_from_slice(unsafe { core::slice::from_raw_parts(link.mData, chunk) });
+                len += chunk;
+            }
+
+            data = link.mNext;
+        }
+
+        if len > 0 {
+            ot!(unsafe { otTcpCommitReceive(&mut self.endpoint, len, 0) })?;
+        }
+
+        Ok(len)
+    }
+}
+
+/// The internal data associated with each `TcpListener` instance.
+pub(crate) struct TcpListenerCtx {
+    /// Whether the data (slot) is taken by a `TcpListener` instance or not.
+    taken: bool,
+    /// The OpenThread native TCP listener.
+    ot_listener: otTcpListener,
+    /// The slot of the socket the next incoming connection should be accepted into, if any.
+    accept_into: Option<usize>,
+    /// The signal that is triggered when an incoming connection is established.
+    accepted: Signal<SocketAddrV6>,
+}
+
+impl TcpListenerCtx {
+    /// Create a new `TcpListenerCtx` instance.
+    pub(crate) const fn new() -> Self {
+        Self {
+            taken: false,
+            // A plain C struct, which is all-zeroes when not initialized
+            ot_listener: unsafe { core::mem::zeroed() },
+            accept_into: None,
+            accepted: Signal::new(),
+        }
+    }
+}
+
+/// Return the slot of the socket of a TCP endpoint.
+fn endpoint_slot(endpoint: *mut otTcpEndpoint) -> usize {
+    unsafe { otTcpEndpointGetContext(endpoint) as usize }
+}
+
+unsafe extern "C" fn plat_c_tcp_established(endpoint: *mut otTcpEndpoint) {
+    let slot = endpoint_slot(endpoint);
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        // We cannot have TCP endpoints if there are no TCP resources
+        unreachable!();
+    };
+
+    let socket = &mut tcp.sockets[slot];
+    socket.established = true;
+    socket.rx.signal(());
+    socket.tx.signal(());
+}
+
+unsafe extern "C" fn plat_c_tcp_forward_progress(
+    endpoint: *mut otTcpEndpoint,
+    in_send_buffer: usize,
+    _backlog: usize,
+) {
+    let slot = endpoint_slot(endpoint);
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        unreachable!();
+    };
+
+    let socket = &mut tcp.sockets[slot];
+
+    unsafe {
+        otTcpCircularSendBufferHandleForwardProgress(&mut socket.send_buffer, in_send_buffer)
+    };
+
+    socket.tx.signal(());
+}
+
+unsafe extern "C" fn plat_c_tcp_receive_available(
+    endpoint: *mut otTcpEndpoint,
+    _bytes_available: usize,
+    end_of_stream: bool,
+    _bytes_remaining: usize,
+) {
+    let slot = endpoint_slot(endpoint);
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        unreachable!();
+    };
+
+    let socket = &mut tcp.sockets[slot];
+    socket.eof |= end_of_stream;
+    socket.rx.signal(());
+}
+
+unsafe extern "C" fn plat_c_tcp_disconnected(
+    endpoint: *mut otTcpEndpoint,
+    reason: otTcpDisconnectedReason,
+) {
+    let slot = endpoint_slot(endpoint);
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        unreachable!();
+    };
+
+    debug!("TCP connection closed, reason: {}", reason);
+
+    let socket = &mut tcp.sockets[slot];
+    socket.disconnected = Some(TcpError::from_reason(reason));
+    socket.rx.signal(());
+    socket.tx.signal(());
+}
+
+unsafe extern "C" fn plat_c_tcp_accept_ready(
+    listener: *mut otTcpListener,
+    _peer: *const otSockAddr,
+    accept_into: *mut *mut otTcpEndpoint,
+) -> otTcpIncomingConnectionAction {
+    let slot = unsafe { otTcpListenerGetContext(listener) as usize };
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        unreachable!();
+    };
+
+    let Some(socket_slot) = tcp.listeners[slot].accept_into.take() else {
+        // Nobody is accepting right now; the peer will retry
+        return otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_DEFER;
+    };
+
+    unsafe { *accept_into = &mut tcp.sockets[socket_slot].endpoint };
+
+    otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT
+}
+
+unsafe extern "C" fn plat_c_tcp_accept_done(
+    listener: *mut otTcpListener,
+    endpoint: *mut otTcpEndpoint,
+    peer: *const otSockAddr,
+) {
+    let slot = unsafe { otTcpListenerGetContext(listener) as usize };
+    let socket_slot = endpoint_slot(endpoint);
+
+    let mut ot = OtContext::callback(core::ptr::null_mut());
+    let Ok(tcp) = ot.state().tcp() else {
+        unreachable!();
+    };
+
+    tcp.sockets[socket_slot].established = true;
+
+    let peer = unsafe { &*peer };
+    tcp.listeners[slot]
+        .accepted
+        .signal(to_sock_addr(&peer.mAddress, peer.mPort, 0));
+}